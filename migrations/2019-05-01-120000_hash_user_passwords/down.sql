@@ -0,0 +1,8 @@
+-- This file should undo anything in `up.sql`
+-- Irreversible: hashes can't be turned back into plaintext, and renaming the column back
+-- while it holds hashes would lock every account out. Restore a backup instead.
+DO $$
+BEGIN
+  RAISE EXCEPTION 'hash_user_passwords can''t be reverted, the passwords are hashed';
+END
+$$;
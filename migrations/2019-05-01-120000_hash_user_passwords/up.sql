@@ -0,0 +1,10 @@
+-- Passwords are stored as Argon2id hashes in PHC string format
+ALTER TABLE users RENAME COLUMN password TO password_hash;
+
+-- The seeded admin/admin account
+UPDATE users
+SET password_hash = '$argon2id$v=19$m=4096,t=3,p=1$p3L5zJE4dKRYHiJbe4aYKQ$ZX9vw+OLVBSVN8czzkghJe1XugwXFxOyZQ0XLT5OLKM'
+WHERE login = 'admin' AND password_hash = 'admin';
+
+-- Any other plaintext rows are rehashed by the server on startup,
+-- see `login::password::upgrade_plaintext_passwords`.
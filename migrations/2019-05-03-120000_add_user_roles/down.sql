@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE parent_students;
+DROP TABLE teacher_classes;
+ALTER TABLE users DROP COLUMN student_id, DROP COLUMN role;
@@ -0,0 +1,22 @@
+-- Your SQL goes here
+ALTER TABLE users
+  ADD COLUMN role TEXT NOT NULL DEFAULT 'student'
+    CHECK (role IN ('admin', 'teacher', 'parent', 'student')),
+  -- The student row a `student` account belongs to
+  ADD COLUMN student_id INTEGER REFERENCES students(id) ON DELETE SET NULL;
+
+UPDATE users SET role = 'admin' WHERE login = 'admin';
+
+-- Classes whose students a teacher may add and edit
+CREATE TABLE teacher_classes (
+  user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+  class TEXT NOT NULL,
+  PRIMARY KEY (user_id, class)
+);
+
+-- Children a parent may see
+CREATE TABLE parent_students (
+  user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+  student_id INTEGER NOT NULL REFERENCES students(id) ON DELETE CASCADE,
+  PRIMARY KEY (user_id, student_id)
+);
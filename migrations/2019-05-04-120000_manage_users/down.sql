@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE users
+  DROP CONSTRAINT users_login_key,
+  DROP COLUMN active;
@@ -0,0 +1,5 @@
+-- Your SQL goes here
+ALTER TABLE users
+  ADD CONSTRAINT users_login_key UNIQUE (login),
+  -- Deactivated accounts can't log in, but keep their history
+  ADD COLUMN active BOOLEAN NOT NULL DEFAULT TRUE;
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE grades;
+DROP TABLE subjects;
@@ -0,0 +1,21 @@
+-- Your SQL goes here
+CREATE TABLE subjects (
+  id SERIAL PRIMARY KEY,
+  name TEXT NOT NULL UNIQUE
+);
+
+-- Polish 1-6 scale, a `+` adds half a grade and a `-` takes away a quarter
+CREATE TABLE grades (
+  id SERIAL PRIMARY KEY,
+  student_id INTEGER NOT NULL REFERENCES students(id) ON DELETE CASCADE,
+  subject_id INTEGER NOT NULL REFERENCES subjects(id),
+  value SMALLINT NOT NULL CHECK (value BETWEEN 1 AND 6),
+  modifier TEXT CHECK (modifier IN ('+', '-')),
+  weight SMALLINT NOT NULL DEFAULT 1 CHECK (weight > 0),
+  category TEXT NOT NULL CHECK (category IN ('test', 'quiz', 'homework')),
+  date DATE NOT NULL DEFAULT CURRENT_DATE,
+  -- Who gave the grade
+  teacher_id INTEGER REFERENCES users(id) ON DELETE SET NULL
+);
+
+CREATE INDEX grades_student_id_idx ON grades(student_id);
@@ -0,0 +1,16 @@
+-- Your SQL goes here
+CREATE TABLE attendance (
+  id SERIAL PRIMARY KEY,
+  student_id INTEGER NOT NULL REFERENCES students(id) ON DELETE CASCADE,
+  subject_id INTEGER NOT NULL REFERENCES subjects(id),
+  date DATE NOT NULL,
+  lesson_number SMALLINT NOT NULL CHECK (lesson_number BETWEEN 0 AND 12),
+  status TEXT NOT NULL
+    CHECK (status IN ('present', 'absent', 'late', 'excused', 'released')),
+  -- Who took the attendance
+  teacher_id INTEGER REFERENCES users(id) ON DELETE SET NULL,
+  -- One record per student per lesson, entering it again overwrites it
+  CONSTRAINT attendance_lesson_key UNIQUE (student_id, date, lesson_number)
+);
+
+CREATE INDEX attendance_date_idx ON attendance(date);
@@ -0,0 +1,16 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE teacher_classes ADD COLUMN class TEXT;
+UPDATE teacher_classes SET class = classes.name
+FROM classes WHERE classes.id = teacher_classes.class_id;
+ALTER TABLE teacher_classes DROP CONSTRAINT teacher_classes_pkey;
+ALTER TABLE teacher_classes DROP COLUMN class_id;
+ALTER TABLE teacher_classes ALTER COLUMN class SET NOT NULL;
+ALTER TABLE teacher_classes ADD PRIMARY KEY (user_id, class);
+
+ALTER TABLE students ADD COLUMN class TEXT;
+UPDATE students SET class = classes.name
+FROM classes WHERE classes.id = students.class_id;
+ALTER TABLE students DROP COLUMN class_id;
+ALTER TABLE students ALTER COLUMN class SET NOT NULL;
+
+DROP TABLE classes;
@@ -0,0 +1,40 @@
+-- Your SQL goes here
+CREATE TABLE classes (
+  id SERIAL PRIMARY KEY,
+  name TEXT NOT NULL,
+  -- e.g. '2018/2019'
+  school_year TEXT NOT NULL,
+  homeroom_teacher_id INTEGER REFERENCES users(id) ON DELETE SET NULL,
+  UNIQUE (name, school_year)
+);
+
+-- Every distinct class name becomes a class of the current school year.
+-- Names are trimmed and lowercased so ' 3D' and '3d' end up as one class.
+INSERT INTO classes (name, school_year)
+SELECT DISTINCT lower(trim(class)),
+  CASE WHEN EXTRACT(MONTH FROM CURRENT_DATE) >= 9
+    THEN EXTRACT(YEAR FROM CURRENT_DATE)::INTEGER || '/' || (EXTRACT(YEAR FROM CURRENT_DATE)::INTEGER + 1)
+    ELSE (EXTRACT(YEAR FROM CURRENT_DATE)::INTEGER - 1) || '/' || EXTRACT(YEAR FROM CURRENT_DATE)::INTEGER
+  END
+FROM (
+  SELECT class FROM students
+  UNION
+  SELECT class FROM teacher_classes
+) AS class_names;
+
+ALTER TABLE students ADD COLUMN class_id INTEGER REFERENCES classes(id);
+UPDATE students SET class_id = classes.id
+FROM classes WHERE classes.name = lower(trim(students.class));
+ALTER TABLE students ALTER COLUMN class_id SET NOT NULL;
+ALTER TABLE students DROP COLUMN class;
+
+ALTER TABLE teacher_classes ADD COLUMN class_id INTEGER REFERENCES classes(id) ON DELETE CASCADE;
+UPDATE teacher_classes SET class_id = classes.id
+FROM classes WHERE classes.name = lower(trim(teacher_classes.class));
+ALTER TABLE teacher_classes DROP CONSTRAINT teacher_classes_pkey;
+ALTER TABLE teacher_classes DROP COLUMN class;
+-- Two spellings of one class could've been assigned to the same teacher
+DELETE FROM teacher_classes a USING teacher_classes b
+WHERE a.ctid < b.ctid AND a.user_id = b.user_id AND a.class_id = b.class_id;
+ALTER TABLE teacher_classes ALTER COLUMN class_id SET NOT NULL;
+ALTER TABLE teacher_classes ADD PRIMARY KEY (user_id, class_id);
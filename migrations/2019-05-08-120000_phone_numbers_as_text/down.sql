@@ -0,0 +1,11 @@
+-- This file should undo anything in `up.sql`
+-- Numbers that don't fit in an INTEGER become 0.
+ALTER TABLE students DROP CONSTRAINT students_phone_number_e164;
+ALTER TABLE students ALTER COLUMN phone_number TYPE INTEGER USING
+  CASE
+    WHEN phone_number LIKE '+48%' AND length(phone_number) <= 12
+      THEN substr(phone_number, 4)::INTEGER
+    WHEN length(phone_number) <= 10
+      THEN substr(phone_number, 2)::INTEGER
+    ELSE 0
+  END;
@@ -0,0 +1,10 @@
+-- Your SQL goes here
+-- Stored as E.164, e.g. '+48123456789'.
+-- The integers had no country code, so up to nine digits are assumed to be Polish.
+ALTER TABLE students ALTER COLUMN phone_number TYPE TEXT USING
+  CASE WHEN length(abs(phone_number)::TEXT) <= 9
+    THEN '+48' || abs(phone_number)::TEXT
+    ELSE '+' || abs(phone_number)::TEXT
+  END;
+ALTER TABLE students ADD CONSTRAINT students_phone_number_e164
+  CHECK (phone_number ~ '^\+[1-9][0-9]{1,14}$');
@@ -0,0 +1,11 @@
+-- This file should undo anything in `up.sql`
+CREATE TABLE parent_students (
+  user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+  student_id INTEGER NOT NULL REFERENCES students(id) ON DELETE CASCADE,
+  PRIMARY KEY (user_id, student_id)
+);
+
+INSERT INTO parent_students (user_id, student_id)
+SELECT user_id, student_id FROM guardians;
+
+DROP TABLE guardians;
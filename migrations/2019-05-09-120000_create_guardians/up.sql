@@ -0,0 +1,17 @@
+-- Your SQL goes here
+-- Replaces `parent_students`, a student can have many guardians and the other way round
+CREATE TABLE guardians (
+  user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+  student_id INTEGER NOT NULL REFERENCES students(id) ON DELETE CASCADE,
+  relationship TEXT NOT NULL DEFAULT 'guardian'
+    CHECK (relationship IN ('mother', 'father', 'guardian', 'other')),
+  -- E.164, like `students.phone_number`
+  phone_number TEXT CHECK (phone_number ~ '^\+[1-9][0-9]{1,14}$'),
+  email TEXT,
+  PRIMARY KEY (user_id, student_id)
+);
+
+INSERT INTO guardians (user_id, student_id)
+SELECT user_id, student_id FROM parent_students;
+
+DROP TABLE parent_students;
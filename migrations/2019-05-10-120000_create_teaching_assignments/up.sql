@@ -0,0 +1,13 @@
+-- Your SQL goes here
+-- Who teaches which subject to which class
+CREATE TABLE teaching_assignments (
+  id SERIAL PRIMARY KEY,
+  teacher_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+  subject_id INTEGER NOT NULL REFERENCES subjects(id) ON DELETE CASCADE,
+  class_id INTEGER NOT NULL REFERENCES classes(id) ON DELETE CASCADE,
+  -- e.g. '2018/2019'
+  school_year TEXT NOT NULL,
+  CONSTRAINT teaching_assignments_key UNIQUE (teacher_id, subject_id, class_id, school_year)
+);
+
+CREATE INDEX teaching_assignments_class_idx ON teaching_assignments (class_id, subject_id);
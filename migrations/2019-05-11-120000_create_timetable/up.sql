@@ -0,0 +1,37 @@
+-- Your SQL goes here
+-- When each lesson of the day starts and ends
+CREATE TABLE lesson_times (
+  lesson_number SMALLINT PRIMARY KEY CHECK (lesson_number >= 0),
+  starts_at TIME NOT NULL,
+  ends_at TIME NOT NULL,
+  CHECK (starts_at < ends_at)
+);
+
+INSERT INTO lesson_times (lesson_number, starts_at, ends_at) VALUES
+(0, '07:10', '07:55'),
+(1, '08:00', '08:45'),
+(2, '08:55', '09:40'),
+(3, '09:50', '10:35'),
+(4, '10:45', '11:30'),
+(5, '11:50', '12:35'),
+(6, '12:45', '13:30'),
+(7, '13:40', '14:25'),
+(8, '14:35', '15:20'),
+(9, '15:30', '16:15');
+
+-- The weekly lesson plan. The school year is copied from the class,
+-- so a teacher or room is only double-booked within one school year.
+CREATE TABLE timetable (
+  id SERIAL PRIMARY KEY,
+  class_id INTEGER NOT NULL REFERENCES classes(id) ON DELETE CASCADE,
+  school_year TEXT NOT NULL,
+  -- ISO, 1 is Monday
+  weekday SMALLINT NOT NULL CHECK (weekday BETWEEN 1 AND 7),
+  lesson_number SMALLINT NOT NULL REFERENCES lesson_times(lesson_number),
+  subject_id INTEGER NOT NULL REFERENCES subjects(id) ON DELETE CASCADE,
+  teacher_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+  room TEXT,
+  CONSTRAINT timetable_class_slot_key UNIQUE (class_id, weekday, lesson_number),
+  CONSTRAINT timetable_teacher_slot_key UNIQUE (teacher_id, school_year, weekday, lesson_number),
+  CONSTRAINT timetable_room_slot_key UNIQUE (room, school_year, weekday, lesson_number)
+);
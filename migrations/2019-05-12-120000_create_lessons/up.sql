@@ -0,0 +1,19 @@
+-- Your SQL goes here
+-- The lesson log, what was taught during each held lesson
+CREATE TABLE lessons (
+  id SERIAL PRIMARY KEY,
+  class_id INTEGER NOT NULL REFERENCES classes(id) ON DELETE CASCADE,
+  subject_id INTEGER NOT NULL REFERENCES subjects(id) ON DELETE CASCADE,
+  teacher_id INTEGER REFERENCES users(id) ON DELETE SET NULL,
+  date DATE NOT NULL,
+  lesson_number SMALLINT NOT NULL REFERENCES lesson_times(lesson_number),
+  topic TEXT NOT NULL,
+  homework TEXT,
+  homework_due DATE,
+  CONSTRAINT lessons_class_slot_key UNIQUE (class_id, date, lesson_number),
+  -- homework always has a due date
+  CHECK ((homework IS NULL) = (homework_due IS NULL)),
+  CHECK (homework_due >= date)
+);
+
+CREATE INDEX lessons_homework_due_idx ON lessons (class_id, homework_due) WHERE homework IS NOT NULL;
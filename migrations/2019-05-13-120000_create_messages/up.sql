@@ -0,0 +1,26 @@
+-- Your SQL goes here
+CREATE TABLE messages (
+  id SERIAL PRIMARY KEY,
+  sender_id INTEGER REFERENCES users(id) ON DELETE SET NULL,
+  -- the first message of the thread, NULL if this is the first one
+  thread_id INTEGER REFERENCES messages(id) ON DELETE CASCADE,
+  subject TEXT NOT NULL,
+  body TEXT NOT NULL,
+  sent_at TIMESTAMP NOT NULL DEFAULT now(),
+  -- hidden from the sender's outbox
+  sender_archived BOOLEAN NOT NULL DEFAULT FALSE
+);
+
+CREATE INDEX messages_sender_idx ON messages (sender_id, sent_at);
+CREATE INDEX messages_thread_idx ON messages (thread_id);
+
+-- Who got the message, read receipts and the recipient's own archive
+CREATE TABLE message_recipients (
+  message_id INTEGER NOT NULL REFERENCES messages(id) ON DELETE CASCADE,
+  user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+  read_at TIMESTAMP,
+  archived BOOLEAN NOT NULL DEFAULT FALSE,
+  PRIMARY KEY (message_id, user_id)
+);
+
+CREATE INDEX message_recipients_user_idx ON message_recipients (user_id, archived);
@@ -0,0 +1,20 @@
+-- Your SQL goes here
+CREATE TABLE announcements (
+  id SERIAL PRIMARY KEY,
+  author_id INTEGER REFERENCES users(id) ON DELETE SET NULL,
+  -- `school`, `class` or `role`
+  audience TEXT NOT NULL CHECK (audience IN ('school', 'class', 'role')),
+  class_id INTEGER REFERENCES classes(id) ON DELETE CASCADE,
+  role TEXT CHECK (role IN ('admin', 'teacher', 'parent', 'student')),
+  title TEXT NOT NULL,
+  body TEXT NOT NULL,
+  pinned BOOLEAN NOT NULL DEFAULT FALSE,
+  publish_at TIMESTAMP NOT NULL DEFAULT now(),
+  expires_at TIMESTAMP,
+  created_at TIMESTAMP NOT NULL DEFAULT now(),
+  CHECK ((audience = 'class') = (class_id IS NOT NULL)),
+  CHECK ((audience = 'role') = (role IS NOT NULL)),
+  CHECK (expires_at > publish_at)
+);
+
+CREATE INDEX announcements_publish_idx ON announcements (publish_at, expires_at);
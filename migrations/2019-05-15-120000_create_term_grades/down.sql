@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE behavior_grades;
+DROP TABLE final_grades;
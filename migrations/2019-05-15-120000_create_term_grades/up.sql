@@ -0,0 +1,21 @@
+-- Your SQL goes here
+-- What goes on the report card at the end of each term, 1 or 2
+CREATE TABLE final_grades (
+  student_id INTEGER NOT NULL REFERENCES students(id) ON DELETE CASCADE,
+  subject_id INTEGER NOT NULL REFERENCES subjects(id) ON DELETE CASCADE,
+  school_year TEXT NOT NULL,
+  term SMALLINT NOT NULL CHECK (term IN (1, 2)),
+  value SMALLINT NOT NULL CHECK (value BETWEEN 1 AND 6),
+  teacher_id INTEGER REFERENCES users(id) ON DELETE SET NULL,
+  PRIMARY KEY (student_id, subject_id, school_year, term)
+);
+
+CREATE TABLE behavior_grades (
+  student_id INTEGER NOT NULL REFERENCES students(id) ON DELETE CASCADE,
+  school_year TEXT NOT NULL,
+  term SMALLINT NOT NULL CHECK (term IN (1, 2)),
+  grade TEXT NOT NULL
+    CHECK (grade IN ('exemplary', 'very_good', 'good', 'acceptable', 'unacceptable', 'reprehensible')),
+  teacher_id INTEGER REFERENCES users(id) ON DELETE SET NULL,
+  PRIMARY KEY (student_id, school_year, term)
+);
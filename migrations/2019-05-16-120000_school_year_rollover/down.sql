@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE students DROP COLUMN graduated_in;
+DROP TABLE closed_school_years;
@@ -0,0 +1,11 @@
+-- Your SQL goes here
+-- Grades, attendance and term grades of a closed school year can't be changed anymore
+CREATE TABLE closed_school_years (
+  -- e.g. '2018/2019'
+  school_year TEXT PRIMARY KEY,
+  closed_at TIMESTAMP NOT NULL DEFAULT now(),
+  closed_by INTEGER REFERENCES users(id) ON DELETE SET NULL
+);
+
+-- Graduates stay in their last class, with the school year they finished
+ALTER TABLE students ADD COLUMN graduated_in TEXT;
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX students_deleted_at_idx;
+ALTER TABLE students DROP COLUMN deleted_at;
@@ -0,0 +1,4 @@
+-- Your SQL goes here
+-- Deleted students are kept, so they can be restored, until they're purged
+ALTER TABLE students ADD COLUMN deleted_at TIMESTAMP;
+CREATE INDEX students_deleted_at_idx ON students (deleted_at) WHERE deleted_at IS NOT NULL;
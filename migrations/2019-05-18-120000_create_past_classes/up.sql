@@ -0,0 +1,10 @@
+-- Your SQL goes here
+-- The classes students were in during earlier school years, the rollover adds a row
+-- before moving a class on. The current class is still students.class_id.
+CREATE TABLE past_classes (
+  student_id INTEGER NOT NULL REFERENCES students(id) ON DELETE CASCADE,
+  class_id INTEGER NOT NULL REFERENCES classes(id) ON DELETE CASCADE,
+  PRIMARY KEY (student_id, class_id)
+);
+
+CREATE INDEX past_classes_class_id_idx ON past_classes(class_id);
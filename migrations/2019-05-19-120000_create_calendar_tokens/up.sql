@@ -0,0 +1,8 @@
+-- Your SQL goes here
+-- Tokens for subscribing to the .ics timetable feeds by URL. Unlike sessions they don't
+-- expire, every user has at most one and it only works on the calendar routes.
+CREATE TABLE calendar_tokens (
+  token TEXT PRIMARY KEY,
+  user_id INTEGER NOT NULL UNIQUE REFERENCES users(id) ON DELETE CASCADE,
+  created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE attendance DROP CONSTRAINT attendance_lesson_number_fkey;
+ALTER TABLE attendance ADD CONSTRAINT attendance_lesson_number_check
+  CHECK (lesson_number BETWEEN 0 AND 12);
@@ -0,0 +1,6 @@
+-- Your SQL goes here
+-- Attendance is taken for the lessons in lesson_times, like the timetable and the lesson log,
+-- instead of its own range. Fails if attendance was taken for a lesson that isn't there.
+ALTER TABLE attendance DROP CONSTRAINT attendance_lesson_number_check;
+ALTER TABLE attendance ADD CONSTRAINT attendance_lesson_number_fkey
+  FOREIGN KEY (lesson_number) REFERENCES lesson_times(lesson_number);
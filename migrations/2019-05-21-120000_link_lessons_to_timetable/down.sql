@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE attendance DROP COLUMN timetable_id;
+ALTER TABLE lessons DROP COLUMN timetable_id;
@@ -0,0 +1,31 @@
+-- Your SQL goes here
+-- The planned lesson of the class in the timetable that a logged lesson or attendance
+-- was held in, NULL for lessons outside of the plan. Deleting the planned lesson keeps the log.
+ALTER TABLE lessons ADD COLUMN timetable_id INTEGER REFERENCES timetable(id) ON DELETE SET NULL;
+ALTER TABLE attendance ADD COLUMN timetable_id INTEGER REFERENCES timetable(id) ON DELETE SET NULL;
+
+-- School years start in September, like `classes::school_year_of`
+UPDATE lessons SET timetable_id = timetable.id
+FROM timetable
+WHERE timetable.class_id = lessons.class_id
+  AND timetable.weekday = EXTRACT(ISODOW FROM lessons.date)
+  AND timetable.lesson_number = lessons.lesson_number
+  AND timetable.school_year = (
+    EXTRACT(YEAR FROM lessons.date - INTERVAL '8 months')::INTEGER::TEXT || '/'
+    || (EXTRACT(YEAR FROM lessons.date - INTERVAL '8 months')::INTEGER + 1)::TEXT
+  );
+
+-- The student's class then is either their current one or one from past_classes,
+-- the school year tells them apart
+UPDATE attendance SET timetable_id = timetable.id
+FROM students, timetable
+WHERE students.id = attendance.student_id
+  AND (timetable.class_id = students.class_id OR timetable.class_id IN (
+    SELECT past_classes.class_id FROM past_classes WHERE past_classes.student_id = attendance.student_id
+  ))
+  AND timetable.weekday = EXTRACT(ISODOW FROM attendance.date)
+  AND timetable.lesson_number = attendance.lesson_number
+  AND timetable.school_year = (
+    EXTRACT(YEAR FROM attendance.date - INTERVAL '8 months')::INTEGER::TEXT || '/'
+    || (EXTRACT(YEAR FROM attendance.date - INTERVAL '8 months')::INTEGER + 1)::TEXT
+  );
@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+-- The hashes can't be turned back into tokens
+DELETE FROM calendar_tokens;
+ALTER TABLE calendar_tokens RENAME COLUMN token_hash TO token;
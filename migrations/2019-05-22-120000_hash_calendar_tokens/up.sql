@@ -0,0 +1,5 @@
+-- Your SQL goes here
+-- Only the SHA-256 of a calendar token is stored. The existing tokens can't be hashed
+-- here without pgcrypto, so they're revoked and have to be issued again.
+DELETE FROM calendar_tokens;
+ALTER TABLE calendar_tokens RENAME COLUMN token TO token_hash;
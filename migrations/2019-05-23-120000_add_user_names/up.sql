@@ -0,0 +1,6 @@
+-- Your SQL goes here
+-- Names to print instead of the login, e.g. the homeroom teacher on report cards.
+-- Existing accounts don't have them until an admin fills them in.
+ALTER TABLE users
+  ADD COLUMN first_name TEXT,
+  ADD COLUMN last_name TEXT;
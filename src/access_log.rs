@@ -0,0 +1,91 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+//!
+//! The access log. Unlike `middleware::Logger` it doesn't write the values of secret
+//! query parameters, like the calendar tokens of the `.ics` feeds, to the logs.
+
+use std::time::Instant;
+
+use actix_web::{
+    HttpRequest,
+    HttpResponse,
+    middleware::{Finished, Middleware, Started},
+};
+
+#[allow(unused_imports)] // it's useful to have these in scope
+use log::{debug, error, info, warn};
+
+/// Query parameters that are logged as `REDACTED`.
+const SECRET_PARAMETERS: &[&str] = &["token"];
+
+/// When the request came in.
+struct StartTime(Instant);
+
+/// Logs `remote "METHOD path?query" status size seconds` for every request, e.g.
+/// `127.0.0.1 "GET /api/classes/1/timetable.ics?token=REDACTED" 200 1024 0.012000`.
+pub struct AccessLog;
+
+impl<S> Middleware<S> for AccessLog {
+    fn start(&self, req: &HttpRequest<S>) -> actix_web::Result<Started> {
+        req.extensions_mut().insert(StartTime(Instant::now()));
+        Ok(Started::Done)
+    }
+
+    fn finish(&self, req: &HttpRequest<S>, resp: &HttpResponse) -> Finished {
+        let seconds = req.extensions().get::<StartTime>().map_or(0.0, |start| {
+            let took = start.0.elapsed();
+            took.as_secs() as f64 + f64::from(took.subsec_micros()) / 1_000_000.0
+        });
+        let query = req.query_string();
+        let target = if query.is_empty() {
+            req.path().to_string()
+        } else {
+            format!("{}?{}", req.path(), redact(query))
+        };
+        let connection = req.connection_info();
+        info!(
+            "{} \"{} {}\" {} {} {:.6}",
+            connection.remote().unwrap_or("-"), req.method(), target,
+            resp.status().as_u16(), resp.response_size(), seconds
+        );
+        Finished::Done
+    }
+}
+
+/// The query with the values of `SECRET_PARAMETERS` replaced. The names are compared decoded,
+/// the way they're read, and parameters that can't be decoded are redacted too.
+fn redact(query: &str) -> String {
+    query
+        .split('&')
+        .map(|pair| {
+            let secret = match serde_urlencoded::from_str::<Vec<(String, String)>>(pair) {
+                Ok(parsed) => parsed.iter().any(|(name, _)| SECRET_PARAMETERS.contains(&name.as_str())),
+                Err(_) => true,
+            };
+            if secret {
+                format!("{}=REDACTED", pair.split('=').next().unwrap_or_default())
+            } else {
+                pair.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redacts_tokens() {
+        assert_eq!(redact("token=abc"), "token=REDACTED");
+        assert_eq!(redact("school_year=2018%2F2019&token=abc"), "school_year=2018%2F2019&token=REDACTED");
+        assert_eq!(redact("tok%65n=abc"), "tok%65n=REDACTED");
+    }
+
+    #[test]
+    fn keeps_the_rest() {
+        assert_eq!(redact("from=2019-01-01&to=2019-02-01"), "from=2019-01-01&to=2019-02-01");
+        assert_eq!(redact("tokens=1"), "tokens=1");
+    }
+}
@@ -0,0 +1,15 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+extern crate serde_derive;
+extern crate actix_web;
+
+mod models;
+
+pub use models::{
+    create,
+    read,
+    update,
+    delete,
+    feed
+};
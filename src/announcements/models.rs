@@ -0,0 +1,74 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use crate::schema::announcements;
+use crate::database::Database;
+use crate::error::ApiError;
+use crate::login::{CurrentUser, Role};
+use actix_web::actix::{Message, Handler};
+use chrono::NaiveDateTime;
+use diesel;
+use diesel::pg::PgConnection;
+
+#[allow(unused_imports)] // Throws errors without this import, but throws warning with it :/
+use diesel::prelude::*;
+
+mod imports;
+
+mod audience;
+pub use audience::Audience;
+
+/// `class_id` is only set for the `class` audience and `role` only for the `role` one.
+///
+/// Shown between `publish_at` and `expires_at`, pinned ones first.
+#[derive(Queryable, Serialize, Deserialize, Debug)]
+pub struct Announcement {
+    pub id: i32,
+    pub author_id: Option<i32>,
+    pub audience: Audience,
+    pub class_id: Option<i32>,
+    pub role: Option<Role>,
+    pub title: String,
+    pub body: String,
+    pub pinned: bool,
+    pub publish_at: NaiveDateTime,
+    pub expires_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime
+}
+
+pub const MAX_TITLE_LENGTH: usize = 200;
+pub const MAX_BODY_LENGTH: usize = 10000;
+
+/// Admins may change every announcement, teachers only their own.
+fn require_author(conn: &PgConnection, user: &CurrentUser, id: i32) -> Result<Announcement, ApiError> {
+    let announcement = announcements::table
+        .find(id)
+        .first::<Announcement>(conn)
+        .optional()?
+        .ok_or_else(|| ApiError::NotFound(format!("Announcement with id of {} not found.", id)))?;
+    if user.role == Role::Admin || announcement.author_id == Some(user.id) {
+        Ok(announcement)
+    } else {
+        Err(ApiError::Forbidden(format!("Announcement with id of {} isn't yours.", id)))
+    }
+}
+
+/* Create */
+mod create;
+pub use create::*;
+
+/* Read */
+mod read;
+pub use read::*;
+
+/* Update */
+mod update;
+pub use update::*;
+
+/* Delete */
+mod delete;
+pub use delete::*;
+
+/* Feed */
+mod feed;
+pub use feed::*;
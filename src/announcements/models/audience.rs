@@ -0,0 +1,50 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use std::io::Write;
+
+use diesel::deserialize::{self, FromSql};
+use diesel::pg::Pg;
+use diesel::serialize::{self, IsNull, Output, ToSql};
+use diesel::sql_types::Text;
+
+/// Who the announcement is for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, AsExpression, FromSqlRow)]
+#[serde(rename_all = "lowercase")]
+#[sql_type = "Text"]
+pub enum Audience {
+    /// Everyone
+    School,
+    /// Students of the class, their guardians and teachers
+    Class,
+    /// Every user with the role
+    Role,
+}
+
+impl Audience {
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            Audience::School => "school",
+            Audience::Class => "class",
+            Audience::Role => "role",
+        }
+    }
+}
+
+impl ToSql<Text, Pg> for Audience {
+    fn to_sql<W: Write>(&self, out: &mut Output<W, Pg>) -> serialize::Result {
+        out.write_all(self.as_str().as_bytes())?;
+        Ok(IsNull::No)
+    }
+}
+
+impl FromSql<Text, Pg> for Audience {
+    fn from_sql(bytes: Option<&[u8]>) -> deserialize::Result<Self> {
+        match not_none!(bytes) {
+            b"school" => Ok(Audience::School),
+            b"class" => Ok(Audience::Class),
+            b"role" => Ok(Audience::Role),
+            _ => Err("Unrecognized announcement audience".into()),
+        }
+    }
+}
@@ -0,0 +1,145 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use super::*;
+use super::imports::*;
+
+use chrono::Utc;
+use diesel::result::{DatabaseErrorKind, Error::DatabaseError};
+
+/// This is the create handler.
+///
+/// Admins and teachers, teachers can only announce to classes they teach.
+pub fn create((request, user, new_announcement): (HttpRequest<State>, CurrentUser, Json<CreateRequest>))
+    -> Box<Future<Item = Json<CreateResponse>, Error = actix_web::Error>>
+{
+    debug!("Request to create announcement: {:?}", &new_announcement);
+    request.state().db
+        .send(CreateAnnouncement {
+            user,
+            fields: new_announcement.into_inner()
+        })
+        .from_err()
+        .and_then(|res| {
+            res.map(|announcement| {
+                info!("Successfully created announcement");
+                Json(CreateResponse {
+                    message: "Success!".to_string(),
+                    announcement: Some(announcement)
+                })
+            }).map_err(actix_web::Error::from)
+        })
+        .responder()
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct CreateRequest {
+    pub audience: Audience,
+    /// Required for the `class` audience
+    pub class_id: Option<i32>,
+    /// Required for the `role` audience
+    pub role: Option<Role>,
+    pub title: String,
+    pub body: String,
+    #[serde(default)]
+    pub pinned: bool,
+    /// Now if not set
+    pub publish_at: Option<NaiveDateTime>,
+    pub expires_at: Option<NaiveDateTime>
+}
+
+impl Validate for CreateRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut validator = Validator::new();
+        validator.text("title", &self.title, MAX_TITLE_LENGTH);
+        validator.text("body", &self.body, MAX_BODY_LENGTH);
+        validator.check(
+            "class_id",
+            (self.audience == Audience::Class) == self.class_id.is_some(),
+            "must be set for the `class` audience and only for it"
+        );
+        validator.check(
+            "role",
+            (self.audience == Audience::Role) == self.role.is_some(),
+            "must be set for the `role` audience and only for it"
+        );
+        if let (Some(publish_at), Some(expires_at)) = (self.publish_at, self.expires_at) {
+            validator.check("expires_at", expires_at > publish_at, "must be after publish_at");
+        }
+        validator.finish()
+    }
+}
+
+/// id should be set automatically
+#[derive(Insertable)]
+#[table_name="announcements"]
+struct NewAnnouncement {
+    author_id: Option<i32>,
+    audience: Audience,
+    class_id: Option<i32>,
+    role: Option<Role>,
+    title: String,
+    body: String,
+    pinned: bool,
+    publish_at: NaiveDateTime,
+    expires_at: Option<NaiveDateTime>,
+    created_at: NaiveDateTime
+}
+
+#[derive(Serialize)]
+pub struct CreateResponse {
+    pub message: String,
+    pub announcement: Option<Announcement>
+}
+
+pub struct CreateAnnouncement {
+    pub user: CurrentUser,
+    pub fields: CreateRequest,
+}
+
+impl Message for CreateAnnouncement {
+    type Result = Result<Announcement, ApiError>;
+}
+
+impl Handler<CreateAnnouncement> for Database {
+    type Result = Result<Announcement, ApiError>;
+
+    fn handle(&mut self, msg: CreateAnnouncement, _: &mut Self::Context) -> Self::Result {
+        require_role(&msg.user, &[Role::Admin, Role::Teacher])?;
+        msg.fields.validate()?;
+        let conn = self.conn()?;
+        let fields = msg.fields;
+        if let Some(class) = fields.class_id {
+            require_class(&conn, &msg.user, class)?;
+        }
+
+        let now = Utc::now().naive_utc();
+        let publish_at = fields.publish_at.unwrap_or(now);
+        if let Some(expires_at) = fields.expires_at {
+            Validator::new()
+                .check("expires_at", expires_at > publish_at, "must be after publish_at")
+                .finish()?;
+        }
+        let new_announcement = NewAnnouncement {
+            author_id: Some(msg.user.id),
+            audience: fields.audience,
+            class_id: fields.class_id,
+            role: fields.role,
+            title: fields.title.trim().to_string(),
+            body: fields.body.trim().to_string(),
+            pinned: fields.pinned,
+            publish_at,
+            expires_at: fields.expires_at,
+            created_at: now,
+        };
+        diesel::insert_into(announcements::table)
+            .values(&new_announcement)
+            .get_result::<Announcement>(&conn)
+            .map_err(|err| match err {
+                DatabaseError(DatabaseErrorKind::ForeignKeyViolation, _) => ApiError::NotFound(
+                    format!("Class with id of {} not found.", new_announcement.class_id.unwrap_or_default())
+                ),
+                err => ApiError::from(err)
+            })
+    }
+}
@@ -0,0 +1,86 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use super::*;
+use super::imports::*;
+
+use chrono::Utc;
+use crate::students::live;
+
+/// The announcements the logged in user should see right now, pinned ones first.
+///
+/// Everyone sees the school ones and the ones for their role. Class ones go to
+/// the students of the class, their guardians, and teachers of the class.
+/// Admins see every class's.
+pub fn feed((request, user): (HttpRequest<State>, CurrentUser))
+    -> Box<Future<Item = Json<Vec<Announcement>>, Error = actix_web::Error>>
+{
+    debug!("Request to read announcements feed of user with id of {}.", user.id);
+    request.state().db
+        .send(FeedRequest{user})
+        .from_err()
+        .and_then(|res| res.map(Json).map_err(actix_web::Error::from))
+        .responder()
+}
+
+/// The classes the user gets the announcements of, `None` for every class.
+///
+/// Teachers get the ones of the classes they can post to.
+fn classes_of(conn: &PgConnection, user: &CurrentUser) -> Result<Option<Vec<i32>>, ApiError> {
+    use crate::schema::{guardians, students};
+    let mut ids = match user.role {
+        Role::Admin => return Ok(None),
+        Role::Teacher => taught_classes(conn, user.id)?,
+        Role::Parent => students::table
+            .inner_join(guardians::table)
+            .filter(guardians::user_id.eq(user.id))
+            .filter(live())
+            .select(students::class_id)
+            .load::<i32>(conn)?,
+        Role::Student => match user.student_id {
+            Some(student) => students::table
+                .find(student)
+                .filter(live())
+                .select(students::class_id)
+                .load::<i32>(conn)?,
+            None => Vec::new(),
+        },
+    };
+    ids.sort();
+    ids.dedup();
+    Ok(Some(ids))
+}
+
+pub struct FeedRequest {
+    pub user: CurrentUser,
+}
+
+impl Message for FeedRequest {
+    type Result = Result<Vec<Announcement>, ApiError>;
+}
+
+impl Handler<FeedRequest> for Database {
+    type Result = Result<Vec<Announcement>, ApiError>;
+
+    fn handle(&mut self, msg: FeedRequest, _: &mut Self::Context) -> Self::Result {
+        use crate::schema::announcements::dsl::*;
+        let conn = self.conn()?;
+        let now = Utc::now().naive_utc();
+
+        let for_role = audience.eq(Audience::Role).and(role.eq(msg.user.role));
+        let mut query = announcements
+            .filter(publish_at.le(now))
+            .filter(expires_at.is_null().or(expires_at.gt(now)))
+            .order((pinned.desc(), publish_at.desc(), id.desc()))
+            .into_boxed();
+        query = match classes_of(&conn, &msg.user)? {
+            None => query.filter(audience.ne(Audience::Role).or(for_role)),
+            Some(classes) => query.filter(
+                audience.eq(Audience::School)
+                    .or(for_role)
+                    .or(audience.eq(Audience::Class).and(class_id.eq_any(classes)))
+            ),
+        };
+        Ok(query.load::<Announcement>(&conn)?)
+    }
+}
@@ -0,0 +1,59 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use super::*;
+use super::imports::*;
+
+/// Every announcement including scheduled and expired ones, newest first, e.g. `?class_id=1`.
+///
+/// Admins see all of them, teachers only their own.
+pub fn read((request, user, query): (HttpRequest<State>, CurrentUser, Query<AnnouncementsQuery>))
+    -> Box<Future<Item = Json<Vec<Announcement>>, Error = actix_web::Error>>
+{
+    debug!("Request to read announcements: {:?}", &*query);
+    request.state().db
+        .send(ReadRequest{query: query.into_inner(), user})
+        .from_err()
+        .and_then(|res| res.map(Json).map_err(actix_web::Error::from))
+        .responder()
+}
+
+/// Every filter is optional.
+#[derive(Deserialize, Serialize, Debug)]
+pub struct AnnouncementsQuery {
+    pub audience: Option<Audience>,
+    pub class_id: Option<i32>
+}
+
+pub struct ReadRequest {
+    pub query: AnnouncementsQuery,
+    pub user: CurrentUser,
+}
+
+impl Message for ReadRequest {
+    type Result = Result<Vec<Announcement>, ApiError>;
+}
+
+impl Handler<ReadRequest> for Database {
+    type Result = Result<Vec<Announcement>, ApiError>;
+
+    fn handle(&mut self, msg: ReadRequest, _: &mut Self::Context) -> Self::Result {
+        use crate::schema::announcements::dsl::*;
+        require_role(&msg.user, &[Role::Admin, Role::Teacher])?;
+        let conn = self.conn()?;
+
+        let mut query = announcements
+            .order((publish_at.desc(), id.desc()))
+            .into_boxed();
+        if msg.user.role != Role::Admin {
+            query = query.filter(author_id.eq(msg.user.id));
+        }
+        if let Some(wanted) = msg.query.audience {
+            query = query.filter(audience.eq(wanted));
+        }
+        if let Some(class) = msg.query.class_id {
+            query = query.filter(class_id.eq(class));
+        }
+        Ok(query.load::<Announcement>(&conn)?)
+    }
+}
@@ -0,0 +1,108 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use super::*;
+use super::imports::*;
+
+/// This is the update handler
+///
+/// Admins, or the teacher who wrote it, 404 if there's no such announcement.
+pub fn update((request, user, id, updated_announcement): (HttpRequest<State>, CurrentUser, Path<i32>, Json<UpdateRequest>))
+    -> Box<Future<Item = HttpResponse, Error = actix_web::Error>>
+{
+    request.state().db
+        .send(UpdateAnnouncement {
+            id: id.clone(),
+            user,
+            fields: updated_announcement.into_inner()
+        })
+        .from_err()
+        .and_then(move |res| {
+            res.map(|announcement| {
+                HttpResponse::Ok().json(UpdateResponse {
+                    message: format!("Updated announcement with id: {:?}.", id),
+                    announcement: Some(announcement)
+                })
+            }).map_err(actix_web::Error::from)
+        }).responder()
+}
+
+/// `"expires_at": null` makes it never expire, leaving it out keeps it as is.
+/// The audience can't be changed, create a new announcement instead.
+#[derive(Serialize, Deserialize, AsChangeset)]
+#[table_name="announcements"]
+pub struct UpdateRequest {
+    pub title: Option<String>,
+    pub body: Option<String>,
+    pub pinned: Option<bool>,
+    pub publish_at: Option<NaiveDateTime>,
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    pub expires_at: Option<Option<NaiveDateTime>>
+}
+
+impl UpdateRequest {
+    /// Texts without surrounding whitespace.
+    fn trimmed(self) -> Self {
+        UpdateRequest {
+            title: self.title.map(|title| title.trim().to_string()),
+            body: self.body.map(|body| body.trim().to_string()),
+            ..self
+        }
+    }
+}
+
+/// Only the fields that are being changed are checked.
+impl Validate for UpdateRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut validator = Validator::new();
+        if let Some(ref title) = self.title {
+            validator.text("title", title, MAX_TITLE_LENGTH);
+        }
+        if let Some(ref body) = self.body {
+            validator.text("body", body, MAX_BODY_LENGTH);
+        }
+        validator.finish()
+    }
+}
+
+pub struct UpdateAnnouncement {
+    pub id: i32,
+    pub user: CurrentUser,
+    pub fields: UpdateRequest,
+}
+
+impl Message for UpdateAnnouncement {
+    type Result = Result<Announcement, ApiError>;
+}
+
+impl Handler<UpdateAnnouncement> for Database {
+    type Result = Result<Announcement, ApiError>;
+
+    fn handle(&mut self, msg: UpdateAnnouncement, _: &mut Self::Context) -> Self::Result {
+        require_role(&msg.user, &[Role::Admin, Role::Teacher])?;
+        msg.fields.validate()?;
+        require_changes(
+            msg.fields.title.is_some() || msg.fields.body.is_some() || msg.fields.pinned.is_some()
+                || msg.fields.publish_at.is_some() || msg.fields.expires_at.is_some()
+        )?;
+        let fields = msg.fields.trimmed();
+        let conn = self.conn()?;
+        let current = require_author(&conn, &msg.user, msg.id)?;
+
+        let publish_at = fields.publish_at.unwrap_or(current.publish_at);
+        if let Some(expires_at) = fields.expires_at.unwrap_or(current.expires_at) {
+            Validator::new()
+                .check("expires_at", expires_at > publish_at, "must be after publish_at")
+                .finish()?;
+        }
+        Ok(diesel::update(announcements::table.find(msg.id))
+            .set(&fields)
+            .get_result::<Announcement>(&conn)?)
+    }
+}
+
+#[derive(Serialize)]
+pub struct UpdateResponse {
+    pub message: String,
+    pub announcement: Option<Announcement>
+}
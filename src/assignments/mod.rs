@@ -0,0 +1,14 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+extern crate serde_derive;
+extern crate actix_web;
+
+mod models;
+
+pub use models::{
+    create,
+    read,
+    delete,
+    my_classes
+};
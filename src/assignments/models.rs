@@ -0,0 +1,38 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use crate::schema::teaching_assignments;
+use crate::database::Database;
+use actix_web::actix::{Message, Handler};
+use diesel;
+
+#[allow(unused_imports)] // Throws errors without this import, but throws warning with it :/
+use diesel::prelude::*;
+
+mod imports;
+
+/// A teacher teaching a subject to a class during a school year.
+#[derive(Queryable, Serialize, Deserialize, Debug)]
+pub struct Assignment {
+    pub id: i32,
+    pub teacher_id: i32,
+    pub subject_id: i32,
+    pub class_id: i32,
+    pub school_year: String
+}
+
+/* Create */
+mod create;
+pub use create::*;
+
+/* Read */
+mod read;
+pub use read::*;
+
+/* Delete */
+mod delete;
+pub use delete::*;
+
+/* My classes */
+mod mine;
+pub use mine::*;
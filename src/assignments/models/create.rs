@@ -0,0 +1,132 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use super::*;
+use super::imports::*;
+
+use diesel::result::{DatabaseErrorKind, Error::DatabaseError};
+
+/// This is the create handler.
+///
+/// Admins only.
+pub fn create((request, user, new_assignment): (HttpRequest<State>, CurrentUser, Json<CreateRequest>))
+    -> Box<Future<Item = Json<CreateResponse>, Error = actix_web::Error>>
+{
+    debug!("Request to create teaching assignment: {:?}", &new_assignment);
+    request.state().db
+        .send(CreateAssignment {
+            user,
+            fields: new_assignment.into_inner()
+        })
+        .from_err()
+        .and_then(|res| {
+            res.map(|assignment| {
+                info!("Successfully added teaching assignment");
+                Json(CreateResponse {
+                    message: "Success!".to_string(),
+                    assignment: Some(assignment)
+                })
+            }).map_err(actix_web::Error::from)
+        })
+        .responder()
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct CreateRequest {
+    pub teacher_id: i32,
+    pub subject_id: i32,
+    pub class_id: i32,
+    /// Defaults to the class's school year
+    pub school_year: Option<String>
+}
+
+impl Validate for CreateRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut validator = Validator::new();
+        if let Some(ref year) = self.school_year {
+            validator.school_year("school_year", year);
+        }
+        validator.finish()
+    }
+}
+
+/// id should be set automatically
+#[derive(Insertable)]
+#[table_name="teaching_assignments"]
+struct NewAssignment {
+    teacher_id: i32,
+    subject_id: i32,
+    class_id: i32,
+    school_year: String
+}
+
+#[derive(Serialize)]
+pub struct CreateResponse {
+    pub message: String,
+    pub assignment: Option<Assignment>
+}
+
+pub struct CreateAssignment {
+    pub user: CurrentUser,
+    pub fields: CreateRequest,
+}
+
+impl Message for CreateAssignment {
+    type Result = Result<Assignment, ApiError>;
+}
+
+impl Handler<CreateAssignment> for Database {
+    type Result = Result<Assignment, ApiError>;
+
+    /// Only `teacher` accounts can be assigned.
+    fn handle(&mut self, msg: CreateAssignment, _: &mut Self::Context) -> Self::Result {
+        use crate::schema::{classes, users};
+        require_role(&msg.user, &[Role::Admin])?;
+        msg.fields.validate()?;
+        let conn = self.conn()?;
+        let fields = msg.fields;
+
+        let role = users::table
+            .find(fields.teacher_id)
+            .select(users::role)
+            .first::<Role>(&conn)
+            .optional()?
+            .ok_or_else(|| ApiError::NotFound(format!("User with id of {} not found.", fields.teacher_id)))?;
+        if role != Role::Teacher {
+            return Err(ApiError::BadRequest(format!(
+                "User with id of {} is a `{}`, only teachers can be assigned.", fields.teacher_id, role.as_str()
+            )));
+        }
+        let class_year = classes::table
+            .find(fields.class_id)
+            .select(classes::school_year)
+            .first::<String>(&conn)
+            .optional()?
+            .ok_or_else(|| ApiError::NotFound(format!("Class with id of {} not found.", fields.class_id)))?;
+
+        let new_assignment = NewAssignment {
+            teacher_id: fields.teacher_id,
+            subject_id: fields.subject_id,
+            class_id: fields.class_id,
+            school_year: fields.school_year.unwrap_or(class_year),
+        };
+        diesel::insert_into(teaching_assignments::table)
+            .values(&new_assignment)
+            .get_result::<Assignment>(&conn)
+            .map_err(|err| match err {
+                DatabaseError(DatabaseErrorKind::UniqueViolation, _) => ApiError::Conflict(
+                    "The teacher already teaches that subject to that class in that school year.".to_string()
+                ),
+                DatabaseError(DatabaseErrorKind::ForeignKeyViolation, info) => ApiError::NotFound(
+                    match info.constraint_name() {
+                        Some("teaching_assignments_subject_id_fkey") =>
+                            format!("Subject with id of {} not found.", new_assignment.subject_id),
+                        Some("teaching_assignments_class_id_fkey") =>
+                            format!("Class with id of {} not found.", new_assignment.class_id),
+                        _ => format!("User with id of {} not found.", new_assignment.teacher_id),
+                    }
+                ),
+                err => ApiError::from(err)
+            })
+    }
+}
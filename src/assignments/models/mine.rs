@@ -0,0 +1,78 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use super::*;
+use super::imports::*;
+
+use std::collections::BTreeMap;
+use crate::classes::Class;
+use crate::subjects::Subject;
+
+/// Lists the classes the logged in teacher teaches or is the homeroom teacher of.
+pub fn my_classes((request, user): (HttpRequest<State>, CurrentUser))
+    -> Box<Future<Item = Json<Vec<MyClass>>, Error = actix_web::Error>>
+{
+    debug!("Request to read classes of teacher with id of {}.", user.id);
+    request.state().db
+        .send(MyClassesRequest{user})
+        .from_err()
+        .and_then(|res| res.map(Json).map_err(actix_web::Error::from))
+        .responder()
+}
+
+/// A class with what the teacher teaches there.
+#[derive(Serialize, Debug)]
+pub struct MyClass {
+    #[serde(flatten)]
+    pub class: Class,
+    pub subjects: Vec<Subject>,
+    pub homeroom: bool,
+}
+
+pub struct MyClassesRequest {
+    pub user: CurrentUser,
+}
+
+impl Message for MyClassesRequest {
+    type Result = Result<Vec<MyClass>, ApiError>;
+}
+
+impl Handler<MyClassesRequest> for Database {
+    type Result = Result<Vec<MyClass>, ApiError>;
+
+    /// Newest school years first.
+    fn handle(&mut self, msg: MyClassesRequest, _: &mut Self::Context) -> Self::Result {
+        use crate::schema::{classes, subjects};
+        require_role(&msg.user, &[Role::Teacher])?;
+        let conn = self.conn()?;
+
+        let taught = teaching_assignments::table
+            .inner_join(classes::table)
+            .inner_join(subjects::table)
+            .filter(teaching_assignments::teacher_id.eq(msg.user.id))
+            .filter(teaching_assignments::school_year.eq(classes::school_year))
+            .select((classes::all_columns, subjects::all_columns))
+            .order((classes::id, subjects::name))
+            .load::<(Class, Subject)>(&conn)?;
+        let homeroom = classes::table
+            .filter(classes::homeroom_teacher_id.eq(msg.user.id))
+            .load::<Class>(&conn)?;
+
+        let mut mine: BTreeMap<i32, MyClass> = BTreeMap::new();
+        for class in homeroom {
+            mine.insert(class.id, MyClass { class, subjects: Vec::new(), homeroom: true });
+        }
+        for (class, subject) in taught {
+            mine.entry(class.id)
+                .or_insert_with(|| MyClass { class, subjects: Vec::new(), homeroom: false })
+                .subjects
+                .push(subject);
+        }
+
+        let mut mine: Vec<MyClass> = mine.into_iter().map(|(_, class)| class).collect();
+        mine.sort_by(|a, b| {
+            b.class.school_year.cmp(&a.class.school_year).then_with(|| a.class.name.cmp(&b.class.name))
+        });
+        Ok(mine)
+    }
+}
@@ -0,0 +1,64 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use super::*;
+use super::imports::*;
+
+/// Lists teaching assignments, e.g. `?class_id=1&school_year=2018/2019`.
+/// 
+/// Admins and teachers only.
+pub fn read((request, user, query): (HttpRequest<State>, CurrentUser, Query<AssignmentsQuery>)) 
+    -> Box<Future<Item = Json<Vec<Assignment>>, Error = actix_web::Error>> 
+{
+    debug!("Request to read teaching assignments: {:?}", &*query);
+    request.state().db
+        .send(ReadRequest{query: query.into_inner(), user})
+        .from_err()
+        .and_then(|res| res.map(Json).map_err(actix_web::Error::from))
+        .responder()
+}
+
+/// Every filter is optional.
+#[derive(Deserialize, Serialize, Debug)]
+pub struct AssignmentsQuery {
+    pub teacher_id: Option<i32>,
+    pub subject_id: Option<i32>,
+    pub class_id: Option<i32>,
+    pub school_year: Option<String>
+}
+
+pub struct ReadRequest {
+    pub query: AssignmentsQuery,
+    pub user: CurrentUser,
+}
+
+impl Message for ReadRequest {
+    type Result = Result<Vec<Assignment>, ApiError>;
+}
+
+impl Handler<ReadRequest> for Database {
+    type Result = Result<Vec<Assignment>, ApiError>;
+
+    fn handle(&mut self, msg: ReadRequest, _: &mut Self::Context) -> Self::Result {
+        use crate::schema::teaching_assignments::dsl::*;
+        require_role(&msg.user, &[Role::Admin, Role::Teacher])?;
+        let conn = self.conn()?;
+
+        let mut query = teaching_assignments
+            .order((school_year.desc(), class_id, subject_id, teacher_id))
+            .into_boxed();
+        if let Some(teacher) = msg.query.teacher_id {
+            query = query.filter(teacher_id.eq(teacher));
+        }
+        if let Some(subject) = msg.query.subject_id {
+            query = query.filter(subject_id.eq(subject));
+        }
+        if let Some(class) = msg.query.class_id {
+            query = query.filter(class_id.eq(class));
+        }
+        if let Some(year) = msg.query.school_year {
+            query = query.filter(school_year.eq(year));
+        }
+        Ok(query.load::<Assignment>(&conn)?)
+    }
+}
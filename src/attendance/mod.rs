@@ -0,0 +1,20 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+extern crate serde_derive;
+extern crate actix_web;
+
+mod models;
+
+pub use models::{
+    Attendance,
+    Counts,
+    Status,
+    DateRange,
+    create,
+    read,
+    update,
+    summary,
+    summary_csv,
+    summary_xlsx
+};
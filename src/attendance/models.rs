@@ -0,0 +1,66 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use crate::schema::attendance;
+use crate::database::Database;
+use actix_web::actix::{Message, Handler};
+use chrono::NaiveDate;
+use diesel;
+use diesel::result::{DatabaseErrorKind, Error::DatabaseError};
+use crate::error::ApiError;
+
+#[allow(unused_imports)] // Throws errors without this import, but throws warning with it :/
+use diesel::prelude::*;
+
+mod imports;
+
+mod status;
+pub use status::Status;
+
+#[derive(Queryable, Serialize, Deserialize, Debug)]
+pub struct Attendance {
+    pub id: i32,
+    pub student_id: i32,
+    pub subject_id: i32,
+    pub date: NaiveDate,
+    pub lesson_number: i16,
+    pub status: Status,
+    /// Who took the attendance
+    pub teacher_id: Option<i32>,
+    /// The lesson in the student's class's timetable, `None` for lessons outside of the plan
+    pub timetable_id: Option<i32>
+}
+
+/// 404 for a subject that doesn't exist, 400 for a lesson that isn't in `lesson_times`.
+fn reference_missing(err: diesel::result::Error, subject_id: Option<i32>) -> ApiError {
+    match err {
+        DatabaseError(DatabaseErrorKind::ForeignKeyViolation, info) => match info.constraint_name() {
+            Some("attendance_subject_id_fkey") => ApiError::NotFound(
+                format!("Subject with id of {} not found.", subject_id.unwrap_or_default())
+            ),
+            Some("attendance_lesson_number_fkey") => ApiError::BadRequest("There's no such lesson number.".to_string()),
+            _ => ApiError::from(DatabaseError(DatabaseErrorKind::ForeignKeyViolation, info)),
+        },
+        err => ApiError::from(err)
+    }
+}
+
+/* Create */
+mod create;
+pub use create::*;
+
+/* Read */
+mod read;
+pub use read::*;
+
+/* Update */
+mod update;
+pub use update::*;
+
+/* Summary */
+mod summary;
+pub use summary::*;
+
+/* Export */
+mod export;
+pub use export::*;
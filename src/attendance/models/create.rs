@@ -0,0 +1,156 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use super::*;
+use super::imports::*;
+
+use std::collections::{BTreeMap, BTreeSet};
+use diesel::pg::upsert::{excluded, on_constraint};
+use crate::students::live;
+
+/// This is the create handler.
+/// 
+/// Takes the attendance of one lesson, usually for the whole class at once.
+/// Entering a lesson again overwrites the statuses. Every record is linked to the lesson
+/// in the timetable of the student's class then, if there is one.
+/// Admins, or teachers assigned to teach the subject in every student's class.
+pub fn create((request, user, lesson): (HttpRequest<State>, CurrentUser, Json<CreateRequest>)) 
+    -> Box<Future<Item = Json<CreateResponse>, Error = actix_web::Error>> 
+{
+    debug!("Request to take attendance: {:?}", &lesson);
+    request.state().db
+        .send(CreateAttendance {
+            user,
+            fields: lesson.into_inner()
+        })
+        .from_err()
+        .and_then(|res| {
+            res.map(|records| {
+                info!("Successfully took attendance of {} students", records.len());
+                Json(CreateResponse {
+                    message: "Success!".to_string(),
+                    attendance: records
+                })
+            }).map_err(actix_web::Error::from)
+        })
+        .responder()
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct Entry {
+    student_id: i32,
+    status: Status
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct CreateRequest {
+    date: NaiveDate,
+    lesson_number: i16,
+    subject_id: i32,
+    entries: Vec<Entry>
+}
+
+/// Every student may be listed only once, the lesson number is checked against `lesson_times`.
+impl Validate for CreateRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut validator = Validator::new();
+        let mut seen = BTreeSet::new();
+        for (index, entry) in self.entries.iter().enumerate() {
+            validator.check(
+                &format!("entries[{}].student_id", index),
+                seen.insert(entry.student_id),
+                &format!("lists student with id of {} again", entry.student_id)
+            );
+        }
+        validator.finish()
+    }
+}
+
+/// id should be set automatically
+#[derive(Insertable)]
+#[table_name="attendance"]
+struct NewAttendance {
+    student_id: i32,
+    subject_id: i32,
+    date: NaiveDate,
+    lesson_number: i16,
+    status: Status,
+    teacher_id: Option<i32>,
+    timetable_id: Option<i32>
+}
+
+#[derive(Serialize)]
+pub struct CreateResponse {
+    pub message: String,
+    pub attendance: Vec<Attendance>
+}
+
+pub struct CreateAttendance {
+    pub user: CurrentUser,
+    pub fields: CreateRequest,
+}
+
+impl Message for CreateAttendance {
+    type Result = Result<Vec<Attendance>, ApiError>;
+}
+
+impl Handler<CreateAttendance> for Database {
+    type Result = Result<Vec<Attendance>, ApiError>;
+
+    fn handle(&mut self, msg: CreateAttendance, _: &mut Self::Context) -> Self::Result {
+        use crate::schema::attendance::dsl::*;
+        use crate::schema::students;
+        require_role(&msg.user, &[Role::Admin, Role::Teacher])?;
+        msg.fields.validate()?;
+        let conn = self.conn()?;
+
+        let student_ids: Vec<i32> = msg.fields.entries.iter().map(|entry| entry.student_id).collect();
+        let found = students::table
+            .filter(students::id.eq_any(&student_ids))
+            .filter(live())
+            .select((students::id, students::class_id))
+            .load::<(i32, i32)>(&conn)?;
+        let missing = student_ids.iter().find(|wanted| {
+            !found.iter().any(|&(found_id, _)| found_id == **wanted)
+        });
+        if let Some(missing) = missing {
+            return Err(ApiError::NotFound(format!("Student with id of {} not found.", missing)));
+        }
+        let classes: BTreeSet<i32> = found.iter().map(|&(_, class)| class).collect();
+        let mut planned = BTreeMap::new();
+        for class in classes {
+            require_assignment(&conn, &msg.user, class, msg.fields.subject_id)?;
+            planned.insert(class, planned_lesson(&conn, class, msg.fields.date, msg.fields.lesson_number)?);
+        }
+        require_open(&conn, &school_year_of(msg.fields.date))?;
+        let class_of: BTreeMap<i32, i32> = found.into_iter().collect();
+
+        let CreateRequest { date: lesson_date, lesson_number: lesson, subject_id: lesson_subject, entries } = msg.fields;
+        let records: Vec<NewAttendance> = entries.into_iter().map(|entry| NewAttendance {
+            student_id: entry.student_id,
+            subject_id: lesson_subject,
+            date: lesson_date,
+            lesson_number: lesson,
+            status: entry.status,
+            teacher_id: Some(msg.user.id),
+            timetable_id: class_of.get(&entry.student_id).and_then(|class| planned[class]),
+        }).collect();
+
+        if records.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        Ok(diesel::insert_into(attendance)
+            .values(&records)
+            .on_conflict(on_constraint("attendance_lesson_key"))
+            .do_update()
+            .set((
+                subject_id.eq(excluded(subject_id)),
+                status.eq(excluded(status)),
+                teacher_id.eq(excluded(teacher_id)),
+                timetable_id.eq(excluded(timetable_id)),
+            ))
+            .get_results::<Attendance>(&conn)
+            .map_err(|err| reference_missing(err, Some(lesson_subject)))?)
+    }
+}
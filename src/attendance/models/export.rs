@@ -0,0 +1,153 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use super::*;
+use super::imports::*;
+
+use std::collections::BTreeMap;
+use bytes::Bytes;
+use futures::{stream, Stream};
+use crate::export::{self, Cell, Row};
+use crate::students::{RegisterKey, after_in_register, live};
+
+/// The per student part of the attendance summary as CSV, same query and access as `summary`.
+///
+/// Read and sent `CSV_PAGE_SIZE` students at a time, so the whole summary never sits in memory.
+pub fn summary_csv((request, user, query): (HttpRequest<State>, CurrentUser, Query<SummaryQuery>))
+    -> Box<Future<Item = HttpResponse, Error = actix_web::Error>>
+{
+    debug!("Request to export attendance summary to CSV: {:?}", &*query);
+    let db = request.state().db.clone();
+    let query = query.into_inner();
+    let file_name = file_name(query.class_id, query.from, query.to, "csv");
+    // The first page is read before responding, so errors still get their status code
+    db.send(SummaryPage{query: query.clone(), user: user.clone(), after: None})
+        .from_err()
+        .and_then(move |res| -> Result<HttpResponse, actix_web::Error> {
+            let (students, next) = res?;
+            let mut rows = vec![header()];
+            rows.extend(students.iter().map(row));
+            let first = export::csv(&rows, true)?;
+
+            let rest = stream::unfold(next, move |after| {
+                after.map(|after| {
+                    db.send(SummaryPage{query: query.clone(), user: user.clone(), after: Some(after)})
+                        .from_err()
+                        .and_then(|res| -> Result<(Bytes, Option<RegisterKey>), actix_web::Error> {
+                            let (students, next) = res?;
+                            let rows: Vec<Row> = students.iter().map(row).collect();
+                            Ok((export::csv(&rows, false)?, next))
+                        })
+                })
+            });
+            Ok(export::attachment(export::CSV, &file_name)
+                .streaming(stream::once::<_, actix_web::Error>(Ok(first)).chain(rest)))
+        })
+        .responder()
+}
+
+/// The per student part of the attendance summary as XLSX, same query and access as `summary`.
+pub fn summary_xlsx((request, user, query): (HttpRequest<State>, CurrentUser, Query<SummaryQuery>))
+    -> Box<Future<Item = HttpResponse, Error = actix_web::Error>>
+{
+    debug!("Request to export attendance summary to XLSX: {:?}", &*query);
+    request.state().db
+        .send(SummaryRequest{query: query.into_inner(), user})
+        .from_err()
+        .and_then(|res| -> Result<HttpResponse, actix_web::Error> {
+            let summary = res?;
+            let xlsx = export::xlsx("Attendance", &rows(&summary))?;
+            Ok(export::attachment(export::XLSX, &file_name(summary.class_id, summary.from, summary.to, "xlsx")).body(xlsx))
+        })
+        .responder()
+}
+
+/// e.g. `attendance-3-2019-03-01-2019-03-31.csv`
+fn file_name(class_id: i32, from: NaiveDate, to: NaiveDate, extension: &str) -> String {
+    format!("attendance-{}-{}-{}.{}", class_id, from, to, extension)
+}
+
+fn header() -> Row {
+    export::header(&[
+        "student_id", "first_name", "last_name",
+        "present", "absent", "late", "excused", "released", "percentage"
+    ])
+}
+
+fn rows(summary: &Summary) -> Vec<Row> {
+    let mut rows = vec![header()];
+    rows.extend(summary.students.iter().map(row));
+    rows
+}
+
+fn row(student: &StudentSummary) -> Row {
+    let counts = &student.attendance;
+    vec![
+        Cell::from(student.student_id),
+        Cell::from(student.first_name.as_str()),
+        Cell::from(student.last_name.as_str()),
+        Cell::from(counts.present),
+        Cell::from(counts.absent),
+        Cell::from(counts.late),
+        Cell::from(counts.excused),
+        Cell::from(counts.released),
+        Cell::from(counts.percentage),
+    ]
+}
+
+/// Students on a page of the CSV.
+const CSV_PAGE_SIZE: i64 = 100;
+
+/// The summaries of the `CSV_PAGE_SIZE` students after `after` in the class register.
+pub struct SummaryPage {
+    pub query: SummaryQuery,
+    pub user: CurrentUser,
+    pub after: Option<RegisterKey>,
+}
+
+impl Message for SummaryPage {
+    type Result = Result<(Vec<StudentSummary>, Option<RegisterKey>), ApiError>;
+}
+
+impl Handler<SummaryPage> for Database {
+    type Result = Result<(Vec<StudentSummary>, Option<RegisterKey>), ApiError>;
+
+    /// Also returns where the next page starts, if there may be one.
+    fn handle(&mut self, msg: SummaryPage, _: &mut Self::Context) -> Self::Result {
+        use crate::schema::students;
+        let conn = self.conn()?;
+        let query = msg.query;
+        require_taught_class(&conn, &msg.user, query.class_id)?;
+
+        let mut in_class = students::table
+            .filter(students::class_id.eq(query.class_id))
+            .filter(live())
+            .select((students::last_name, students::first_name, students::id))
+            .order((students::last_name, students::first_name, students::id))
+            .limit(CSV_PAGE_SIZE)
+            .into_boxed();
+        if let Some(key) = msg.after {
+            in_class = in_class.filter(after_in_register(key));
+        }
+        let page = in_class.load::<RegisterKey>(&conn)?;
+        let next = if page.len() as i64 == CSV_PAGE_SIZE { page.last().cloned() } else { None };
+
+        let records = attendance::table
+            .filter(attendance::student_id.eq_any(page.iter().map(|&(_, _, id)| id).collect::<Vec<_>>()))
+            .filter(attendance::date.between(query.from, query.to))
+            .select((attendance::student_id, attendance::status))
+            .load::<(i32, Status)>(&conn)?;
+
+        let mut counts: BTreeMap<i32, Counts> = BTreeMap::new();
+        for (student_id, status) in records {
+            counts.entry(student_id).or_insert_with(Counts::default).add(status);
+        }
+
+        // Every student of the page is listed, even without any records
+        Ok((page.into_iter().map(|(last_name, first_name, student_id)| {
+            let mut attendance = counts.remove(&student_id).unwrap_or_default();
+            attendance.finish();
+            StudentSummary { student_id, first_name, last_name, attendance }
+        }).collect(), next))
+    }
+}
@@ -0,0 +1,30 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+pub use actix_web::{
+    Json,
+    HttpRequest,
+    HttpResponse,
+    Path,
+    Query,
+    AsyncResponder,
+    error
+};
+pub use log::{debug, error, info, warn};
+pub use futures::future::Future;
+
+pub use crate::State;
+pub use crate::classes::school_year_of;
+pub use crate::school_years::require_open;
+pub use crate::error::ApiError;
+pub use crate::timetable::planned_lesson;
+pub use crate::login::{
+    CurrentUser,
+    Role,
+    require_role,
+    require_taught_class,
+    require_student,
+    require_assignment,
+    require_student_assignment
+};
+pub use crate::validation::{Validate, ValidationErrors, Validator, require_changes};
@@ -0,0 +1,62 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use super::*;
+use super::imports::*;
+
+/// Lists the attendance of the student with the id from the path,
+/// optionally only `from` and/or `to` a date.
+/// 
+/// Parents only see their children's attendance and students only their own.
+pub fn read((request, user, student_id, range): (HttpRequest<State>, CurrentUser, Path<i32>, Query<DateRange>)) 
+    -> Box<Future<Item = Json<Vec<Attendance>>, Error = actix_web::Error>> 
+{
+    debug!("Request to read attendance of student with id of {}.", student_id.as_ref());
+    request.state().db
+        .send(ReadRequest{
+            student_id: student_id.into_inner(),
+            range: range.into_inner(),
+            user
+        })
+        .from_err()
+        .and_then(|res| res.map(Json).map_err(actix_web::Error::from))
+        .responder()
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct DateRange {
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>
+}
+
+pub struct ReadRequest {
+    pub student_id: i32,
+    pub range: DateRange,
+    pub user: CurrentUser,
+}
+
+impl Message for ReadRequest {
+    type Result = Result<Vec<Attendance>, ApiError>;
+}
+
+impl Handler<ReadRequest> for Database {
+    type Result = Result<Vec<Attendance>, ApiError>;
+
+    fn handle(&mut self, msg: ReadRequest, _: &mut Self::Context) -> Self::Result {
+        use crate::schema::attendance::dsl::*;
+        let conn = self.conn()?;
+        require_student(&conn, &msg.user, msg.student_id)?;
+
+        let mut query = attendance
+            .filter(student_id.eq(msg.student_id))
+            .order((date, lesson_number))
+            .into_boxed();
+        if let Some(from) = msg.range.from {
+            query = query.filter(date.ge(from));
+        }
+        if let Some(to) = msg.range.to {
+            query = query.filter(date.le(to));
+        }
+        Ok(query.load::<Attendance>(&conn)?)
+    }
+}
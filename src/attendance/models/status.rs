@@ -0,0 +1,54 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use std::io::Write;
+
+use diesel::deserialize::{self, FromSql};
+use diesel::pg::Pg;
+use diesel::serialize::{self, IsNull, Output, ToSql};
+use diesel::sql_types::Text;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, AsExpression, FromSqlRow)]
+#[serde(rename_all = "lowercase")]
+#[sql_type = "Text"]
+pub enum Status {
+    Present,
+    Absent,
+    Late,
+    /// Absent with a note from the parents
+    Excused,
+    /// Let off the lesson by the school, doesn't count towards attendance
+    Released,
+}
+
+impl Status {
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            Status::Present => "present",
+            Status::Absent => "absent",
+            Status::Late => "late",
+            Status::Excused => "excused",
+            Status::Released => "released",
+        }
+    }
+}
+
+impl ToSql<Text, Pg> for Status {
+    fn to_sql<W: Write>(&self, out: &mut Output<W, Pg>) -> serialize::Result {
+        out.write_all(self.as_str().as_bytes())?;
+        Ok(IsNull::No)
+    }
+}
+
+impl FromSql<Text, Pg> for Status {
+    fn from_sql(bytes: Option<&[u8]>) -> deserialize::Result<Self> {
+        match not_none!(bytes) {
+            b"present" => Ok(Status::Present),
+            b"absent" => Ok(Status::Absent),
+            b"late" => Ok(Status::Late),
+            b"excused" => Ok(Status::Excused),
+            b"released" => Ok(Status::Released),
+            _ => Err("Unrecognized attendance status".into()),
+        }
+    }
+}
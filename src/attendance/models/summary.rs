@@ -0,0 +1,157 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use super::*;
+use super::imports::*;
+
+use std::collections::BTreeMap;
+use crate::students::live;
+
+/// Attendance percentages of a class, per student and per subject.
+/// 
+/// Admins, or teachers of the class.
+pub fn summary((request, user, query): (HttpRequest<State>, CurrentUser, Query<SummaryQuery>)) 
+    -> Box<Future<Item = Json<Summary>, Error = actix_web::Error>> 
+{
+    debug!("Request for attendance summary: {:?}", &*query);
+    request.state().db
+        .send(SummaryRequest{query: query.into_inner(), user})
+        .from_err()
+        .and_then(|res| res.map(Json).map_err(actix_web::Error::from))
+        .responder()
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct SummaryQuery {
+    pub class_id: i32,
+    pub from: NaiveDate,
+    pub to: NaiveDate
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct Counts {
+    pub present: u32,
+    pub absent: u32,
+    pub late: u32,
+    pub excused: u32,
+    pub released: u32,
+    /// Present or late out of every lesson the student wasn't released from,
+    /// `None` if there weren't any.
+    pub percentage: Option<f64>
+}
+
+impl Counts {
+    pub fn add(&mut self, status: Status) {
+        match status {
+            Status::Present => self.present += 1,
+            Status::Absent => self.absent += 1,
+            Status::Late => self.late += 1,
+            Status::Excused => self.excused += 1,
+            Status::Released => self.released += 1,
+        }
+    }
+
+    pub fn finish(&mut self) {
+        let attended = self.present + self.late;
+        let counted = attended + self.absent + self.excused;
+        if counted > 0 {
+            let percentage = f64::from(attended) / f64::from(counted) * 100.0;
+            self.percentage = Some((percentage * 100.0).round() / 100.0);
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct StudentSummary {
+    pub student_id: i32,
+    pub first_name: String,
+    pub last_name: String,
+    pub attendance: Counts
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SubjectSummary {
+    pub subject_id: i32,
+    pub subject: String,
+    pub attendance: Counts
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Summary {
+    pub class_id: i32,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub students: Vec<StudentSummary>,
+    pub subjects: Vec<SubjectSummary>
+}
+
+pub struct SummaryRequest {
+    pub query: SummaryQuery,
+    pub user: CurrentUser,
+}
+
+impl Message for SummaryRequest {
+    type Result = Result<Summary, ApiError>;
+}
+
+impl Handler<SummaryRequest> for Database {
+    type Result = Result<Summary, ApiError>;
+
+    fn handle(&mut self, msg: SummaryRequest, _: &mut Self::Context) -> Self::Result {
+        use crate::schema::{students, subjects};
+        let conn = self.conn()?;
+        let query = msg.query;
+        require_taught_class(&conn, &msg.user, query.class_id)?;
+
+        // Every student of the class is listed, even without any records
+        let mut by_student: BTreeMap<i32, StudentSummary> = students::table
+            .filter(students::class_id.eq(query.class_id))
+            .filter(live())
+            .select((students::id, students::first_name, students::last_name))
+            .load::<(i32, String, String)>(&conn)?
+            .into_iter()
+            .map(|(student_id, first_name, last_name)| {
+                (student_id, StudentSummary { student_id, first_name, last_name, attendance: Counts::default() })
+            })
+            .collect();
+
+        let records = attendance::table
+            .inner_join(students::table)
+            .inner_join(subjects::table)
+            .filter(students::class_id.eq(query.class_id))
+            .filter(live())
+            .filter(attendance::date.between(query.from, query.to))
+            .select((attendance::student_id, attendance::subject_id, subjects::name, attendance::status))
+            .load::<(i32, i32, String, Status)>(&conn)?;
+
+        let mut by_subject: BTreeMap<(String, i32), Counts> = BTreeMap::new();
+        for (student_id, subject_id, subject, status) in records {
+            if let Some(summary) = by_student.get_mut(&student_id) {
+                summary.attendance.add(status);
+            }
+            by_subject.entry((subject, subject_id)).or_insert_with(Counts::default).add(status);
+        }
+
+        let mut student_summaries: Vec<StudentSummary> = by_student
+            .into_iter()
+            .map(|(_, mut summary)| {
+                summary.attendance.finish();
+                summary
+            })
+            .collect();
+        student_summaries.sort_by(|a, b| {
+            (&a.last_name, &a.first_name).cmp(&(&b.last_name, &b.first_name))
+        });
+
+        Ok(Summary {
+            class_id: query.class_id,
+            from: query.from,
+            to: query.to,
+            students: student_summaries,
+            subjects: by_subject.into_iter().map(|((subject, subject_id), mut attendance)| {
+                attendance.finish();
+                SubjectSummary { subject_id, subject, attendance }
+            }).collect(),
+        })
+    }
+}
@@ -0,0 +1,76 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use super::*;
+use super::imports::*;
+
+/// This is the update handler, mostly for excusing absences.
+/// 
+/// Admins, or teachers assigned to teach the lesson's subject in the student's class.
+pub fn update((request, user, id, updated_record): (HttpRequest<State>, CurrentUser, Path<i32>, Json<UpdateRequest>)) 
+    -> Box<Future<Item = HttpResponse, Error = actix_web::Error>> 
+{
+    request.state().db
+        .send(UpdateAttendance {
+            id: id.clone(),
+            user,
+            fields: updated_record.into_inner()
+        })
+        .from_err()
+        .and_then(move |updated_record| {
+            updated_record.map(|record| {
+                HttpResponse::Ok().json(UpdateResponse { 
+                    message: format!("Updated attendance with id: {:?}.", id),
+                    attendance: Some(record),
+                })
+            }).map_err(actix_web::Error::from)
+        }).responder()
+}
+
+#[derive(Serialize, Deserialize, AsChangeset)]
+#[table_name="attendance"]
+pub struct UpdateRequest {
+    pub subject_id: Option<i32>,
+    pub status: Option<Status>
+}
+
+pub struct UpdateAttendance {
+    pub id: i32,
+    pub user: CurrentUser,
+    pub fields: UpdateRequest,
+}
+
+impl Message for UpdateAttendance {
+    type Result = Result<Attendance, ApiError>;
+}
+
+impl Handler<UpdateAttendance> for Database {
+    type Result = Result<Attendance, ApiError>;
+
+    fn handle(&mut self, msg: UpdateAttendance, _: &mut Self::Context) -> Self::Result {
+        use crate::schema::attendance::dsl::*;
+        require_changes(msg.fields.subject_id.is_some() || msg.fields.status.is_some())?;
+        let conn = self.conn()?;
+        let (absent_student, lesson_subject, lesson_date) = attendance
+            .find(msg.id)
+            .select((student_id, subject_id, date))
+            .first::<(i32, i32, NaiveDate)>(&conn)
+            .optional()?
+            .ok_or_else(|| ApiError::NotFound(format!("Attendance with id of {} not found.", msg.id)))?;
+        require_student_assignment(&conn, &msg.user, absent_student, lesson_subject)?;
+        if let Some(new_subject) = msg.fields.subject_id {
+            require_student_assignment(&conn, &msg.user, absent_student, new_subject)?;
+        }
+        require_open(&conn, &school_year_of(lesson_date))?;
+        diesel::update(attendance.find(msg.id))
+            .set(&msg.fields)
+            .get_result::<Attendance>(&conn)
+            .map_err(|err| reference_missing(err, msg.fields.subject_id))
+    }
+}
+
+#[derive(Serialize)]
+pub struct UpdateResponse {
+    pub message: String,
+    pub attendance: Option<Attendance>
+}
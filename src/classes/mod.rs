@@ -0,0 +1,21 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+extern crate serde_derive;
+extern crate actix_web;
+
+mod models;
+
+pub use models::{
+    Class,
+    school_year_of,
+    school_year_bounds,
+    create,
+    read,
+    update,
+    delete,
+    students,
+    teachers,
+    add_teacher,
+    remove_teacher
+};
@@ -0,0 +1,89 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use crate::schema::classes;
+use crate::database::Database;
+use crate::error::ApiError;
+use crate::login::Role;
+use actix_web::actix::{Message, Handler};
+use chrono::{Datelike, NaiveDate};
+use diesel;
+use diesel::pg::PgConnection;
+use diesel::result::{DatabaseErrorKind, Error::DatabaseError};
+
+#[allow(unused_imports)] // Throws errors without this import, but throws warning with it :/
+use diesel::prelude::*;
+
+mod imports;
+
+#[derive(Queryable, Serialize, Deserialize, Debug)]
+pub struct Class {
+    pub id: i32,
+    pub name: String,
+    /// e.g. `2018/2019`
+    pub school_year: String,
+    pub homeroom_teacher_id: Option<i32>
+}
+
+/// The school year the date falls into, years switch on the 1st of September.
+pub fn school_year_of(date: NaiveDate) -> String {
+    let first = if date.month() >= 9 { date.year() } else { date.year() - 1 };
+    format!("{}/{}", first, first + 1)
+}
+
+/// From the 1st of September to the 30th of June, `None` if the year isn't like `2018/2019`.
+pub fn school_year_bounds(school_year: &str) -> Option<(NaiveDate, NaiveDate)> {
+    let first: i32 = school_year.split('/').next()?.parse().ok()?;
+    Some((NaiveDate::from_ymd(first, 9, 1), NaiveDate::from_ymd(first + 1, 6, 30)))
+}
+
+/// Turns the unique name per school year constraint violation into a 409 Conflict.
+fn name_taken(err: diesel::result::Error, name: &str) -> ApiError {
+    match err {
+        DatabaseError(DatabaseErrorKind::UniqueViolation, _) => {
+            ApiError::Conflict(format!("Class `{}` already exists in this school year.", name))
+        }
+        err => ApiError::from(err)
+    }
+}
+
+/// Only `teacher` accounts can be homeroom teachers.
+fn require_teacher(conn: &PgConnection, teacher_id: i32) -> Result<(), ApiError> {
+    use crate::schema::users;
+    let role = users::table
+        .find(teacher_id)
+        .select(users::role)
+        .first::<Role>(conn)
+        .optional()?
+        .ok_or_else(|| ApiError::NotFound(format!("User with id of {} not found.", teacher_id)))?;
+    if role != Role::Teacher {
+        return Err(ApiError::BadRequest(format!(
+            "User with id of {} is a `{}`, only teachers can be homeroom teachers.", teacher_id, role.as_str()
+        )));
+    }
+    Ok(())
+}
+
+/* Create */
+mod create;
+pub use create::*;
+
+/* Read */
+mod read;
+pub use read::*;
+
+/* Update */
+mod update;
+pub use update::*;
+
+/* Delete */
+mod delete;
+pub use delete::*;
+
+/* Students of a class */
+mod roster;
+pub use roster::*;
+
+/* Teachers of a class */
+mod teachers;
+pub use teachers::*;
@@ -0,0 +1,80 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use super::*;
+use super::imports::*;
+
+/// This is the create handler.
+/// 
+/// Admins only.
+pub fn create((request, user, new_class): (HttpRequest<State>, CurrentUser, Json<CreateRequest>)) 
+    -> Box<Future<Item = Json<CreateResponse>, Error = actix_web::Error>> 
+{
+    debug!("Request to create class: {:?}", &new_class);
+    request.state().db
+        .send(CreateClass {
+            user,
+            fields: new_class.into_inner()
+        })
+        .from_err()
+        .and_then(|res| {
+            res.map(|class| {
+                info!("Successfully added class");
+                Json(CreateResponse {
+                    message: "Success!".to_string(),
+                    class: Some(class)
+                })
+            }).map_err(actix_web::Error::from)
+        })
+        .responder()
+}
+
+/// id should be set automatically
+#[derive(Insertable, Deserialize, Serialize, Debug)]
+#[table_name="classes"]
+pub struct CreateRequest {
+    name: String,
+    school_year: String,
+    homeroom_teacher_id: Option<i32>
+}
+
+impl Validate for CreateRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        Validator::new()
+            .class_name("name", &self.name)
+            .school_year("school_year", &self.school_year)
+            .finish()
+    }
+}
+
+#[derive(Serialize)]
+pub struct CreateResponse {
+    pub message: String,
+    pub class: Option<Class>
+}
+
+pub struct CreateClass {
+    pub user: CurrentUser,
+    pub fields: CreateRequest,
+}
+
+impl Message for CreateClass {
+    type Result = Result<Class, ApiError>;
+}
+
+impl Handler<CreateClass> for Database {
+    type Result = Result<Class, ApiError>;
+
+    fn handle(&mut self, msg: CreateClass, _: &mut Self::Context) -> Self::Result {
+        require_role(&msg.user, &[Role::Admin])?;
+        msg.fields.validate()?;
+        let conn = self.conn()?;
+        if let Some(teacher_id) = msg.fields.homeroom_teacher_id {
+            require_teacher(&conn, teacher_id)?;
+        }
+        diesel::insert_into(classes::table)
+            .values(&msg.fields)
+            .get_result::<Class>(&conn)
+            .map_err(|err| name_taken(err, &msg.fields.name))
+    }
+}
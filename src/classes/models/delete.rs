@@ -0,0 +1,90 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use super::*;
+use super::imports::*;
+
+use diesel::result::{DatabaseErrorKind, Error::DatabaseError};
+
+/// This is the delete handler
+/// 
+/// Only empty classes can be deleted, 404 if there's no such class. Admins only.
+pub fn delete((request, user, id): (HttpRequest<State>, CurrentUser, Path<i32>)) 
+    -> Box<Future<Item = HttpResponse, Error = actix_web::Error>> 
+{
+    debug!("Request to delete class with id of {}.", id.as_ref());
+    
+    request.state().db
+        .send(DeleteRequest{id: id.clone(), user})
+        .from_err()
+        .and_then(move |res| {
+            res.map(|_| {
+                info!("Class with id of {} successfully deleted.", id);
+                HttpResponse::Ok()
+                    .json(DeleteResponse {
+                        message: format!("Deleted class with id: {:?}.", id)
+                    })
+            }).map_err(actix_web::Error::from)
+        }).responder()
+}
+
+pub struct DeleteRequest {
+    pub id: i32,
+    pub user: CurrentUser,
+}
+
+impl Message for DeleteRequest {
+    type Result = Result<usize, ApiError>;
+}
+
+impl Handler<DeleteRequest> for Database {
+    type Result = Result<usize, ApiError>;
+
+    /// Students are the only rows that keep a class from being deleted, the timetable,
+    /// assignments, lessons, announcements and past class memberships are deleted with it.
+    /// Deleted students still count until they're purged.
+    fn handle(&mut self, msg: DeleteRequest, _: &mut Self::Context) -> Self::Result {
+        use crate::schema::classes::dsl::*;
+        use crate::schema::students;
+        require_role(&msg.user, &[Role::Admin])?;
+        let conn = self.conn()?;
+        conn.transaction::<_, ApiError, _>(|| {
+            let (live_students, deleted_students) = students::table
+                .filter(students::class_id.eq(msg.id))
+                .select(students::deleted_at.is_null())
+                .load::<bool>(&conn)?
+                .into_iter()
+                .fold((0, 0), |(live, deleted), is_live| {
+                    if is_live { (live + 1, deleted) } else { (live, deleted + 1) }
+                });
+            if live_students > 0 {
+                return Err(ApiError::Conflict(format!(
+                    "Class with id of {} still has {} students, move them to another class first.", msg.id, live_students
+                )));
+            }
+            if deleted_students > 0 {
+                return Err(ApiError::Conflict(format!(
+                    "Class with id of {} still has {} deleted students, it can be deleted once they're purged.",
+                    msg.id, deleted_students
+                )));
+            }
+            let deleted = diesel::delete(classes.find(msg.id))
+                .execute(&conn)
+                .map_err(|err| match err {
+                    DatabaseError(DatabaseErrorKind::ForeignKeyViolation, _) => ApiError::Conflict(
+                        format!("Class with id of {} got new students while it was being deleted.", msg.id)
+                    ),
+                    err => ApiError::from(err)
+                })?;
+            match deleted {
+                0 => Err(ApiError::NotFound(format!("Class with id of {} not found.", msg.id))),
+                deleted => Ok(deleted)
+            }
+        })
+    }
+}
+
+#[derive(Serialize)]
+pub struct DeleteResponse {
+    pub message: String,
+}
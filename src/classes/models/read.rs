@@ -0,0 +1,33 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use super::*;
+use super::imports::*;
+
+/// Every logged in user may list classes.
+pub fn read((request, _user): (HttpRequest<State>, CurrentUser)) 
+    -> Box<Future<Item = Json<Vec<Class>>, Error = actix_web::Error>> 
+{
+    debug!("Request to read all classes.");
+    request.state().db
+        .send(ReadRequest{})
+        .from_err()
+        .and_then(|res| res.map(Json).map_err(actix_web::Error::from))
+        .responder()
+}
+
+pub struct ReadRequest{}
+
+impl Message for ReadRequest {
+    type Result = Result<Vec<Class>, ApiError>;
+}
+
+impl Handler<ReadRequest> for Database {
+    type Result = Result<Vec<Class>, ApiError>;
+
+    fn handle(&mut self, _msg: ReadRequest, _: &mut Self::Context) -> Self::Result {
+        use crate::schema::classes::dsl::*;
+        let conn = self.conn()?;
+        Ok(classes.order((school_year.desc(), name)).load::<Class>(&conn)?)
+    }
+}
@@ -0,0 +1,50 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use super::*;
+use super::imports::*;
+
+use crate::students::{Student, live};
+
+/// Lists the students of the class with the id from the path.
+/// 
+/// Admins and teachers only.
+pub fn students((request, user, class_id): (HttpRequest<State>, CurrentUser, Path<i32>)) 
+    -> Box<Future<Item = Json<Vec<Student>>, Error = actix_web::Error>> 
+{
+    debug!("Request to read students of class with id of {}.", class_id.as_ref());
+    request.state().db
+        .send(StudentsRequest{class_id: class_id.into_inner(), user})
+        .from_err()
+        .and_then(|res| res.map(Json).map_err(actix_web::Error::from))
+        .responder()
+}
+
+pub struct StudentsRequest {
+    pub class_id: i32,
+    pub user: CurrentUser,
+}
+
+impl Message for StudentsRequest {
+    type Result = Result<Vec<Student>, ApiError>;
+}
+
+impl Handler<StudentsRequest> for Database {
+    type Result = Result<Vec<Student>, ApiError>;
+
+    fn handle(&mut self, msg: StudentsRequest, _: &mut Self::Context) -> Self::Result {
+        use crate::schema::students;
+        require_role(&msg.user, &[Role::Admin, Role::Teacher])?;
+        let conn = self.conn()?;
+        let exists = diesel::select(diesel::dsl::exists(classes::table.find(msg.class_id)))
+            .get_result::<bool>(&conn)?;
+        if !exists {
+            return Err(ApiError::NotFound(format!("Class with id of {} not found.", msg.class_id)));
+        }
+        Ok(students::table
+            .filter(students::class_id.eq(msg.class_id))
+            .filter(live())
+            .order((students::last_name, students::first_name))
+            .load::<Student>(&conn)?)
+    }
+}
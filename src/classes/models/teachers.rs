@@ -0,0 +1,185 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use super::*;
+use super::imports::*;
+
+use crate::schema::teacher_classes;
+
+/// Lists the teachers linked to the class with the id from the path in `teacher_classes`.
+///
+/// They, together with the homeroom teacher, may edit the class's students. Admins only.
+pub fn teachers((request, user, class_id): (HttpRequest<State>, CurrentUser, Path<i32>))
+    -> Box<Future<Item = Json<Vec<ClassTeacher>>, Error = actix_web::Error>>
+{
+    debug!("Request to read teachers of class with id of {}.", class_id.as_ref());
+    request.state().db
+        .send(TeachersRequest{class_id: class_id.into_inner(), user})
+        .from_err()
+        .and_then(|res| res.map(Json).map_err(actix_web::Error::from))
+        .responder()
+}
+
+/// Links a teacher to the class with the id from the path. Admins only.
+pub fn add_teacher((request, user, class_id, teacher): (HttpRequest<State>, CurrentUser, Path<i32>, Json<AddTeacherRequest>))
+    -> Box<Future<Item = HttpResponse, Error = actix_web::Error>>
+{
+    let class_id = class_id.into_inner();
+    let teacher_id = teacher.user_id;
+    debug!("Request to link teacher with id of {} to class with id of {}.", teacher_id, class_id);
+    request.state().db
+        .send(AddTeacher{class_id, teacher_id, user})
+        .from_err()
+        .and_then(move |res| {
+            res.map(|_| {
+                info!("Teacher with id of {} linked to class with id of {}.", teacher_id, class_id);
+                HttpResponse::Ok().json(TeacherResponse {
+                    message: format!("Linked teacher with id: {} to class with id: {}.", teacher_id, class_id)
+                })
+            }).map_err(actix_web::Error::from)
+        }).responder()
+}
+
+/// Unlinks the teacher from the class, their account and assignments stay. Admins only.
+pub fn remove_teacher((request, user, ids): (HttpRequest<State>, CurrentUser, Path<(i32, i32)>))
+    -> Box<Future<Item = HttpResponse, Error = actix_web::Error>>
+{
+    let (class_id, teacher_id) = ids.into_inner();
+    debug!("Request to unlink teacher with id of {} from class with id of {}.", teacher_id, class_id);
+    request.state().db
+        .send(RemoveTeacher{class_id, teacher_id, user})
+        .from_err()
+        .and_then(move |res| {
+            res.map(|_| {
+                info!("Teacher with id of {} unlinked from class with id of {}.", teacher_id, class_id);
+                HttpResponse::Ok().json(TeacherResponse {
+                    message: format!("Unlinked teacher with id: {} from class with id: {}.", teacher_id, class_id)
+                })
+            }).map_err(actix_web::Error::from)
+        }).responder()
+}
+
+#[derive(Queryable, Serialize, Deserialize, Debug)]
+pub struct ClassTeacher {
+    pub user_id: i32,
+    pub login: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct AddTeacherRequest {
+    pub user_id: i32
+}
+
+#[derive(Serialize)]
+pub struct TeacherResponse {
+    pub message: String
+}
+
+/// 404 if there's no such class.
+fn require_existing(conn: &PgConnection, class_id: i32) -> Result<(), ApiError> {
+    let exists = diesel::select(diesel::dsl::exists(classes::table.find(class_id)))
+        .get_result::<bool>(conn)?;
+    if exists {
+        Ok(())
+    } else {
+        Err(ApiError::NotFound(format!("Class with id of {} not found.", class_id)))
+    }
+}
+
+pub struct TeachersRequest {
+    pub class_id: i32,
+    pub user: CurrentUser,
+}
+
+impl Message for TeachersRequest {
+    type Result = Result<Vec<ClassTeacher>, ApiError>;
+}
+
+impl Handler<TeachersRequest> for Database {
+    type Result = Result<Vec<ClassTeacher>, ApiError>;
+
+    fn handle(&mut self, msg: TeachersRequest, _: &mut Self::Context) -> Self::Result {
+        use crate::schema::users;
+        require_role(&msg.user, &[Role::Admin])?;
+        let conn = self.conn()?;
+        require_existing(&conn, msg.class_id)?;
+        Ok(teacher_classes::table
+            .inner_join(users::table)
+            .filter(teacher_classes::class_id.eq(msg.class_id))
+            .select((users::id, users::login))
+            .order(users::login)
+            .load::<ClassTeacher>(&conn)?)
+    }
+}
+
+pub struct AddTeacher {
+    pub class_id: i32,
+    pub teacher_id: i32,
+    pub user: CurrentUser,
+}
+
+impl Message for AddTeacher {
+    type Result = Result<(), ApiError>;
+}
+
+impl Handler<AddTeacher> for Database {
+    type Result = Result<(), ApiError>;
+
+    /// 409 if the teacher is already linked to the class.
+    fn handle(&mut self, msg: AddTeacher, _: &mut Self::Context) -> Self::Result {
+        use crate::schema::users;
+        require_role(&msg.user, &[Role::Admin])?;
+        let conn = self.conn()?;
+        require_existing(&conn, msg.class_id)?;
+        let role = users::table
+            .find(msg.teacher_id)
+            .select(users::role)
+            .first::<Role>(&conn)
+            .optional()?
+            .ok_or_else(|| ApiError::NotFound(format!("User with id of {} not found.", msg.teacher_id)))?;
+        if role != Role::Teacher {
+            return Err(ApiError::BadRequest(format!(
+                "User with id of {} is a `{}`, only teachers can be linked to classes.", msg.teacher_id, role.as_str()
+            )));
+        }
+        diesel::insert_into(teacher_classes::table)
+            .values((
+                teacher_classes::user_id.eq(msg.teacher_id),
+                teacher_classes::class_id.eq(msg.class_id),
+            ))
+            .execute(&conn)
+            .map_err(|err| match err {
+                DatabaseError(DatabaseErrorKind::UniqueViolation, _) => ApiError::Conflict(format!(
+                    "Teacher with id of {} is already linked to class with id of {}.", msg.teacher_id, msg.class_id
+                )),
+                err => ApiError::from(err)
+            })?;
+        Ok(())
+    }
+}
+
+pub struct RemoveTeacher {
+    pub class_id: i32,
+    pub teacher_id: i32,
+    pub user: CurrentUser,
+}
+
+impl Message for RemoveTeacher {
+    type Result = Result<(), ApiError>;
+}
+
+impl Handler<RemoveTeacher> for Database {
+    type Result = Result<(), ApiError>;
+
+    fn handle(&mut self, msg: RemoveTeacher, _: &mut Self::Context) -> Self::Result {
+        require_role(&msg.user, &[Role::Admin])?;
+        let conn = self.conn()?;
+        let linked = teacher_classes::table.find((msg.teacher_id, msg.class_id));
+        match diesel::delete(linked).execute(&conn)? {
+            0 => Err(ApiError::NotFound(format!(
+                "Teacher with id of {} isn't linked to class with id of {}.", msg.teacher_id, msg.class_id
+            ))),
+            _ => Ok(())
+        }
+    }
+}
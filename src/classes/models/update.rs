@@ -0,0 +1,130 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use super::*;
+use super::imports::*;
+
+/// This is the update handler
+/// 
+/// Admins only. Moving a class to another school year moves its timetable and
+/// teaching assignments with it.
+pub fn update((request, user, id, updated_class): (HttpRequest<State>, CurrentUser, Path<i32>, Json<UpdateRequest>)) 
+    -> Box<Future<Item = HttpResponse, Error = actix_web::Error>> 
+{
+    request.state().db
+        .send(UpdateClass {
+            id: id.clone(),
+            user,
+            fields: updated_class.into_inner()
+        })
+        .from_err()
+        .and_then(move |updated_class| {
+            match updated_class {
+                Ok(class) => Ok(HttpResponse::Ok().json(
+                    UpdateResponse{ 
+                        message: format!("Updated class with id: {:?}.", id),
+                        class: Some(class),
+                    }
+                )),
+                Err(ApiError::NotFound(_)) => Err(actix_web::Error::from(
+                    ApiError::NotFound(format!("Class with id of {} does not exist.", id))
+                )),
+                Err(e) => Err(actix_web::Error::from(e))
+            }
+        }).responder()
+}
+
+/// `"homeroom_teacher_id": null` removes the homeroom teacher, leaving it out keeps it as is.
+#[derive(Serialize, Deserialize, AsChangeset)]
+#[table_name="classes"]
+pub struct UpdateRequest {
+    pub name: Option<String>,
+    pub school_year: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    pub homeroom_teacher_id: Option<Option<i32>>
+}
+
+impl Validate for UpdateRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut validator = Validator::new();
+        if let Some(ref name) = self.name {
+            validator.class_name("name", name);
+        }
+        if let Some(ref year) = self.school_year {
+            validator.school_year("school_year", year);
+        }
+        validator.finish()
+    }
+}
+
+pub struct UpdateClass {
+    pub id: i32,
+    pub user: CurrentUser,
+    pub fields: UpdateRequest,
+}
+
+impl Message for UpdateClass {
+    type Result = Result<Class, ApiError>;
+}
+
+impl Handler<UpdateClass> for Database {
+    type Result = Result<Class, ApiError>;
+
+    fn handle(&mut self, msg: UpdateClass, _: &mut Self::Context) -> Self::Result {
+        use crate::schema::{teaching_assignments, timetable};
+        require_role(&msg.user, &[Role::Admin])?;
+        msg.fields.validate()?;
+        require_changes(
+            msg.fields.name.is_some() || msg.fields.school_year.is_some() || msg.fields.homeroom_teacher_id.is_some()
+        )?;
+        let conn = self.conn()?;
+        let class_id = msg.id;
+        let fields = msg.fields;
+        let current = classes::table
+            .find(class_id)
+            .first::<Class>(&conn)
+            .optional()?
+            .ok_or_else(|| ApiError::NotFound(format!("Class with id of {} not found.", class_id)))?;
+        if let Some(Some(teacher_id)) = fields.homeroom_teacher_id {
+            require_teacher(&conn, teacher_id)?;
+        }
+        let new_name = fields.name.clone().unwrap_or_else(|| current.name.clone());
+
+        conn.transaction(|| {
+            let updated = diesel::update(classes::table.find(class_id))
+                .set(&fields)
+                .get_result::<Class>(&conn)
+                .map_err(|err| name_taken(err, &new_name))?;
+            if updated.school_year != current.school_year {
+                diesel::update(
+                    teaching_assignments::table
+                        .filter(teaching_assignments::class_id.eq(class_id))
+                        .filter(teaching_assignments::school_year.eq(&current.school_year))
+                )
+                    .set(teaching_assignments::school_year.eq(&updated.school_year))
+                    .execute(&conn)?;
+                diesel::update(
+                    timetable::table
+                        .filter(timetable::class_id.eq(class_id))
+                        .filter(timetable::school_year.eq(&current.school_year))
+                )
+                    .set(timetable::school_year.eq(&updated.school_year))
+                    .execute(&conn)
+                    .map_err(|err| match err {
+                        DatabaseError(DatabaseErrorKind::UniqueViolation, _) => ApiError::Conflict(format!(
+                            "The timetable of class `{}` clashes with the teachers' or rooms' lessons in {}.",
+                            updated.name, updated.school_year
+                        )),
+                        err => ApiError::from(err)
+                    })?;
+            }
+            Ok(updated)
+        })
+    }
+}
+
+#[derive(Serialize)]
+pub struct UpdateResponse {
+    pub message: String,
+    pub class: Option<Class>
+}
@@ -3,9 +3,11 @@
 
 use std::env;
 use diesel::pg::PgConnection;
-use diesel::r2d2::{ ConnectionManager, Pool };
+use diesel::r2d2::{ ConnectionManager, Pool, PooledConnection };
 use actix_web::actix::{Actor, SyncContext};
 
+use crate::error::ApiError;
+
 pub fn pool() -> Pool<ConnectionManager<PgConnection>> {
     let database_url = env::var("DATABASE_URL").expect("DATABASE_URL not set!");
     let manager = ConnectionManager::<PgConnection>::new(database_url);
@@ -14,6 +16,13 @@ pub fn pool() -> Pool<ConnectionManager<PgConnection>> {
 
 pub struct Database(pub Pool<ConnectionManager<PgConnection>>);
 
+impl Database {
+    /// Fails with `ApiError::Pool` instead of panicking when every connection is taken.
+    pub fn conn(&self) -> Result<PooledConnection<ConnectionManager<PgConnection>>, ApiError> {
+        Ok(self.0.get()?)
+    }
+}
+
 //unsafe impl Send for Database {}
 
 impl Actor for Database {
@@ -0,0 +1,153 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use std::fmt;
+
+use actix_web::{HttpResponse, ResponseError, http::StatusCode};
+use diesel::r2d2::PoolError;
+use diesel::result::Error as DieselError;
+use serde_json::Value;
+
+#[allow(unused_imports)] // it's useful to have these in scope
+use log::{debug, error, info, warn};
+
+use crate::validation::ValidationErrors;
+
+/// Every error a handler can respond with.
+///
+/// Only the 5xx ones are reported to Sentry, by `SentryMiddleware`.
+#[derive(Debug)]
+pub enum ApiError {
+    /// Responds with 400 Bad Request
+    BadRequest(String),
+    /// Responds with 401 Unauthorized
+    Unauthorized(String),
+    /// Responds with 403 Forbidden
+    Forbidden(String),
+    /// Responds with 404 Not Found
+    NotFound(String),
+    /// Responds with 409 Conflict
+    Conflict(String),
+    /// Responds with 422 Unprocessable Entity, every failed field is in `details`
+    Validation(ValidationErrors),
+    /// Responds with 500 Internal Server Error
+    Database(DieselError),
+    /// Responds with 500 Internal Server Error, for failures that aren't the database's
+    Internal(String),
+    /// Responds with 503 Service Unavailable when no connection is free
+    Pool(PoolError),
+}
+
+/// The body of every error response.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ErrorBody {
+    /// Doesn't change between versions, unlike `message`
+    pub code: String,
+    pub message: String,
+    /// e.g. the field errors of `validation_failed`, otherwise `null`
+    pub details: Option<Value>,
+}
+
+impl ApiError {
+    pub fn code(&self) -> &'static str {
+        match *self {
+            ApiError::BadRequest(_) => "bad_request",
+            ApiError::Unauthorized(_) => "unauthorized",
+            ApiError::Forbidden(_) => "forbidden",
+            ApiError::NotFound(_) => "not_found",
+            ApiError::Conflict(_) => "conflict",
+            ApiError::Validation(_) => "validation_failed",
+            ApiError::Database(_) => "database_error",
+            ApiError::Internal(_) => "internal_error",
+            ApiError::Pool(_) => "database_unavailable",
+        }
+    }
+
+    pub fn status(&self) -> StatusCode {
+        match *self {
+            ApiError::BadRequest(_) => StatusCode::BAD_REQUEST,
+            ApiError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            ApiError::Forbidden(_) => StatusCode::FORBIDDEN,
+            ApiError::NotFound(_) => StatusCode::NOT_FOUND,
+            ApiError::Conflict(_) => StatusCode::CONFLICT,
+            ApiError::Validation(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            ApiError::Database(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            ApiError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            ApiError::Pool(_) => StatusCode::SERVICE_UNAVAILABLE,
+        }
+    }
+
+    /// What's sent in the response, without the details of 5xx errors.
+    pub fn body(&self) -> ErrorBody {
+        let message = match *self {
+            // Don't leak queries and constraint names
+            ApiError::Database(_) => "Database error.".to_string(),
+            ApiError::Internal(_) => "Internal error.".to_string(),
+            _ => format!("{}", self),
+        };
+        ErrorBody {
+            code: self.code().to_string(),
+            message,
+            details: self.details(),
+        }
+    }
+
+    fn details(&self) -> Option<Value> {
+        match *self {
+            ApiError::Validation(ref err) => serde_json::to_value(&err.errors).ok(),
+            _ => None,
+        }
+    }
+}
+
+/// Diesel's `NotFound` comes from `.first()` and `.get_result()` finding no rows.
+impl From<DieselError> for ApiError {
+    fn from(err: DieselError) -> Self {
+        match err {
+            DieselError::NotFound => ApiError::NotFound("Not found.".to_string()),
+            err => ApiError::Database(err),
+        }
+    }
+}
+
+impl From<PoolError> for ApiError {
+    fn from(err: PoolError) -> Self {
+        ApiError::Pool(err)
+    }
+}
+
+impl From<ValidationErrors> for ApiError {
+    fn from(err: ValidationErrors) -> Self {
+        ApiError::Validation(err)
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ApiError::BadRequest(ref message) => write!(f, "{}", message),
+            ApiError::Unauthorized(ref message) => write!(f, "{}", message),
+            ApiError::Forbidden(ref message) => write!(f, "{}", message),
+            ApiError::NotFound(ref message) => write!(f, "{}", message),
+            ApiError::Conflict(ref message) => write!(f, "{}", message),
+            ApiError::Validation(ref err) => write!(f, "{}", err.message),
+            ApiError::Database(ref err) => write!(f, "Database error: {}", err),
+            ApiError::Internal(ref message) => write!(f, "Internal error: {}", message),
+            ApiError::Pool(ref err) => write!(f, "No database connection available: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+impl ResponseError for ApiError {
+    fn error_response(&self) -> HttpResponse {
+        let status = self.status();
+        if status.is_server_error() {
+            error!("{}", self);
+        } else {
+            info!("{} {}", status, self);
+        }
+        HttpResponse::build(status).json(self.body())
+    }
+}
@@ -0,0 +1,243 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+//!
+//! Spreadsheet exports. CSV is written a chunk of rows at a time so it can be
+//! streamed, XLSX is a zip and has to be built whole, in memory.
+
+use std::io::{Cursor, Write};
+
+use actix_web::{HttpResponse, dev::HttpResponseBuilder};
+use bytes::Bytes;
+use zip::write::{FileOptions, ZipWriter};
+
+use crate::error::ApiError;
+
+pub const CSV: &str = "text/csv; charset=utf-8";
+pub const XLSX: &str = "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet";
+
+/// So that Excel doesn't take the file for Windows-1250.
+const UTF8_BOM: &str = "\u{feff}";
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Cell {
+    Text(String),
+    Number(f64),
+    Empty,
+}
+
+impl From<String> for Cell {
+    fn from(text: String) -> Self {
+        Cell::Text(text)
+    }
+}
+
+impl<'a> From<&'a str> for Cell {
+    fn from(text: &'a str) -> Self {
+        Cell::Text(text.to_string())
+    }
+}
+
+impl From<i32> for Cell {
+    fn from(number: i32) -> Self {
+        Cell::Number(number.into())
+    }
+}
+
+impl From<u32> for Cell {
+    fn from(number: u32) -> Self {
+        Cell::Number(number.into())
+    }
+}
+
+impl From<f64> for Cell {
+    fn from(number: f64) -> Self {
+        Cell::Number(number)
+    }
+}
+
+impl<T: Into<Cell>> From<Option<T>> for Cell {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Cell::Empty, Into::into)
+    }
+}
+
+pub type Row = Vec<Cell>;
+
+/// The header row out of column names.
+pub fn header(columns: &[&str]) -> Row {
+    columns.iter().map(|&column| Cell::from(column)).collect()
+}
+
+/// Starts a download of the file.
+pub fn attachment(content_type: &str, file_name: &str) -> HttpResponseBuilder {
+    let mut response = HttpResponse::Ok();
+    response
+        .content_type(content_type)
+        .header("Content-Disposition", format!("attachment; filename=\"{}\"", file_name));
+    response
+}
+
+/// Rows as CSV, starting with a byte order mark if it's the beginning of the file.
+///
+/// Text that a spreadsheet would run as a formula is prefixed with `'`, see `defuse`.
+pub fn csv(rows: &[Row], first: bool) -> Result<Bytes, ApiError> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    for row in rows {
+        writer
+            .write_record(row.iter().map(|cell| match *cell {
+                Cell::Text(ref text) => defuse(text),
+                Cell::Number(number) => number.to_string(),
+                Cell::Empty => String::new(),
+            }))
+            .map_err(|err| ApiError::Internal(format!("Couldn't write CSV: {}", err)))?;
+    }
+    let written = writer
+        .into_inner()
+        .map_err(|err| ApiError::Internal(format!("Couldn't write CSV: {}", err)))?;
+    let mut chunk = if first { UTF8_BOM.as_bytes().to_vec() } else { Vec::new() };
+    chunk.extend(written);
+    Ok(Bytes::from(chunk))
+}
+
+/// Excel and LibreOffice take text starting with `=`, `+`, `-` or `@` for a formula,
+/// so a name like `=HYPERLINK(...)` would be run when the file is opened. A leading `'`
+/// makes it plain text again. Tabs and carriage returns can start a formula too.
+fn defuse(text: &str) -> String {
+    match text.chars().next() {
+        Some('=') | Some('+') | Some('-') | Some('@') | Some('\t') | Some('\r') => format!("'{}", text),
+        _ => text.to_string(),
+    }
+}
+
+/// A workbook with a single sheet, strings are inline so there's no shared string table.
+pub fn xlsx(sheet_name: &str, rows: &[Row]) -> Result<Vec<u8>, ApiError> {
+    // Excel's limits on sheet names
+    let sheet_name: String = sheet_name
+        .chars()
+        .filter(|c| !"[]:*?/\\".contains(*c))
+        .take(31)
+        .collect();
+
+    let mut sheet = String::from(concat!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+        r#"<worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"><sheetData>"#
+    ));
+    for (index, row) in rows.iter().enumerate() {
+        let number = index + 1;
+        sheet.push_str(&format!(r#"<row r="{}">"#, number));
+        for (column, cell) in row.iter().enumerate() {
+            let reference = format!("{}{}", column_name(column), number);
+            match *cell {
+                Cell::Text(ref text) => sheet.push_str(&format!(
+                    r#"<c r="{}" t="inlineStr"><is><t xml:space="preserve">{}</t></is></c>"#,
+                    reference, escape(text)
+                )),
+                Cell::Number(number) => sheet.push_str(&format!(r#"<c r="{}"><v>{}</v></c>"#, reference, number)),
+                Cell::Empty => {}
+            }
+        }
+        sheet.push_str("</row>");
+    }
+    sheet.push_str("</sheetData></worksheet>");
+
+    let files = [
+        ("[Content_Types].xml", String::from(concat!(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+            r#"<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">"#,
+            r#"<Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/>"#,
+            r#"<Default Extension="xml" ContentType="application/xml"/>"#,
+            r#"<Override PartName="/xl/workbook.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.sheet.main+xml"/>"#,
+            r#"<Override PartName="/xl/worksheets/sheet1.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.worksheet+xml"/>"#,
+            r#"</Types>"#
+        ))),
+        ("_rels/.rels", String::from(concat!(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+            r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">"#,
+            r#"<Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="xl/workbook.xml"/>"#,
+            r#"</Relationships>"#
+        ))),
+        ("xl/workbook.xml", format!(
+            concat!(
+                r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+                r#"<workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" "#,
+                r#"xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">"#,
+                r#"<sheets><sheet name="{}" sheetId="1" r:id="rId1"/></sheets></workbook>"#
+            ),
+            escape(&sheet_name)
+        )),
+        ("xl/_rels/workbook.xml.rels", String::from(concat!(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+            r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">"#,
+            r#"<Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="worksheets/sheet1.xml"/>"#,
+            r#"</Relationships>"#
+        ))),
+        ("xl/worksheets/sheet1.xml", sheet),
+    ];
+
+    let zip_error = |err| ApiError::Internal(format!("Couldn't write XLSX: {}", err));
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    for &(name, ref content) in files.iter() {
+        zip.start_file(name, FileOptions::default()).map_err(zip_error)?;
+        zip.write_all(content.as_bytes()).map_err(|err| zip_error(err.into()))?;
+    }
+    Ok(zip.finish().map_err(zip_error)?.into_inner())
+}
+
+/// `A`, ..., `Z`, `AA`, ... for the zero based column.
+fn column_name(column: usize) -> String {
+    let mut name = Vec::new();
+    let mut column = column + 1;
+    while column > 0 {
+        let rest = (column - 1) % 26;
+        name.push(b'A' + rest as u8);
+        column = (column - 1) / 26;
+    }
+    name.reverse();
+    String::from_utf8(name).unwrap_or_default()
+}
+
+/// Escapes text for XML, dropping the control characters XML can't contain.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(c),
+            c if (c as u32) < 0x20 => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defuses_formulas() {
+        assert_eq!(defuse("=HYPERLINK(\"http://example.com\")"), "'=HYPERLINK(\"http://example.com\")");
+        assert_eq!(defuse("+48123456789"), "'+48123456789");
+        assert_eq!(defuse("-1"), "'-1");
+        assert_eq!(defuse("@SUM(A1)"), "'@SUM(A1)");
+        assert_eq!(defuse("\t=1"), "'\t=1");
+        assert_eq!(defuse("\r=1"), "'\r=1");
+    }
+
+    #[test]
+    fn leaves_plain_text_alone() {
+        assert_eq!(defuse("Kowalski"), "Kowalski");
+        assert_eq!(defuse("a=b"), "a=b");
+        assert_eq!(defuse(""), "");
+    }
+
+    #[test]
+    fn numbers_arent_defused() {
+        let written = csv(&[vec![Cell::Number(-1.5), Cell::from("-1.5")]], false).unwrap();
+        assert_eq!(&written[..], b"-1.5,'-1.5\n");
+    }
+}
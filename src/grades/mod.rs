@@ -0,0 +1,19 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+extern crate serde_derive;
+extern crate actix_web;
+
+mod models;
+
+pub use models::{
+    Grade,
+    create,
+    read,
+    update,
+    delete,
+    averages,
+    class_sheet,
+    class_sheet_csv,
+    class_sheet_xlsx
+};
@@ -0,0 +1,75 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use crate::schema::grades;
+use crate::database::Database;
+use crate::error::ApiError;
+use actix_web::actix::{Message, Handler};
+use chrono::NaiveDate;
+use diesel;
+use diesel::result::{DatabaseErrorKind, Error::DatabaseError};
+
+#[allow(unused_imports)] // Throws errors without this import, but throws warning with it :/
+use diesel::prelude::*;
+
+mod imports;
+
+mod scale;
+pub use scale::{Modifier, Category};
+
+#[derive(Queryable, Serialize, Deserialize, Debug)]
+pub struct Grade {
+    pub id: i32,
+    pub student_id: i32,
+    pub subject_id: i32,
+    pub value: i16,
+    pub modifier: Option<Modifier>,
+    pub weight: i16,
+    pub category: Category,
+    pub date: NaiveDate,
+    /// Who gave the grade
+    pub teacher_id: Option<i32>
+}
+
+impl Grade {
+    /// What the grade counts as in an average.
+    pub fn numeric(&self) -> f64 {
+        scale::numeric(self.value, self.modifier)
+    }
+}
+
+/// Turns the subject's foreign key violation into a 404 Not Found.
+fn subject_missing(err: diesel::result::Error, subject_id: Option<i32>) -> ApiError {
+    match err {
+        DatabaseError(DatabaseErrorKind::ForeignKeyViolation, ref info)
+            if info.constraint_name() == Some("grades_subject_id_fkey") =>
+        {
+            ApiError::NotFound(format!("Subject with id of {} not found.", subject_id.unwrap_or_default()))
+        }
+        err => ApiError::from(err)
+    }
+}
+
+/* Create */
+mod create;
+pub use create::*;
+
+/* Read */
+mod read;
+pub use read::*;
+
+/* Update */
+mod update;
+pub use update::*;
+
+/* Delete */
+mod delete;
+pub use delete::*;
+
+/* Averages */
+mod averages;
+pub use averages::*;
+
+/* Class sheet */
+mod sheet;
+pub use sheet::*;
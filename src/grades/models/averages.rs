@@ -0,0 +1,81 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use super::*;
+use super::imports::*;
+
+use std::collections::BTreeMap;
+
+/// Weighted per subject averages of the student with the id from the path.
+/// 
+/// Same access rules as reading the grades.
+pub fn averages((request, user, student_id): (HttpRequest<State>, CurrentUser, Path<i32>)) 
+    -> Box<Future<Item = Json<Vec<SubjectAverage>>, Error = actix_web::Error>> 
+{
+    debug!("Request to read averages of student with id of {}.", student_id.as_ref());
+    request.state().db
+        .send(AveragesRequest{student_id: student_id.into_inner(), user})
+        .from_err()
+        .and_then(|res| res.map(Json).map_err(actix_web::Error::from))
+        .responder()
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SubjectAverage {
+    pub subject_id: i32,
+    pub subject: String,
+    /// Rounded to two decimal places
+    pub average: f64,
+    pub count: usize,
+}
+
+/// Sum of value times weight over the sum of weights.
+pub fn weighted_average(graded: &[Grade]) -> f64 {
+    let (sum, weights) = graded.iter().fold((0.0, 0.0), |(sum, weights), grade| {
+        let weight = f64::from(grade.weight);
+        (sum + grade.numeric() * weight, weights + weight)
+    });
+    if weights > 0.0 {
+        (sum / weights * 100.0).round() / 100.0
+    } else {
+        0.0
+    }
+}
+
+pub struct AveragesRequest {
+    pub student_id: i32,
+    pub user: CurrentUser,
+}
+
+impl Message for AveragesRequest {
+    type Result = Result<Vec<SubjectAverage>, ApiError>;
+}
+
+impl Handler<AveragesRequest> for Database {
+    type Result = Result<Vec<SubjectAverage>, ApiError>;
+
+    fn handle(&mut self, msg: AveragesRequest, _: &mut Self::Context) -> Self::Result {
+        use crate::schema::subjects;
+        let conn = self.conn()?;
+        require_student(&conn, &msg.user, msg.student_id)?;
+
+        let graded = grades::table
+            .inner_join(subjects::table)
+            .filter(grades::student_id.eq(msg.student_id))
+            .select((grades::all_columns, subjects::name))
+            .load::<(Grade, String)>(&conn)?;
+
+        let mut by_subject: BTreeMap<(String, i32), Vec<Grade>> = BTreeMap::new();
+        for (grade, subject) in graded {
+            by_subject.entry((subject, grade.subject_id)).or_insert_with(Vec::new).push(grade);
+        }
+        Ok(by_subject.into_iter().map(|((subject, subject_id), graded)| {
+            SubjectAverage {
+                subject_id,
+                subject,
+                average: weighted_average(&graded),
+                count: graded.len(),
+            }
+        }).collect())
+    }
+}
@@ -0,0 +1,115 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use super::*;
+use super::imports::*;
+
+use chrono::Local;
+
+/// This is the create handler.
+/// 
+/// Admins, or teachers assigned to teach the subject in the student's class.
+pub fn create((request, user, new_grade): (HttpRequest<State>, CurrentUser, Json<CreateRequest>)) 
+    -> Box<Future<Item = Json<CreateResponse>, Error = actix_web::Error>> 
+{
+    debug!("Request to create grade: {:?}", &new_grade);
+    request.state().db
+        .send(CreateGrade {
+            user,
+            fields: new_grade.into_inner()
+        })
+        .from_err()
+        .and_then(|res| {
+            res.map(|grade| {
+                info!("Successfully added grade");
+                Json(CreateResponse {
+                    message: "Success!".to_string(),
+                    grade: Some(grade)
+                })
+            }).map_err(actix_web::Error::from)
+        })
+        .responder()
+}
+
+fn default_weight() -> i16 {
+    1
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct CreateRequest {
+    student_id: i32,
+    subject_id: i32,
+    value: i16,
+    modifier: Option<Modifier>,
+    #[serde(default = "default_weight")]
+    weight: i16,
+    category: Category,
+    /// Today if not set
+    date: Option<NaiveDate>
+}
+
+impl Validate for CreateRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut validator = Validator::new();
+        scale::check_value(&mut validator, self.value, self.modifier);
+        validator
+            .check("weight", self.weight > 0, "must be greater than 0")
+            .finish()
+    }
+}
+
+/// id should be set automatically
+#[derive(Insertable)]
+#[table_name="grades"]
+struct NewGrade {
+    student_id: i32,
+    subject_id: i32,
+    value: i16,
+    modifier: Option<Modifier>,
+    weight: i16,
+    category: Category,
+    date: NaiveDate,
+    teacher_id: Option<i32>
+}
+
+#[derive(Serialize)]
+pub struct CreateResponse {
+    pub message: String,
+    pub grade: Option<Grade>
+}
+
+pub struct CreateGrade {
+    pub user: CurrentUser,
+    pub fields: CreateRequest,
+}
+
+impl Message for CreateGrade {
+    type Result = Result<Grade, ApiError>;
+}
+
+impl Handler<CreateGrade> for Database {
+    type Result = Result<Grade, ApiError>;
+
+    fn handle(&mut self, msg: CreateGrade, _: &mut Self::Context) -> Self::Result {
+        msg.fields.validate()?;
+        let conn = self.conn()?;
+        require_student_assignment(&conn, &msg.user, msg.fields.student_id, msg.fields.subject_id)?;
+        let fields = msg.fields;
+        let date = fields.date.unwrap_or_else(|| Local::today().naive_local());
+        require_open(&conn, &school_year_of(date))?;
+        let new_grade = NewGrade {
+            student_id: fields.student_id,
+            subject_id: fields.subject_id,
+            value: fields.value,
+            modifier: fields.modifier,
+            weight: fields.weight,
+            category: fields.category,
+            date,
+            teacher_id: Some(msg.user.id),
+        };
+        diesel::insert_into(grades::table)
+            .values(&new_grade)
+            .get_result::<Grade>(&conn)
+            .map_err(|err| subject_missing(err, Some(new_grade.subject_id)))
+    }
+}
@@ -0,0 +1,58 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use super::*;
+use super::imports::*;
+
+/// This is the delete handler
+/// 
+/// Admins, or teachers assigned to teach the subject in the student's class.
+pub fn delete((request, user, id): (HttpRequest<State>, CurrentUser, Path<i32>)) 
+    -> Box<Future<Item = HttpResponse, Error = actix_web::Error>> 
+{
+    debug!("Request to delete grade with id of {}.", id.as_ref());
+    
+    request.state().db
+        .send(DeleteRequest{id: id.clone(), user})
+        .from_err()
+        .and_then(move |res| {
+            res.map(|_| {
+                info!("Grade with id of {} successfully deleted.", id);
+                HttpResponse::Ok().json(DeleteResponse {
+                    message: format!("Deleted grade with id: {:?}.", id)
+                })
+            }).map_err(actix_web::Error::from)
+        }).responder()
+}
+
+pub struct DeleteRequest {
+    pub id: i32,
+    pub user: CurrentUser,
+}
+
+impl Message for DeleteRequest {
+    type Result = Result<usize, ApiError>;
+}
+
+impl Handler<DeleteRequest> for Database {
+    type Result = Result<usize, ApiError>;
+
+    fn handle(&mut self, msg: DeleteRequest, _: &mut Self::Context) -> Self::Result {
+        use crate::schema::grades::dsl::*;
+        let conn = self.conn()?;
+        let (graded_student, graded_subject, graded_on) = grades
+            .find(msg.id)
+            .select((student_id, subject_id, date))
+            .first::<(i32, i32, NaiveDate)>(&conn)
+            .optional()?
+            .ok_or_else(|| ApiError::NotFound(format!("Grade with id of {} not found.", msg.id)))?;
+        require_student_assignment(&conn, &msg.user, graded_student, graded_subject)?;
+        require_open(&conn, &school_year_of(graded_on))?;
+        Ok(diesel::delete(grades.find(msg.id)).execute(&conn)?)
+    }
+}
+
+#[derive(Serialize)]
+pub struct DeleteResponse {
+    pub message: String,
+}
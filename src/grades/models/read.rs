@@ -0,0 +1,42 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use super::*;
+use super::imports::*;
+
+/// Lists the grades of the student with the id from the path.
+/// 
+/// Parents only see their children's grades and students only their own.
+pub fn read((request, user, student_id): (HttpRequest<State>, CurrentUser, Path<i32>)) 
+    -> Box<Future<Item = Json<Vec<Grade>>, Error = actix_web::Error>> 
+{
+    debug!("Request to read grades of student with id of {}.", student_id.as_ref());
+    request.state().db
+        .send(ReadRequest{student_id: student_id.into_inner(), user})
+        .from_err()
+        .and_then(|res| res.map(Json).map_err(actix_web::Error::from))
+        .responder()
+}
+
+pub struct ReadRequest {
+    pub student_id: i32,
+    pub user: CurrentUser,
+}
+
+impl Message for ReadRequest {
+    type Result = Result<Vec<Grade>, ApiError>;
+}
+
+impl Handler<ReadRequest> for Database {
+    type Result = Result<Vec<Grade>, ApiError>;
+
+    fn handle(&mut self, msg: ReadRequest, _: &mut Self::Context) -> Self::Result {
+        use crate::schema::grades::dsl::*;
+        let conn = self.conn()?;
+        require_student(&conn, &msg.user, msg.student_id)?;
+        Ok(grades
+            .filter(student_id.eq(msg.student_id))
+            .order((subject_id, date, id))
+            .load::<Grade>(&conn)?)
+    }
+}
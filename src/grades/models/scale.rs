@@ -0,0 +1,144 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use std::io::Write;
+
+use diesel::deserialize::{self, FromSql};
+use diesel::pg::Pg;
+use diesel::serialize::{self, IsNull, Output, ToSql};
+use diesel::sql_types::Text;
+
+use crate::validation::Validator;
+
+/// `4+` is worth 4.5, `4-` is worth 3.75.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, AsExpression, FromSqlRow)]
+#[sql_type = "Text"]
+pub enum Modifier {
+    #[serde(rename = "+")]
+    Plus,
+    #[serde(rename = "-")]
+    Minus,
+}
+
+impl Modifier {
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            Modifier::Plus => "+",
+            Modifier::Minus => "-",
+        }
+    }
+}
+
+impl ToSql<Text, Pg> for Modifier {
+    fn to_sql<W: Write>(&self, out: &mut Output<W, Pg>) -> serialize::Result {
+        out.write_all(self.as_str().as_bytes())?;
+        Ok(IsNull::No)
+    }
+}
+
+impl FromSql<Text, Pg> for Modifier {
+    fn from_sql(bytes: Option<&[u8]>) -> deserialize::Result<Self> {
+        match not_none!(bytes) {
+            b"+" => Ok(Modifier::Plus),
+            b"-" => Ok(Modifier::Minus),
+            _ => Err("Unrecognized grade modifier".into()),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, AsExpression, FromSqlRow)]
+#[serde(rename_all = "lowercase")]
+#[sql_type = "Text"]
+pub enum Category {
+    Test,
+    Quiz,
+    Homework,
+}
+
+impl Category {
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            Category::Test => "test",
+            Category::Quiz => "quiz",
+            Category::Homework => "homework",
+        }
+    }
+}
+
+impl ToSql<Text, Pg> for Category {
+    fn to_sql<W: Write>(&self, out: &mut Output<W, Pg>) -> serialize::Result {
+        out.write_all(self.as_str().as_bytes())?;
+        Ok(IsNull::No)
+    }
+}
+
+impl FromSql<Text, Pg> for Category {
+    fn from_sql(bytes: Option<&[u8]>) -> deserialize::Result<Self> {
+        match not_none!(bytes) {
+            b"test" => Ok(Category::Test),
+            b"quiz" => Ok(Category::Quiz),
+            b"homework" => Ok(Category::Homework),
+            _ => Err("Unrecognized grade category".into()),
+        }
+    }
+}
+
+/// What the grade counts as in an average.
+pub fn numeric(value: i16, modifier: Option<Modifier>) -> f64 {
+    let value = f64::from(value);
+    match modifier {
+        Some(Modifier::Plus) => value + 0.5,
+        Some(Modifier::Minus) => value - 0.25,
+        None => value,
+    }
+}
+
+pub const MIN_VALUE: i16 = 1;
+pub const MAX_VALUE: i16 = 6;
+
+/// From 1 to 6, except for `6+` and `1-` which are off the scale.
+pub fn on_scale(value: i16, modifier: Option<Modifier>) -> bool {
+    match (value, modifier) {
+        (MAX_VALUE, Some(Modifier::Plus)) | (MIN_VALUE, Some(Modifier::Minus)) => false,
+        (value, _) => value >= MIN_VALUE && value <= MAX_VALUE,
+    }
+}
+
+/// The value has to be on the scale, and only then is the modifier checked against it.
+pub fn check_value(validator: &mut Validator, value: i16, modifier: Option<Modifier>) -> &mut Validator {
+    if value >= MIN_VALUE && value <= MAX_VALUE {
+        validator.check("modifier", on_scale(value, modifier), "`6+` and `1-` aren't on the scale")
+    } else {
+        validator.range("value", value.into(), MIN_VALUE.into(), MAX_VALUE.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ends_of_the_scale() {
+        assert!(on_scale(1, None));
+        assert!(on_scale(6, None));
+        assert!(on_scale(1, Some(Modifier::Plus)));
+        assert!(on_scale(6, Some(Modifier::Minus)));
+    }
+
+    #[test]
+    fn off_the_scale() {
+        assert!(!on_scale(6, Some(Modifier::Plus)));
+        assert!(!on_scale(1, Some(Modifier::Minus)));
+        assert!(!on_scale(0, None));
+        assert!(!on_scale(7, None));
+    }
+
+    #[test]
+    fn value_is_reported_before_modifier() {
+        let errors = check_value(&mut Validator::new(), 0, Some(Modifier::Minus)).finish().unwrap_err();
+        assert_eq!(errors.errors.len(), 1);
+        assert_eq!(errors.errors[0].field, "value");
+        let errors = check_value(&mut Validator::new(), 6, Some(Modifier::Plus)).finish().unwrap_err();
+        assert_eq!(errors.errors[0].field, "modifier");
+    }
+}
@@ -0,0 +1,245 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use super::*;
+use super::imports::*;
+
+use std::collections::BTreeMap;
+use bytes::Bytes;
+use diesel::pg::PgConnection;
+use futures::{stream, Stream};
+use crate::classes::Class;
+use crate::export::{self, Cell, Row};
+use crate::students::{RegisterKey, after_in_register, live};
+
+/// The grades of every student in the class with the id from the path, a row per student and subject.
+///
+/// Admins, or teachers and the homeroom teacher of the class.
+pub fn class_sheet((request, user, class_id): (HttpRequest<State>, CurrentUser, Path<i32>))
+    -> Box<Future<Item = Json<ClassSheet>, Error = actix_web::Error>>
+{
+    debug!("Request to read grade sheet of class with id of {}.", class_id.as_ref());
+    request.state().db
+        .send(ClassSheetRequest{class_id: class_id.into_inner(), user})
+        .from_err()
+        .and_then(|res| res.map(Json).map_err(actix_web::Error::from))
+        .responder()
+}
+
+/// The grade sheet as CSV, see `class_sheet`.
+///
+/// Read and sent `CSV_PAGE_SIZE` students at a time, so the whole sheet never sits in memory.
+pub fn class_sheet_csv((request, user, class_id): (HttpRequest<State>, CurrentUser, Path<i32>))
+    -> Box<Future<Item = HttpResponse, Error = actix_web::Error>>
+{
+    debug!("Request to export grade sheet of class with id of {} to CSV.", class_id.as_ref());
+    let db = request.state().db.clone();
+    let class_id = class_id.into_inner();
+    // The first page is read before responding, so errors still get their status code
+    db.send(SheetPage{class_id, user: user.clone(), after: None})
+        .from_err()
+        .and_then(move |res| -> Result<HttpResponse, actix_web::Error> {
+            let (sheet, next) = res?;
+            let first = export::csv(&sheet.rows(), true)?;
+
+            let rest = stream::unfold(next, move |after| {
+                after.map(|after| {
+                    db.send(SheetPage{class_id, user: user.clone(), after: Some(after)})
+                        .from_err()
+                        .and_then(|res| -> Result<(Bytes, Option<RegisterKey>), actix_web::Error> {
+                            let (sheet, next) = res?;
+                            let rows: Vec<Row> = sheet.rows.iter().map(row).collect();
+                            Ok((export::csv(&rows, false)?, next))
+                        })
+                })
+            });
+            Ok(export::attachment(export::CSV, &sheet.file_name("csv"))
+                .streaming(stream::once::<_, actix_web::Error>(Ok(first)).chain(rest)))
+        })
+        .responder()
+}
+
+/// The grade sheet as XLSX, see `class_sheet`.
+pub fn class_sheet_xlsx((request, user, class_id): (HttpRequest<State>, CurrentUser, Path<i32>))
+    -> Box<Future<Item = HttpResponse, Error = actix_web::Error>>
+{
+    debug!("Request to export grade sheet of class with id of {} to XLSX.", class_id.as_ref());
+    request.state().db
+        .send(ClassSheetRequest{class_id: class_id.into_inner(), user})
+        .from_err()
+        .and_then(|res| -> Result<HttpResponse, actix_web::Error> {
+            let sheet = res?;
+            let xlsx = export::xlsx(&sheet.class_name, &sheet.rows())?;
+            Ok(export::attachment(export::XLSX, &sheet.file_name("xlsx")).body(xlsx))
+        })
+        .responder()
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SheetRow {
+    pub student_id: i32,
+    pub first_name: String,
+    pub last_name: String,
+    pub subject_id: i32,
+    pub subject: String,
+    /// Oldest first, e.g. `["5+", "4", "3-"]`
+    pub grades: Vec<String>,
+    /// Weighted, rounded to two decimal places
+    pub average: f64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ClassSheet {
+    pub class_id: i32,
+    pub class_name: String,
+    pub school_year: String,
+    /// Students like in the class register, each one's subjects alphabetically
+    pub rows: Vec<SheetRow>,
+}
+
+impl ClassSheet {
+    /// e.g. `grades-3d-2018-2019.csv`
+    fn file_name(&self, extension: &str) -> String {
+        format!("grades-{}-{}.{}", self.class_name, self.school_year, extension)
+            .chars()
+            .map(|c| if c.is_alphanumeric() || c == '.' || c == '-' { c } else { '-' })
+            .collect()
+    }
+
+    /// The spreadsheet, with the header.
+    fn rows(&self) -> Vec<Row> {
+        let mut rows = vec![export::header(&[
+            "student_id", "first_name", "last_name", "subject_id", "subject", "grades", "average"
+        ])];
+        rows.extend(self.rows.iter().map(row));
+        rows
+    }
+}
+
+fn row(row: &SheetRow) -> Row {
+    vec![
+        Cell::from(row.student_id),
+        Cell::from(row.first_name.as_str()),
+        Cell::from(row.last_name.as_str()),
+        Cell::from(row.subject_id),
+        Cell::from(row.subject.as_str()),
+        Cell::from(row.grades.join(", ")),
+        Cell::from(row.average),
+    ]
+}
+
+/// Students on a page of the CSV.
+const CSV_PAGE_SIZE: i64 = 100;
+
+pub struct ClassSheetRequest {
+    pub class_id: i32,
+    pub user: CurrentUser,
+}
+
+impl Message for ClassSheetRequest {
+    type Result = Result<ClassSheet, ApiError>;
+}
+
+impl Handler<ClassSheetRequest> for Database {
+    type Result = Result<ClassSheet, ApiError>;
+
+    fn handle(&mut self, msg: ClassSheetRequest, _: &mut Self::Context) -> Self::Result {
+        let conn = self.conn()?;
+        Ok(read_sheet(&conn, &msg.user, msg.class_id, None, None)?.0)
+    }
+}
+
+/// The part of the grade sheet with the `CSV_PAGE_SIZE` students after `after` in the class register.
+pub struct SheetPage {
+    pub class_id: i32,
+    pub user: CurrentUser,
+    pub after: Option<RegisterKey>,
+}
+
+impl Message for SheetPage {
+    type Result = Result<(ClassSheet, Option<RegisterKey>), ApiError>;
+}
+
+impl Handler<SheetPage> for Database {
+    type Result = Result<(ClassSheet, Option<RegisterKey>), ApiError>;
+
+    fn handle(&mut self, msg: SheetPage, _: &mut Self::Context) -> Self::Result {
+        let conn = self.conn()?;
+        read_sheet(&conn, &msg.user, msg.class_id, msg.after, Some(CSV_PAGE_SIZE))
+    }
+}
+
+/// The sheet of up to `limit` students after `after` in the class register, and where the
+/// next page starts if there may be one.
+///
+/// Only subjects the student has grades in are listed.
+fn read_sheet(conn: &PgConnection, user: &CurrentUser, class_id: i32, after: Option<RegisterKey>, limit: Option<i64>)
+    -> Result<(ClassSheet, Option<RegisterKey>), ApiError>
+{
+    use crate::schema::{classes, students, subjects};
+    require_taught_class(conn, user, class_id)?;
+    let class = classes::table
+        .find(class_id)
+        .first::<Class>(conn)
+        .optional()?
+        .ok_or_else(|| ApiError::NotFound(format!("Class with id of {} not found.", class_id)))?;
+
+    let mut in_class = students::table
+        .filter(students::class_id.eq(class_id))
+        .filter(live())
+        .select((students::last_name, students::first_name, students::id))
+        .order((students::last_name, students::first_name, students::id))
+        .into_boxed();
+    if let Some(key) = after {
+        in_class = in_class.filter(after_in_register(key));
+    }
+    if let Some(limit) = limit {
+        in_class = in_class.limit(limit);
+    }
+    let page = in_class.load::<RegisterKey>(conn)?;
+    let next = match limit {
+        Some(limit) if page.len() as i64 == limit => page.last().cloned(),
+        _ => None,
+    };
+
+    let graded = grades::table
+        .inner_join(subjects::table)
+        .filter(grades::student_id.eq_any(page.iter().map(|&(_, _, id)| id).collect::<Vec<_>>()))
+        .select((grades::all_columns, subjects::name))
+        .order((grades::date, grades::id))
+        .load::<(Grade, String)>(conn)?;
+
+    let mut by_student: BTreeMap<i32, BTreeMap<(String, i32), Vec<Grade>>> = BTreeMap::new();
+    for (grade, subject) in graded {
+        by_student
+            .entry(grade.student_id)
+            .or_insert_with(BTreeMap::new)
+            .entry((subject, grade.subject_id))
+            .or_insert_with(Vec::new)
+            .push(grade);
+    }
+
+    let mut rows = Vec::new();
+    for (last_name, first_name, student_id) in page {
+        for ((subject, subject_id), graded) in by_student.remove(&student_id).unwrap_or_default() {
+            rows.push(SheetRow {
+                student_id,
+                first_name: first_name.clone(),
+                last_name: last_name.clone(),
+                subject_id,
+                subject,
+                grades: graded.iter().map(|grade| {
+                    format!("{}{}", grade.value, grade.modifier.map_or("", |modifier| modifier.as_str()))
+                }).collect(),
+                average: weighted_average(&graded),
+            });
+        }
+    }
+
+    Ok((ClassSheet {
+        class_id: class.id,
+        class_name: class.name,
+        school_year: class.school_year,
+        rows,
+    }, next))
+}
@@ -0,0 +1,109 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use super::*;
+use super::imports::*;
+
+/// This is the update handler
+/// 
+/// Admins, or teachers assigned to teach the subject in the student's class.
+pub fn update((request, user, id, updated_grade): (HttpRequest<State>, CurrentUser, Path<i32>, Json<UpdateRequest>)) 
+    -> Box<Future<Item = HttpResponse, Error = actix_web::Error>> 
+{
+    request.state().db
+        .send(UpdateGrade {
+            id: id.clone(),
+            user,
+            fields: updated_grade.into_inner()
+        })
+        .from_err()
+        .and_then(move |updated_grade| {
+            updated_grade.map(|grade| {
+                HttpResponse::Ok().json(UpdateResponse { 
+                    message: format!("Updated grade with id: {:?}.", id),
+                    grade: Some(grade),
+                })
+            }).map_err(actix_web::Error::from)
+        }).responder()
+}
+
+/// `"modifier": null` removes the modifier, leaving it out keeps it as is.
+#[derive(Serialize, Deserialize, AsChangeset)]
+#[table_name="grades"]
+pub struct UpdateRequest {
+    pub subject_id: Option<i32>,
+    pub value: Option<i16>,
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    pub modifier: Option<Option<Modifier>>,
+    pub weight: Option<i16>,
+    pub category: Option<Category>,
+    pub date: Option<NaiveDate>
+}
+
+/// Only the fields that are being changed are checked, a lone value or modifier
+/// is checked together with the stored other half in the handler.
+impl Validate for UpdateRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut validator = Validator::new();
+        if let Some(value) = self.value {
+            validator.range("value", value.into(), scale::MIN_VALUE.into(), scale::MAX_VALUE.into());
+        }
+        if let Some(weight) = self.weight {
+            validator.check("weight", weight > 0, "must be greater than 0");
+        }
+        validator.finish()
+    }
+}
+
+pub struct UpdateGrade {
+    pub id: i32,
+    pub user: CurrentUser,
+    pub fields: UpdateRequest,
+}
+
+impl Message for UpdateGrade {
+    type Result = Result<Grade, ApiError>;
+}
+
+impl Handler<UpdateGrade> for Database {
+    type Result = Result<Grade, ApiError>;
+
+    fn handle(&mut self, msg: UpdateGrade, _: &mut Self::Context) -> Self::Result {
+        use crate::schema::grades::dsl::*;
+        require_changes(
+            msg.fields.subject_id.is_some() || msg.fields.value.is_some() || msg.fields.modifier.is_some()
+                || msg.fields.weight.is_some() || msg.fields.category.is_some() || msg.fields.date.is_some()
+        )?;
+        msg.fields.validate()?;
+        let conn = self.conn()?;
+        let (graded_student, graded_subject, graded_on, current_value, current_modifier) = grades
+            .find(msg.id)
+            .select((student_id, subject_id, date, value, modifier))
+            .first::<(i32, i32, NaiveDate, i16, Option<Modifier>)>(&conn)
+            .optional()?
+            .ok_or_else(|| ApiError::NotFound(format!("Grade with id of {} not found.", msg.id)))?;
+        scale::check_value(
+            &mut Validator::new(),
+            msg.fields.value.unwrap_or(current_value),
+            msg.fields.modifier.unwrap_or(current_modifier)
+        ).finish()?;
+        require_student_assignment(&conn, &msg.user, graded_student, graded_subject)?;
+        if let Some(new_subject) = msg.fields.subject_id {
+            require_student_assignment(&conn, &msg.user, graded_student, new_subject)?;
+        }
+        require_open(&conn, &school_year_of(graded_on))?;
+        if let Some(new_date) = msg.fields.date {
+            require_open(&conn, &school_year_of(new_date))?;
+        }
+        diesel::update(grades.find(msg.id))
+            .set(&msg.fields)
+            .get_result::<Grade>(&conn)
+            .map_err(|err| subject_missing(err, msg.fields.subject_id))
+    }
+}
+
+#[derive(Serialize)]
+pub struct UpdateResponse {
+    pub message: String,
+    pub grade: Option<Grade>
+}
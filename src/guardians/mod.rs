@@ -0,0 +1,19 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+extern crate serde_derive;
+extern crate actix_web;
+
+mod models;
+
+pub use models::{
+    GuardianContact,
+    contacts,
+    create,
+    read,
+    update,
+    delete,
+    children,
+    child_grades,
+    child_attendance
+};
@@ -0,0 +1,76 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use crate::schema::guardians;
+use crate::database::Database;
+use crate::error::ApiError;
+use crate::students::{PhoneNumber, RawPhoneNumber, live, require_live};
+use actix_web::actix::{Message, Handler};
+use diesel;
+use diesel::pg::PgConnection;
+
+#[allow(unused_imports)] // Throws errors without this import, but throws warning with it :/
+use diesel::prelude::*;
+
+mod imports;
+
+mod relationship;
+pub use relationship::Relationship;
+
+/// Links a `parent` account to one of their children.
+#[derive(Queryable, Insertable, Serialize, Deserialize, Debug)]
+#[table_name="guardians"]
+pub struct Guardian {
+    pub user_id: i32,
+    pub student_id: i32,
+    pub relationship: Relationship,
+    pub phone_number: Option<PhoneNumber>,
+    pub email: Option<String>
+}
+
+/// A guardian of a student, with the login of their account.
+#[derive(Queryable, Serialize, Deserialize, Debug)]
+pub struct GuardianContact {
+    pub user_id: i32,
+    pub login: String,
+    pub relationship: Relationship,
+    pub phone_number: Option<PhoneNumber>,
+    pub email: Option<String>
+}
+
+/// Every guardian of the student.
+pub fn contacts(conn: &PgConnection, student: i32) -> Result<Vec<GuardianContact>, ApiError> {
+    use crate::schema::users;
+    Ok(guardians::table
+        .inner_join(users::table)
+        .filter(guardians::student_id.eq(student))
+        .select((
+            guardians::user_id,
+            users::login,
+            guardians::relationship,
+            guardians::phone_number,
+            guardians::email,
+        ))
+        .order(guardians::user_id)
+        .load::<GuardianContact>(conn)?)
+}
+
+/* Create */
+mod create;
+pub use create::*;
+
+/* Read */
+mod read;
+pub use read::*;
+
+/* Update */
+mod update;
+pub use update::*;
+
+/* Delete */
+mod delete;
+pub use delete::*;
+
+/* Children */
+mod children;
+pub use children::*;
@@ -0,0 +1,142 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use super::*;
+use super::imports::*;
+
+use crate::attendance::{Attendance, DateRange};
+use crate::grades::Grade;
+use crate::students::Student;
+
+/// Lists the children of the logged in parent.
+pub fn children((request, user): (HttpRequest<State>, CurrentUser))
+    -> Box<Future<Item = Json<Vec<Child>>, Error = actix_web::Error>>
+{
+    debug!("Request to read children of user with id of {}.", user.id);
+    request.state().db
+        .send(ChildrenRequest{user})
+        .from_err()
+        .and_then(|res| res.map(Json).map_err(actix_web::Error::from))
+        .responder()
+}
+
+/// Lists the grades of one of the logged in parent's children.
+pub fn child_grades((request, user, student_id): (HttpRequest<State>, CurrentUser, Path<i32>))
+    -> Box<Future<Item = Json<Vec<Grade>>, Error = actix_web::Error>>
+{
+    debug!("Request to read grades of child with id of {}.", student_id.as_ref());
+    request.state().db
+        .send(ChildGradesRequest{student_id: student_id.into_inner(), user})
+        .from_err()
+        .and_then(|res| res.map(Json).map_err(actix_web::Error::from))
+        .responder()
+}
+
+/// Lists the attendance of one of the logged in parent's children,
+/// optionally only `from` and/or `to` a date.
+pub fn child_attendance((request, user, student_id, range): (HttpRequest<State>, CurrentUser, Path<i32>, Query<DateRange>))
+    -> Box<Future<Item = Json<Vec<Attendance>>, Error = actix_web::Error>>
+{
+    debug!("Request to read attendance of child with id of {}.", student_id.as_ref());
+    request.state().db
+        .send(ChildAttendanceRequest{
+            student_id: student_id.into_inner(),
+            range: range.into_inner(),
+            user
+        })
+        .from_err()
+        .and_then(|res| res.map(Json).map_err(actix_web::Error::from))
+        .responder()
+}
+
+/// A student together with who the parent is to them.
+#[derive(Serialize, Debug)]
+pub struct Child {
+    #[serde(flatten)]
+    pub student: Student,
+    pub relationship: Relationship,
+}
+
+pub struct ChildrenRequest {
+    pub user: CurrentUser,
+}
+
+impl Message for ChildrenRequest {
+    type Result = Result<Vec<Child>, ApiError>;
+}
+
+impl Handler<ChildrenRequest> for Database {
+    type Result = Result<Vec<Child>, ApiError>;
+
+    fn handle(&mut self, msg: ChildrenRequest, _: &mut Self::Context) -> Self::Result {
+        use crate::schema::students;
+        require_role(&msg.user, &[Role::Parent])?;
+        let conn = self.conn()?;
+        let children = students::table
+            .inner_join(guardians::table)
+            .filter(guardians::user_id.eq(msg.user.id))
+            .filter(live())
+            .select((students::all_columns, guardians::relationship))
+            .order((students::last_name, students::first_name, students::id))
+            .load::<(Student, Relationship)>(&conn)?;
+        Ok(children
+            .into_iter()
+            .map(|(student, relationship)| Child { student, relationship })
+            .collect())
+    }
+}
+
+pub struct ChildGradesRequest {
+    pub student_id: i32,
+    pub user: CurrentUser,
+}
+
+impl Message for ChildGradesRequest {
+    type Result = Result<Vec<Grade>, ApiError>;
+}
+
+impl Handler<ChildGradesRequest> for Database {
+    type Result = Result<Vec<Grade>, ApiError>;
+
+    fn handle(&mut self, msg: ChildGradesRequest, _: &mut Self::Context) -> Self::Result {
+        use crate::schema::grades::dsl::*;
+        let conn = self.conn()?;
+        require_guardian(&conn, &msg.user, msg.student_id)?;
+        Ok(grades
+            .filter(student_id.eq(msg.student_id))
+            .order((subject_id, date, id))
+            .load::<Grade>(&conn)?)
+    }
+}
+
+pub struct ChildAttendanceRequest {
+    pub student_id: i32,
+    pub range: DateRange,
+    pub user: CurrentUser,
+}
+
+impl Message for ChildAttendanceRequest {
+    type Result = Result<Vec<Attendance>, ApiError>;
+}
+
+impl Handler<ChildAttendanceRequest> for Database {
+    type Result = Result<Vec<Attendance>, ApiError>;
+
+    fn handle(&mut self, msg: ChildAttendanceRequest, _: &mut Self::Context) -> Self::Result {
+        use crate::schema::attendance::dsl::*;
+        let conn = self.conn()?;
+        require_guardian(&conn, &msg.user, msg.student_id)?;
+
+        let mut query = attendance
+            .filter(student_id.eq(msg.student_id))
+            .order((date, lesson_number))
+            .into_boxed();
+        if let Some(from) = msg.range.from {
+            query = query.filter(date.ge(from));
+        }
+        if let Some(to) = msg.range.to {
+            query = query.filter(date.le(to));
+        }
+        Ok(query.load::<Attendance>(&conn)?)
+    }
+}
@@ -0,0 +1,120 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use super::*;
+use super::imports::*;
+
+use diesel::result::{DatabaseErrorKind, Error::DatabaseError};
+
+/// This is the create handler.
+///
+/// Makes the user a guardian of the student with the id from the path.
+/// Only `parent` accounts can be guardians. Admins only.
+pub fn create((request, user, student_id, new_guardian): (HttpRequest<State>, CurrentUser, Path<i32>, Json<CreateRequest>))
+    -> Box<Future<Item = Json<CreateResponse>, Error = actix_web::Error>>
+{
+    debug!("Request to add guardian of student with id of {}: {:?}", student_id.as_ref(), &new_guardian);
+    request.state().db
+        .send(CreateGuardian {
+            student_id: student_id.into_inner(),
+            user,
+            fields: new_guardian.into_inner()
+        })
+        .from_err()
+        .and_then(|res| {
+            res.map(|guardian| {
+                info!("Successfully added guardian");
+                Json(CreateResponse {
+                    message: "Success!".to_string(),
+                    guardian: Some(guardian)
+                })
+            }).map_err(actix_web::Error::from)
+        })
+        .responder()
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct CreateRequest {
+    pub user_id: i32,
+    /// Defaults to `guardian`
+    #[serde(default)]
+    pub relationship: Relationship,
+    pub phone_number: Option<RawPhoneNumber>,
+    pub email: Option<String>
+}
+
+impl Validate for CreateRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut validator = Validator::new();
+        if let Some(Err(message)) = self.phone_number.as_ref().map(RawPhoneNumber::parse) {
+            validator.check("phone_number", false, &message);
+        }
+        if let Some(ref email) = self.email {
+            validator.email("email", email);
+        }
+        validator.finish()
+    }
+}
+
+#[derive(Serialize)]
+pub struct CreateResponse {
+    pub message: String,
+    pub guardian: Option<Guardian>
+}
+
+pub struct CreateGuardian {
+    pub student_id: i32,
+    pub user: CurrentUser,
+    pub fields: CreateRequest,
+}
+
+impl Message for CreateGuardian {
+    type Result = Result<Guardian, ApiError>;
+}
+
+impl Handler<CreateGuardian> for Database {
+    type Result = Result<Guardian, ApiError>;
+
+    fn handle(&mut self, msg: CreateGuardian, _: &mut Self::Context) -> Self::Result {
+        use crate::schema::users;
+        require_role(&msg.user, &[Role::Admin])?;
+        msg.fields.validate()?;
+        let conn = self.conn()?;
+        require_live(&conn, msg.student_id)?;
+
+        let role = users::table
+            .find(msg.fields.user_id)
+            .select(users::role)
+            .first::<Role>(&conn)
+            .optional()?
+            .ok_or_else(|| ApiError::NotFound(format!("User with id of {} not found.", msg.fields.user_id)))?;
+        if role != Role::Parent {
+            return Err(ApiError::BadRequest(format!(
+                "User with id of {} is a `{}`, only parents can be guardians.", msg.fields.user_id, role.as_str()
+            )));
+        }
+
+        let guardian = Guardian {
+            user_id: msg.fields.user_id,
+            student_id: msg.student_id,
+            relationship: msg.fields.relationship,
+            phone_number: match msg.fields.phone_number {
+                Some(ref number) => Some(number.parse().map_err(ApiError::BadRequest)?),
+                None => None,
+            },
+            email: msg.fields.email.map(|email| email.trim().to_string()),
+        };
+        diesel::insert_into(guardians::table)
+            .values(&guardian)
+            .get_result::<Guardian>(&conn)
+            .map_err(|err| match err {
+                DatabaseError(DatabaseErrorKind::UniqueViolation, _) => ApiError::Conflict(format!(
+                    "User with id of {} already is a guardian of student with id of {}.", guardian.user_id, guardian.student_id
+                )),
+                DatabaseError(DatabaseErrorKind::ForeignKeyViolation, _) => ApiError::NotFound(
+                    format!("Student with id of {} not found.", guardian.student_id)
+                ),
+                err => ApiError::from(err)
+            })
+    }
+}
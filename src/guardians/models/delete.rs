@@ -0,0 +1,60 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use super::*;
+use super::imports::*;
+
+/// This is the delete handler
+/// 
+/// Unlinks the guardian from the student, the account stays. Admins only.
+pub fn delete((request, user, ids): (HttpRequest<State>, CurrentUser, Path<(i32, i32)>)) 
+    -> Box<Future<Item = HttpResponse, Error = actix_web::Error>> 
+{
+    let (student_id, user_id) = ids.into_inner();
+    debug!("Request to remove guardian with id of {} of student with id of {}.", user_id, student_id);
+    
+    request.state().db
+        .send(DeleteRequest{student_id, user_id, user})
+        .from_err()
+        .and_then(move |res| {
+            res.map(|_| {
+                info!("Guardian with id of {} of student with id of {} successfully removed.", user_id, student_id);
+                HttpResponse::Ok()
+                    .json(DeleteResponse {
+                        message: format!("Removed guardian with id: {} of student with id: {}.", user_id, student_id)
+                    })
+            }).map_err(actix_web::Error::from)
+        }).responder()
+}
+
+pub struct DeleteRequest {
+    pub student_id: i32,
+    pub user_id: i32,
+    pub user: CurrentUser,
+}
+
+impl Message for DeleteRequest {
+    type Result = Result<usize, ApiError>;
+}
+
+impl Handler<DeleteRequest> for Database {
+    type Result = Result<usize, ApiError>;
+
+    fn handle(&mut self, msg: DeleteRequest, _: &mut Self::Context) -> Self::Result {
+        use crate::schema::guardians::dsl::*;
+        require_role(&msg.user, &[Role::Admin])?;
+        let conn = self.conn()?;
+        require_live(&conn, msg.student_id)?;
+        match diesel::delete(guardians.find((msg.user_id, msg.student_id))).execute(&conn)? {
+            0 => Err(ApiError::NotFound(format!(
+                "User with id of {} isn't a guardian of student with id of {}.", msg.user_id, msg.student_id
+            ))),
+            deleted => Ok(deleted)
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct DeleteResponse {
+    pub message: String,
+}
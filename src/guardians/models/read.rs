@@ -0,0 +1,38 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use super::*;
+use super::imports::*;
+
+/// Lists the guardians of the student with the id from the path.
+/// 
+/// Same access rules as the student.
+pub fn read((request, user, student_id): (HttpRequest<State>, CurrentUser, Path<i32>)) 
+    -> Box<Future<Item = Json<Vec<GuardianContact>>, Error = actix_web::Error>> 
+{
+    debug!("Request to read guardians of student with id of {}.", student_id.as_ref());
+    request.state().db
+        .send(ReadRequest{student_id: student_id.into_inner(), user})
+        .from_err()
+        .and_then(|res| res.map(Json).map_err(actix_web::Error::from))
+        .responder()
+}
+
+pub struct ReadRequest {
+    pub student_id: i32,
+    pub user: CurrentUser,
+}
+
+impl Message for ReadRequest {
+    type Result = Result<Vec<GuardianContact>, ApiError>;
+}
+
+impl Handler<ReadRequest> for Database {
+    type Result = Result<Vec<GuardianContact>, ApiError>;
+
+    fn handle(&mut self, msg: ReadRequest, _: &mut Self::Context) -> Self::Result {
+        let conn = self.conn()?;
+        require_student(&conn, &msg.user, msg.student_id)?;
+        contacts(&conn, msg.student_id)
+    }
+}
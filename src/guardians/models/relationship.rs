@@ -0,0 +1,57 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use std::io::Write;
+
+use diesel::deserialize::{self, FromSql};
+use diesel::pg::Pg;
+use diesel::serialize::{self, IsNull, Output, ToSql};
+use diesel::sql_types::Text;
+
+/// Who the guardian is to the student.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, AsExpression, FromSqlRow)]
+#[serde(rename_all = "lowercase")]
+#[sql_type = "Text"]
+pub enum Relationship {
+    Mother,
+    Father,
+    /// Legal guardian
+    Guardian,
+    Other,
+}
+
+impl Default for Relationship {
+    fn default() -> Self {
+        Relationship::Guardian
+    }
+}
+
+impl Relationship {
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            Relationship::Mother => "mother",
+            Relationship::Father => "father",
+            Relationship::Guardian => "guardian",
+            Relationship::Other => "other",
+        }
+    }
+}
+
+impl ToSql<Text, Pg> for Relationship {
+    fn to_sql<W: Write>(&self, out: &mut Output<W, Pg>) -> serialize::Result {
+        out.write_all(self.as_str().as_bytes())?;
+        Ok(IsNull::No)
+    }
+}
+
+impl FromSql<Text, Pg> for Relationship {
+    fn from_sql(bytes: Option<&[u8]>) -> deserialize::Result<Self> {
+        match not_none!(bytes) {
+            b"mother" => Ok(Relationship::Mother),
+            b"father" => Ok(Relationship::Father),
+            b"guardian" => Ok(Relationship::Guardian),
+            b"other" => Ok(Relationship::Other),
+            _ => Err("Unrecognized guardian relationship".into()),
+        }
+    }
+}
@@ -0,0 +1,118 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use super::*;
+use super::imports::*;
+
+/// This is the update handler
+///
+/// Edits how the user with the id from the path is the student's guardian. Admins only,
+/// 404 if they aren't one.
+pub fn update((request, user, ids, updated_guardian): (HttpRequest<State>, CurrentUser, Path<(i32, i32)>, Json<UpdateRequest>))
+    -> Box<Future<Item = HttpResponse, Error = actix_web::Error>>
+{
+    let (student_id, user_id) = ids.into_inner();
+    debug!("Request to update guardian with id of {} of student with id of {}.", user_id, student_id);
+    request.state().db
+        .send(UpdateGuardian {
+            student_id,
+            user_id,
+            user,
+            fields: updated_guardian.into_inner()
+        })
+        .from_err()
+        .and_then(move |res| {
+            res.map(|guardian| {
+                HttpResponse::Ok().json(UpdateResponse {
+                    message: format!("Updated guardian with id: {} of student with id: {}.", user_id, student_id),
+                    guardian: Some(guardian)
+                })
+            }).map_err(actix_web::Error::from)
+        }).responder()
+}
+
+/// `"phone_number": null` or `"email": null` removes them, leaving them out keeps them as they are.
+#[derive(Serialize, Deserialize)]
+pub struct UpdateRequest {
+    pub relationship: Option<Relationship>,
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    pub phone_number: Option<Option<RawPhoneNumber>>,
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    pub email: Option<Option<String>>
+}
+
+#[derive(AsChangeset)]
+#[table_name="guardians"]
+struct GuardianChanges {
+    relationship: Option<Relationship>,
+    phone_number: Option<Option<PhoneNumber>>,
+    email: Option<Option<String>>
+}
+
+impl UpdateRequest {
+    /// The email without surrounding whitespace and the parsed phone number, once they're valid.
+    fn changes(&self) -> Result<GuardianChanges, ApiError> {
+        Ok(GuardianChanges {
+            relationship: self.relationship,
+            phone_number: match self.phone_number {
+                Some(Some(ref number)) => Some(Some(number.parse().map_err(ApiError::BadRequest)?)),
+                Some(None) => Some(None),
+                None => None,
+            },
+            email: self.email.as_ref().map(|email| email.as_ref().map(|email| email.trim().to_string())),
+        })
+    }
+}
+
+/// Only the fields that are being changed are checked.
+impl Validate for UpdateRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut validator = Validator::new();
+        if let Some(Some(Err(message))) = self.phone_number.as_ref().map(|number| number.as_ref().map(RawPhoneNumber::parse)) {
+            validator.check("phone_number", false, &message);
+        }
+        if let Some(Some(ref email)) = self.email {
+            validator.email("email", email);
+        }
+        validator.finish()
+    }
+}
+
+pub struct UpdateGuardian {
+    pub student_id: i32,
+    pub user_id: i32,
+    pub user: CurrentUser,
+    pub fields: UpdateRequest,
+}
+
+impl Message for UpdateGuardian {
+    type Result = Result<Guardian, ApiError>;
+}
+
+impl Handler<UpdateGuardian> for Database {
+    type Result = Result<Guardian, ApiError>;
+
+    fn handle(&mut self, msg: UpdateGuardian, _: &mut Self::Context) -> Self::Result {
+        require_role(&msg.user, &[Role::Admin])?;
+        msg.fields.validate()?;
+        require_changes(
+            msg.fields.relationship.is_some() || msg.fields.phone_number.is_some() || msg.fields.email.is_some()
+        )?;
+        let changes = msg.fields.changes()?;
+        let conn = self.conn()?;
+        require_live(&conn, msg.student_id)?;
+        diesel::update(guardians::table.find((msg.user_id, msg.student_id)))
+            .set(&changes)
+            .get_result::<Guardian>(&conn)
+            .optional()?
+            .ok_or_else(|| ApiError::NotFound(format!(
+                "User with id of {} isn't a guardian of student with id of {}.", msg.user_id, msg.student_id
+            )))
+    }
+}
+
+#[derive(Serialize)]
+pub struct UpdateResponse {
+    pub message: String,
+    pub guardian: Option<Guardian>
+}
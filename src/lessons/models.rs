@@ -0,0 +1,86 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use crate::schema::lessons;
+use crate::database::Database;
+use crate::error::ApiError;
+use crate::validation::Validator;
+use actix_web::actix::{Message, Handler};
+use chrono::NaiveDate;
+use diesel;
+use diesel::result::{DatabaseErrorKind, Error::DatabaseError};
+
+#[allow(unused_imports)] // Throws errors without this import, but throws warning with it :/
+use diesel::prelude::*;
+
+mod imports;
+
+/// A held lesson in the lesson log, with what was taught and the homework given.
+#[derive(Queryable, Serialize, Deserialize, Debug)]
+pub struct Lesson {
+    pub id: i32,
+    pub class_id: i32,
+    pub subject_id: i32,
+    pub teacher_id: Option<i32>,
+    pub date: NaiveDate,
+    pub lesson_number: i16,
+    pub topic: String,
+    pub homework: Option<String>,
+    pub homework_due: Option<NaiveDate>,
+    /// The lesson in the timetable it was held in, `None` for lessons outside of the plan
+    pub timetable_id: Option<i32>
+}
+
+pub const MAX_TOPIC_LENGTH: usize = 500;
+pub const MAX_HOMEWORK_LENGTH: usize = 2000;
+
+/// Homework needs a due date that isn't before the lesson, and the other way round.
+fn check_homework(
+    validator: &mut Validator,
+    date: NaiveDate,
+    homework: Option<&str>,
+    due: Option<NaiveDate>
+) -> &mut Validator {
+    if let Some(homework) = homework {
+        validator.text("homework", homework, MAX_HOMEWORK_LENGTH);
+    }
+    match (homework, due) {
+        (Some(_), None) => validator.check("homework_due", false, "is required when there's homework"),
+        (None, Some(_)) => validator.check("homework", false, "is required when there's a due date"),
+        (Some(_), Some(due)) => validator.check("homework_due", due >= date, "must not be before the lesson"),
+        (None, None) => validator,
+    }
+}
+
+/// Turns the one lesson per class and slot constraint violation into a 409 Conflict.
+fn slot_taken(err: diesel::result::Error) -> ApiError {
+    match err {
+        DatabaseError(DatabaseErrorKind::UniqueViolation, _) => ApiError::Conflict(
+            "The class already has a lesson logged then.".to_string()
+        ),
+        DatabaseError(DatabaseErrorKind::ForeignKeyViolation, _) => ApiError::BadRequest(
+            "The subject or lesson number doesn't exist.".to_string()
+        ),
+        err => ApiError::from(err)
+    }
+}
+
+/* Create */
+mod create;
+pub use create::*;
+
+/* Read */
+mod read;
+pub use read::*;
+
+/* Update */
+mod update;
+pub use update::*;
+
+/* Delete */
+mod delete;
+pub use delete::*;
+
+/* Homework */
+mod homework;
+pub use homework::*;
@@ -0,0 +1,117 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use super::*;
+use super::imports::*;
+
+use chrono::Local;
+
+/// This is the create handler.
+///
+/// Admins, or teachers assigned to teach the subject in the class.
+pub fn create((request, user, new_lesson): (HttpRequest<State>, CurrentUser, Json<CreateRequest>))
+    -> Box<Future<Item = Json<CreateResponse>, Error = actix_web::Error>>
+{
+    debug!("Request to log lesson: {:?}", &new_lesson);
+    request.state().db
+        .send(CreateLesson {
+            user,
+            fields: new_lesson.into_inner()
+        })
+        .from_err()
+        .and_then(|res| {
+            res.map(|lesson| {
+                info!("Successfully logged lesson");
+                Json(CreateResponse {
+                    message: "Success!".to_string(),
+                    lesson: Some(lesson)
+                })
+            }).map_err(actix_web::Error::from)
+        })
+        .responder()
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct CreateRequest {
+    pub class_id: i32,
+    pub subject_id: i32,
+    /// Today if not set
+    pub date: Option<NaiveDate>,
+    pub lesson_number: i16,
+    pub topic: String,
+    pub homework: Option<String>,
+    pub homework_due: Option<NaiveDate>
+}
+
+impl CreateRequest {
+    fn date(&self) -> NaiveDate {
+        self.date.unwrap_or_else(|| Local::today().naive_local())
+    }
+}
+
+impl Validate for CreateRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut validator = Validator::new();
+        validator.text("topic", &self.topic, MAX_TOPIC_LENGTH);
+        check_homework(&mut validator, self.date(), self.homework.as_ref().map(String::as_str), self.homework_due);
+        validator.finish()
+    }
+}
+
+/// id should be set automatically
+#[derive(Insertable)]
+#[table_name="lessons"]
+struct NewLesson {
+    class_id: i32,
+    subject_id: i32,
+    teacher_id: Option<i32>,
+    date: NaiveDate,
+    lesson_number: i16,
+    topic: String,
+    homework: Option<String>,
+    homework_due: Option<NaiveDate>,
+    timetable_id: Option<i32>
+}
+
+#[derive(Serialize)]
+pub struct CreateResponse {
+    pub message: String,
+    pub lesson: Option<Lesson>
+}
+
+pub struct CreateLesson {
+    pub user: CurrentUser,
+    pub fields: CreateRequest,
+}
+
+impl Message for CreateLesson {
+    type Result = Result<Lesson, ApiError>;
+}
+
+impl Handler<CreateLesson> for Database {
+    type Result = Result<Lesson, ApiError>;
+
+    fn handle(&mut self, msg: CreateLesson, _: &mut Self::Context) -> Self::Result {
+        msg.fields.validate()?;
+        let conn = self.conn()?;
+        require_assignment(&conn, &msg.user, msg.fields.class_id, msg.fields.subject_id)?;
+        let date = msg.fields.date();
+        let timetable_id = planned_lesson(&conn, msg.fields.class_id, date, msg.fields.lesson_number)?;
+        let fields = msg.fields;
+        let new_lesson = NewLesson {
+            class_id: fields.class_id,
+            subject_id: fields.subject_id,
+            teacher_id: Some(msg.user.id),
+            date,
+            lesson_number: fields.lesson_number,
+            topic: fields.topic.trim().to_string(),
+            homework: fields.homework.map(|homework| homework.trim().to_string()),
+            homework_due: fields.homework_due,
+            timetable_id,
+        };
+        diesel::insert_into(lessons::table)
+            .values(&new_lesson)
+            .get_result::<Lesson>(&conn)
+            .map_err(slot_taken)
+    }
+}
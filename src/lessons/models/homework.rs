@@ -0,0 +1,114 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use super::*;
+use super::imports::*;
+
+use chrono::Local;
+use crate::attendance::DateRange;
+use crate::students::live;
+
+/// Homework of the logged in student, or of every child of the logged in parent,
+/// ordered by due date. Only homework due from today on, unless `from` is set.
+pub fn homework((request, user, range): (HttpRequest<State>, CurrentUser, Query<DateRange>))
+    -> Box<Future<Item = Json<Vec<Homework>>, Error = actix_web::Error>>
+{
+    debug!("Request to read homework of user with id of {}.", user.id);
+    request.state().db
+        .send(HomeworkRequest{range: range.into_inner(), user})
+        .from_err()
+        .and_then(|res| res.map(Json).map_err(actix_web::Error::from))
+        .responder()
+}
+
+/// Homework given to a student during a lesson.
+#[derive(Serialize, Debug)]
+pub struct Homework {
+    pub student_id: i32,
+    pub lesson_id: i32,
+    pub subject_id: i32,
+    pub subject: String,
+    pub given_on: NaiveDate,
+    pub due: NaiveDate,
+    pub homework: String
+}
+
+pub struct HomeworkRequest {
+    pub range: DateRange,
+    pub user: CurrentUser,
+}
+
+impl Message for HomeworkRequest {
+    type Result = Result<Vec<Homework>, ApiError>;
+}
+
+impl Handler<HomeworkRequest> for Database {
+    type Result = Result<Vec<Homework>, ApiError>;
+
+    fn handle(&mut self, msg: HomeworkRequest, _: &mut Self::Context) -> Self::Result {
+        use crate::schema::{guardians, students, subjects};
+        require_role(&msg.user, &[Role::Student, Role::Parent])?;
+        let conn = self.conn()?;
+
+        // (student, their class)
+        let children: Vec<(i32, i32)> = match msg.user.role {
+            Role::Parent => students::table
+                .inner_join(guardians::table)
+                .filter(guardians::user_id.eq(msg.user.id))
+                .filter(live())
+                .select((students::id, students::class_id))
+                .load(&conn)?,
+            _ => match msg.user.student_id {
+                Some(student) => students::table
+                    .find(student)
+                    .filter(live())
+                    .select((students::id, students::class_id))
+                    .load(&conn)?,
+                None => Vec::new(),
+            },
+        };
+        let classes: Vec<i32> = children.iter().map(|&(_, class)| class).collect();
+
+        let from = msg.range.from.unwrap_or_else(|| Local::today().naive_local());
+        let mut query = lessons::table
+            .inner_join(subjects::table)
+            .filter(lessons::class_id.eq_any(classes))
+            .filter(lessons::homework_due.ge(from))
+            .select((
+                lessons::id,
+                lessons::class_id,
+                lessons::subject_id,
+                subjects::name,
+                lessons::date,
+                lessons::homework_due,
+                lessons::homework,
+            ))
+            .order((lessons::homework_due, subjects::name, lessons::id))
+            .into_boxed();
+        if let Some(to) = msg.range.to {
+            query = query.filter(lessons::homework_due.le(to));
+        }
+        let given = query.load::<(i32, i32, i32, String, NaiveDate, Option<NaiveDate>, Option<String>)>(&conn)?;
+
+        let mut feed = Vec::new();
+        for (lesson_id, class, subject_id, subject, given_on, due, homework) in given {
+            // both are set together, see the migration
+            let (due, homework) = match (due, homework) {
+                (Some(due), Some(homework)) => (due, homework),
+                _ => continue,
+            };
+            for &(student_id, _) in children.iter().filter(|&&(_, child_class)| child_class == class) {
+                feed.push(Homework {
+                    student_id,
+                    lesson_id,
+                    subject_id,
+                    subject: subject.clone(),
+                    given_on,
+                    due,
+                    homework: homework.clone(),
+                });
+            }
+        }
+        Ok(feed)
+    }
+}
@@ -0,0 +1,67 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use super::*;
+use super::imports::*;
+
+/// The lesson log of the class with the id from the path, e.g. `?subject_id=1&from=2019-05-01`.
+///
+/// Admins, or the teachers of the class, so every teacher who can log its lessons
+/// through a teaching assignment can read them too.
+pub fn read((request, user, class_id, query): (HttpRequest<State>, CurrentUser, Path<i32>, Query<LessonsQuery>))
+    -> Box<Future<Item = Json<Vec<Lesson>>, Error = actix_web::Error>>
+{
+    debug!("Request to read lesson log of class with id of {}: {:?}", class_id.as_ref(), &*query);
+    request.state().db
+        .send(ReadRequest{
+            class_id: class_id.into_inner(),
+            query: query.into_inner(),
+            user
+        })
+        .from_err()
+        .and_then(|res| res.map(Json).map_err(actix_web::Error::from))
+        .responder()
+}
+
+/// Every filter is optional, `from` and `to` are inclusive.
+#[derive(Deserialize, Serialize, Debug)]
+pub struct LessonsQuery {
+    pub subject_id: Option<i32>,
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>
+}
+
+pub struct ReadRequest {
+    pub class_id: i32,
+    pub query: LessonsQuery,
+    pub user: CurrentUser,
+}
+
+impl Message for ReadRequest {
+    type Result = Result<Vec<Lesson>, ApiError>;
+}
+
+impl Handler<ReadRequest> for Database {
+    type Result = Result<Vec<Lesson>, ApiError>;
+
+    fn handle(&mut self, msg: ReadRequest, _: &mut Self::Context) -> Self::Result {
+        use crate::schema::lessons::dsl::*;
+        let conn = self.conn()?;
+        require_taught_class(&conn, &msg.user, msg.class_id)?;
+
+        let mut query = lessons
+            .filter(class_id.eq(msg.class_id))
+            .order((date, lesson_number))
+            .into_boxed();
+        if let Some(subject) = msg.query.subject_id {
+            query = query.filter(subject_id.eq(subject));
+        }
+        if let Some(from) = msg.query.from {
+            query = query.filter(date.ge(from));
+        }
+        if let Some(to) = msg.query.to {
+            query = query.filter(date.le(to));
+        }
+        Ok(query.load::<Lesson>(&conn)?)
+    }
+}
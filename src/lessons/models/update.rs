@@ -0,0 +1,128 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use super::*;
+use super::imports::*;
+
+/// This is the update handler
+///
+/// Admins, or teachers assigned to teach the subject in the class.
+/// 404 if there's no such lesson.
+pub fn update((request, user, id, updated_lesson): (HttpRequest<State>, CurrentUser, Path<i32>, Json<UpdateRequest>))
+    -> Box<Future<Item = HttpResponse, Error = actix_web::Error>>
+{
+    request.state().db
+        .send(UpdateLesson {
+            id: id.clone(),
+            user,
+            fields: updated_lesson.into_inner()
+        })
+        .from_err()
+        .and_then(move |res| {
+            res.map(|lesson| {
+                HttpResponse::Ok().json(UpdateResponse {
+                    message: format!("Updated lesson with id: {:?}.", id),
+                    lesson: Some(lesson)
+                })
+            }).map_err(actix_web::Error::from)
+        }).responder()
+}
+
+/// `"homework": null` together with `"homework_due": null` removes the homework,
+/// leaving them out keeps them as they are.
+#[derive(Serialize, Deserialize, AsChangeset)]
+#[table_name="lessons"]
+pub struct UpdateRequest {
+    pub subject_id: Option<i32>,
+    pub date: Option<NaiveDate>,
+    pub lesson_number: Option<i16>,
+    pub topic: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    pub homework: Option<Option<String>>,
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    pub homework_due: Option<Option<NaiveDate>>
+}
+
+impl UpdateRequest {
+    /// Texts without surrounding whitespace.
+    fn trimmed(self) -> Self {
+        UpdateRequest {
+            topic: self.topic.map(|topic| topic.trim().to_string()),
+            homework: self.homework.map(|homework| homework.map(|homework| homework.trim().to_string())),
+            ..self
+        }
+    }
+}
+
+pub struct UpdateLesson {
+    pub id: i32,
+    pub user: CurrentUser,
+    pub fields: UpdateRequest,
+}
+
+impl Message for UpdateLesson {
+    type Result = Result<Lesson, ApiError>;
+}
+
+impl Handler<UpdateLesson> for Database {
+    type Result = Result<Lesson, ApiError>;
+
+    /// The homework is checked as it will be after the change.
+    /// A new date or lesson number links the lesson to the planned one then.
+    fn handle(&mut self, msg: UpdateLesson, _: &mut Self::Context) -> Self::Result {
+        let lesson_id = msg.id;
+        let fields = msg.fields.trimmed();
+        require_changes(
+            fields.subject_id.is_some() || fields.date.is_some() || fields.lesson_number.is_some()
+                || fields.topic.is_some() || fields.homework.is_some() || fields.homework_due.is_some()
+        )?;
+        let conn = self.conn()?;
+        let current = lessons::table
+            .find(lesson_id)
+            .first::<Lesson>(&conn)
+            .optional()?
+            .ok_or_else(|| ApiError::NotFound(format!("Lesson with id of {} not found.", lesson_id)))?;
+        require_assignment(&conn, &msg.user, current.class_id, current.subject_id)?;
+        if let Some(new_subject) = fields.subject_id {
+            require_assignment(&conn, &msg.user, current.class_id, new_subject)?;
+        }
+
+        let mut validator = Validator::new();
+        if let Some(ref topic) = fields.topic {
+            validator.text("topic", topic, MAX_TOPIC_LENGTH);
+        }
+        let homework = match fields.homework {
+            Some(ref homework) => homework.as_ref(),
+            None => current.homework.as_ref(),
+        };
+        check_homework(
+            &mut validator,
+            fields.date.unwrap_or(current.date),
+            homework.map(String::as_str),
+            fields.homework_due.unwrap_or(current.homework_due)
+        ).finish()?;
+
+        let relinked = if fields.date.is_some() || fields.lesson_number.is_some() {
+            let planned = planned_lesson(
+                &conn,
+                current.class_id,
+                fields.date.unwrap_or(current.date),
+                fields.lesson_number.unwrap_or(current.lesson_number)
+            )?;
+            Some(lessons::timetable_id.eq(planned))
+        } else {
+            None
+        };
+
+        diesel::update(lessons::table.find(lesson_id))
+            .set((&fields, relinked))
+            .get_result::<Lesson>(&conn)
+            .map_err(slot_taken)
+    }
+}
+
+#[derive(Serialize)]
+pub struct UpdateResponse {
+    pub message: String,
+    pub lesson: Option<Lesson>
+}
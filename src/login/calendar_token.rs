@@ -0,0 +1,187 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use actix_web::{
+    AsyncResponder,
+    HttpRequest,
+    HttpResponse,
+    actix::{Message, Handler}
+};
+use chrono::NaiveDateTime;
+use diesel;
+#[allow(unused_imports)] // Throws errors without this import, but throws warning with it :/
+use diesel::prelude::*;
+use futures::future::Future;
+use sha2::{Digest, Sha256};
+
+#[allow(unused_imports)] // it's useful to have these in scope
+use log::{debug, error, info, warn};
+
+use super::{CurrentUser, Role};
+use super::session::generate_token;
+use crate::database::Database;
+use crate::error::ApiError;
+use crate::schema::calendar_tokens;
+use crate::students::live;
+use crate::State;
+
+/// Lets calendar apps, which subscribe by URL and can't send headers, read the `.ics` feeds
+/// as `?token=<token>`. Doesn't expire, but a new one replaces it and it can be revoked.
+///
+/// Only the hash of the token is stored, the token itself is shown once when it's issued.
+#[derive(Queryable, Debug)]
+pub struct CalendarToken {
+    pub token_hash: String,
+    pub user_id: i32,
+    pub created_at: NaiveDateTime,
+}
+
+/// Hex SHA-256 of the token. The tokens are random, so unlike passwords
+/// they don't need a salt or a slow hash.
+fn hash_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
+
+/// Issues a new calendar token for the user, the old one stops working.
+pub struct IssueCalendarToken {
+    pub user_id: i32,
+}
+
+impl Message for IssueCalendarToken {
+    type Result = Result<String, ApiError>;
+}
+
+impl Handler<IssueCalendarToken> for Database {
+    type Result = Result<String, ApiError>;
+
+    /// Returns the token, it can't be read back later.
+    fn handle(&mut self, msg: IssueCalendarToken, _: &mut Self::Context) -> Self::Result {
+        let conn = self.conn()?;
+        let token = generate_token();
+        conn.transaction::<_, ApiError, _>(|| {
+            diesel::delete(calendar_tokens::table.filter(calendar_tokens::user_id.eq(msg.user_id))).execute(&conn)?;
+            diesel::insert_into(calendar_tokens::table)
+                .values((
+                    calendar_tokens::token_hash.eq(hash_token(&token)),
+                    calendar_tokens::user_id.eq(msg.user_id),
+                ))
+                .execute(&conn)?;
+            Ok(())
+        })?;
+        Ok(token)
+    }
+}
+
+/// Deletes the user's calendar token, 404 if they don't have one.
+pub struct RevokeCalendarToken {
+    pub user_id: i32,
+}
+
+impl Message for RevokeCalendarToken {
+    type Result = Result<usize, ApiError>;
+}
+
+impl Handler<RevokeCalendarToken> for Database {
+    type Result = Result<usize, ApiError>;
+
+    fn handle(&mut self, msg: RevokeCalendarToken, _: &mut Self::Context) -> Self::Result {
+        let conn = self.conn()?;
+        match diesel::delete(calendar_tokens::table.filter(calendar_tokens::user_id.eq(msg.user_id))).execute(&conn)? {
+            0 => Err(ApiError::NotFound("You don't have a calendar token.".to_string())),
+            deleted => Ok(deleted)
+        }
+    }
+}
+
+/// Finds the user owning a calendar token, same as `ValidateSession` except for the expiry.
+pub struct ValidateCalendarToken {
+    pub token: String,
+}
+
+impl Message for ValidateCalendarToken {
+    type Result = Result<Option<CurrentUser>, ApiError>;
+}
+
+impl Handler<ValidateCalendarToken> for Database {
+    type Result = Result<Option<CurrentUser>, ApiError>;
+
+    fn handle(&mut self, msg: ValidateCalendarToken, _: &mut Self::Context) -> Self::Result {
+        use crate::schema::{students, users};
+        let conn = self.conn()?;
+        let user = calendar_tokens::table
+            .inner_join(users::table)
+            .filter(calendar_tokens::token_hash.eq(hash_token(&msg.token)))
+            .filter(users::active.eq(true))
+            .filter(users::student_id.is_null().or(users::student_id.eq_any(
+                students::table.filter(live()).select(students::id.nullable())
+            )))
+            .select((users::id, users::login, users::role, users::student_id))
+            .first::<(i32, String, Role, Option<i32>)>(&conn)
+            .optional()?;
+        Ok(user.map(|(id, login, role, student_id)| {
+            CurrentUser { id, login, role, student_id, token: msg.token }
+        }))
+    }
+}
+
+#[derive(Serialize)]
+pub struct CalendarTokenResponse {
+    message: String,
+    /// Append it to the `.ics` URLs as `?token=<token>`
+    token: Option<String>,
+}
+
+/// This is the calendar token handler
+///
+/// Returns a new calendar token, the old one stops working. It's only shown this once.
+pub fn issue_calendar_token((request, user): (HttpRequest<State>, CurrentUser))
+    -> Box<Future<Item = HttpResponse, Error = actix_web::Error>>
+{
+    let user_id = user.id;
+    debug!("Request to issue calendar token for user with id of {}.", user_id);
+    request.state().db
+        .send(IssueCalendarToken{user_id})
+        .from_err()
+        .and_then(move |res| {
+            res.map(|token| {
+                info!("Issued calendar token for user with id of {}.", user_id);
+                HttpResponse::Ok().json(CalendarTokenResponse {
+                    message: "Calendar token issued.".to_string(),
+                    token: Some(token),
+                })
+            }).map_err(actix_web::Error::from)
+        }).responder()
+}
+
+/// This is the calendar token revoking handler
+///
+/// Calendars subscribed with the token stop getting updates.
+pub fn revoke_calendar_token((request, user): (HttpRequest<State>, CurrentUser))
+    -> Box<Future<Item = HttpResponse, Error = actix_web::Error>>
+{
+    let user_id = user.id;
+    debug!("Request to revoke calendar token of user with id of {}.", user_id);
+    request.state().db
+        .send(RevokeCalendarToken{user_id})
+        .from_err()
+        .and_then(move |res| {
+            res.map(|_| {
+                info!("Revoked calendar token of user with id of {}.", user_id);
+                HttpResponse::Ok().json(CalendarTokenResponse {
+                    message: "Calendar token revoked.".to_string(),
+                    token: None,
+                })
+            }).map_err(actix_web::Error::from)
+        }).responder()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hashes_are_hex_sha256() {
+        assert_eq!(hash_token(""), "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855");
+        assert_eq!(hash_token("abc"), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+    }
+}
@@ -0,0 +1,127 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use actix_web::{
+    FromRequest,
+    HttpRequest,
+    ResponseError,
+    http::header,
+    middleware::{Middleware, Started},
+};
+use actix_web::actix::MailboxError;
+use futures::future::Future;
+
+#[allow(unused_imports)] // it's useful to have these in scope
+use log::{debug, error, info, warn};
+
+use super::session::ValidateSession;
+use super::calendar_token::ValidateCalendarToken;
+use super::roles::Role;
+use crate::State;
+use crate::error::ApiError;
+
+/// The user the request's bearer token belongs to.
+///
+/// Put into the request extensions by `RequireSession`,
+/// handlers behind it can take it as an extractor.
+#[derive(Clone, Debug)]
+pub struct CurrentUser {
+    pub id: i32,
+    pub login: String,
+    pub role: Role,
+    /// Set for `student` accounts
+    pub student_id: Option<i32>,
+    /// The session or calendar token the request was made with
+    pub token: String,
+}
+
+impl FromRequest<State> for CurrentUser {
+    type Config = ();
+    type Result = Result<CurrentUser, actix_web::Error>;
+
+    fn from_request(req: &HttpRequest<State>, _: &Self::Config) -> Self::Result {
+        req.extensions()
+            .get::<CurrentUser>()
+            .cloned()
+            .ok_or_else(|| ApiError::Unauthorized("Not logged in.".to_string()).into())
+    }
+}
+
+/// Takes the token out of an `Authorization: Bearer <token>` header.
+fn bearer_token(req: &HttpRequest<State>) -> Option<String> {
+    let value = req.headers().get(header::AUTHORIZATION)?.to_str().ok()?;
+    let mut parts = value.splitn(2, ' ');
+    match (parts.next(), parts.next()) {
+        (Some(scheme), Some(token)) if scheme.eq_ignore_ascii_case("Bearer") => {
+            Some(token.trim().to_string())
+        }
+        _ => None
+    }
+}
+
+/// Rejects requests without a valid session token with 401 Unauthorized.
+///
+/// Register it on every resource that needs a logged in user.
+pub struct RequireSession;
+
+impl Middleware<State> for RequireSession {
+    fn start(&self, req: &HttpRequest<State>) -> actix_web::Result<Started> {
+        match bearer_token(req) {
+            Some(token) => Ok(validate(req, req.state().db.send(ValidateSession{token}))),
+            None => {
+                warn!("Request to {} without a bearer token.", req.path());
+                Ok(Started::Response(
+                    ApiError::Unauthorized("Missing bearer token.".to_string()).error_response()
+                ))
+            }
+        }
+    }
+}
+
+/// Like `RequireSession`, but a calendar token can be sent as `?token=<token>` instead.
+///
+/// Only for the `.ics` feeds, which are subscribed to by URL and can't send headers.
+/// Session tokens aren't accepted in the query, URLs end up in logs and browser histories,
+/// so a leaked calendar token only shows the timetable and can be revoked.
+pub struct RequireSessionOrCalendarToken;
+
+impl Middleware<State> for RequireSessionOrCalendarToken {
+    fn start(&self, req: &HttpRequest<State>) -> actix_web::Result<Started> {
+        if let Some(token) = bearer_token(req) {
+            return Ok(validate(req, req.state().db.send(ValidateSession{token})));
+        }
+        match req.query().get("token").cloned() {
+            Some(token) => Ok(validate(req, req.state().db.send(ValidateCalendarToken{token}))),
+            None => {
+                warn!("Request to {} without a token.", req.path());
+                Ok(Started::Response(
+                    ApiError::Unauthorized("Missing bearer token or calendar `token` parameter.".to_string()).error_response()
+                ))
+            }
+        }
+    }
+}
+
+/// Waits for the token to be looked up, putting its user into the request extensions.
+fn validate<F>(req: &HttpRequest<State>, lookup: F) -> Started
+    where F: Future<Item = Result<Option<CurrentUser>, ApiError>, Error = MailboxError> + 'static
+{
+    let request = req.clone();
+    let validation = lookup
+        .from_err()
+        .and_then(move |user| {
+            match user {
+                Ok(Some(user)) => {
+                    debug!("Request by user with id of {}.", user.id);
+                    request.extensions_mut().insert(user);
+                    Ok(None)
+                }
+                Ok(None) => {
+                    warn!("Request to {} with an invalid or expired token.", request.path());
+                    Ok(Some(ApiError::Unauthorized("Invalid or expired token.".to_string()).error_response()))
+                }
+                Err(e) => Err(actix_web::Error::from(e))
+            }
+        });
+    Started::Future(Box::new(validation))
+}
@@ -19,6 +19,34 @@ use log::{debug, error, info, warn};
 use crate::database::Database;
 //use crate::schema::users;
 use crate::State;
+use crate::error::ApiError;
+use crate::students::live;
+use chrono::NaiveDateTime;
+
+mod password;
+pub use password::{upgrade_plaintext_passwords, hash as hash_password};
+
+mod session;
+pub use session::{logout, refresh, Session};
+
+mod calendar_token;
+pub use calendar_token::{issue_calendar_token, revoke_calendar_token};
+
+mod middleware;
+pub use middleware::{CurrentUser, RequireSession, RequireSessionOrCalendarToken};
+
+mod roles;
+pub use roles::{
+    Role,
+    require_role,
+    require_class,
+    require_taught_class,
+    taught_classes,
+    require_student,
+    require_guardian,
+    require_assignment,
+    require_student_assignment
+};
 
 use actix_web::{
     Json,
@@ -33,24 +61,43 @@ pub struct LoginRequest {
 }
 
 impl Message for LoginRequest {
-    type Result = Result<Vec<User>, diesel::result::Error>;
+    type Result = Result<Option<Session>, ApiError>;
 }
 
 impl Handler<LoginRequest> for Database {
-    type Result = Result<Vec<User>, diesel::result::Error>;
+    type Result = Result<Option<Session>, ApiError>;
 
+    /// Looks the user up by login only, the password is verified here
+    /// against the stored hash instead of in the `WHERE` clause.
+    /// 
+    /// Starts a new session if the password matches. Students who were deleted can't log in.
     fn handle(&mut self, msg: LoginRequest, _: &mut Self::Context) -> Self::Result {
         use crate::schema::users::dsl::*;
-        let conn = self.0.get().unwrap();
+        use crate::schema::students;
+        let conn = self.conn()?;
         let req_login = msg.login.trim().to_owned();
-        let req_password = msg.password.trim().to_owned();
-        users.filter(login.eq(req_login).and(password.eq(req_password))).load(&conn)
+        let req_password = msg.password.trim();
+        let user = users
+            .filter(login.eq(req_login))
+            .filter(active.eq(true))
+            .filter(student_id.is_null().or(student_id.eq_any(
+                students::table.filter(live()).select(students::id.nullable())
+            )))
+            .first::<User>(&conn)
+            .optional()?;
+        match user.filter(|user| password::verify(&user.password_hash, req_password)) {
+            Some(user) => Ok(session::create_session(&conn, user.id).map(Some)?),
+            None => Ok(None)
+        }
     }
 }
 
 #[derive(Serialize, Deserialize)]
 pub struct LoginResponse {
     message: String,
+    /// Send it back as `Authorization: Bearer <token>`
+    token: Option<String>,
+    expires_at: Option<NaiveDateTime>,
 }
 
 #[derive(Queryable)]
@@ -58,31 +105,38 @@ pub struct LoginResponse {
 pub struct User {
     id: i32,
     login: String,
-    password: String,
+    password_hash: String,
+    role: Role,
+    student_id: Option<i32>,
+    active: bool,
+    first_name: Option<String>,
+    last_name: Option<String>,
 }
 
 /// This is the login handler
 /// 
-/// Returns empty response body. If found such user returns Response 200 OK. Else 400.
+/// If found such user returns Response 200 OK with a session token. Else 401.
 pub fn login((request, credentials): (HttpRequest<State>, Json<LoginRequest>)) 
     -> Box<Future<Item = HttpResponse, Error = actix_web::Error>> {
-    debug!(
-        "Request to login with credentials:\nlogin: {}, password: {}.",
-        credentials.login.as_str(), credentials.password.as_str()
-    );
+    debug!("Request to login with login: {}.", credentials.login.as_str());
     request.state().db
         .send(credentials.into_inner())
         .from_err()
-        .and_then(|num_users_found| {
-            let num_users_found = num_users_found
-                .expect("Error finding login and password in database.")
-                .len();
-            if num_users_found == 0 {
-                warn!("Login credentials not found!");
-                Ok(HttpResponse::BadRequest().finish())
-            } else {
-                debug!("User successfully logged in!");
-                Ok(HttpResponse::Ok().finish())
+        .and_then(|session| {
+            match session {
+                Ok(Some(session)) => {
+                    debug!("User successfully logged in!");
+                    Ok(HttpResponse::Ok().json(LoginResponse {
+                        message: "Logged in.".to_string(),
+                        token: Some(session.token),
+                        expires_at: Some(session.expires_at),
+                    }))
+                }
+                Ok(None) => {
+                    warn!("Login credentials not found!");
+                    Err(actix_web::Error::from(ApiError::Unauthorized("Wrong login or password.".to_string())))
+                }
+                Err(e) => Err(actix_web::Error::from(e))
             }
         }).responder()
 }
\ No newline at end of file
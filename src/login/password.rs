@@ -0,0 +1,88 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use argon2::{self, Config, Variant};
+use rand::Rng;
+use diesel;
+use diesel::pg::PgConnection;
+use diesel::r2d2::{ConnectionManager, Pool};
+#[allow(unused_imports)] // Throws errors without this import, but throws warning with it :/
+use diesel::prelude::*;
+
+#[allow(unused_imports)] // it's useful to have these in scope
+use log::{debug, error, info, warn};
+
+/// Every hash starts with this, anything else in the column is plaintext.
+const HASH_PREFIX: &str = "$argon2";
+
+fn config<'a>() -> Config<'a> {
+    Config {
+        variant: Variant::Argon2id,
+        ..Config::default()
+    }
+}
+
+/// Hashes the password with Argon2id and a random 16 byte salt.
+///
+/// The result is a PHC string, so it carries the salt and parameters with it.
+pub fn hash(password: &str) -> String {
+    try_hash(password).expect("Error hashing password")
+}
+
+/// `hash`, but failing instead of panicking.
+fn try_hash(password: &str) -> Result<String, argon2::Error> {
+    let salt: [u8; 16] = rand::thread_rng().gen();
+    argon2::hash_encoded(password.as_bytes(), &salt, &config())
+}
+
+/// Checks the password against a PHC string created by `hash`.
+///
+/// A malformed hash never verifies.
+pub fn verify(hash: &str, password: &str) -> bool {
+    argon2::verify_encoded(hash, password.as_bytes()).unwrap_or(false)
+}
+
+/// Rehashes rows still holding plaintext passwords.
+///
+/// The `hash_user_passwords` migration only knows the seeded admin's password and SQL
+/// can't compute Argon2, so the rest gets upgraded here before the server starts
+/// accepting requests. Trimmed like the password given when logging in.
+///
+/// A row that can't be rehashed is logged and skipped, it keeps failing logins
+/// until it's fixed, but the server still starts.
+pub fn upgrade_plaintext_passwords(pool: &Pool<ConnectionManager<PgConnection>>) {
+    use crate::schema::users::dsl::*;
+    let conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(err) => {
+            error!("Couldn't check for plaintext passwords, no database connection: {}", err);
+            return;
+        }
+    };
+
+    let plaintext = match users
+        .filter(password_hash.not_like(format!("{}%", HASH_PREFIX)))
+        .select((id, password_hash))
+        .load::<(i32, String)>(&conn)
+    {
+        Ok(plaintext) => plaintext,
+        Err(err) => {
+            error!("Couldn't load users with plaintext passwords: {}", err);
+            return;
+        }
+    };
+
+    for (user_id, plain) in plaintext {
+        let hashed = match try_hash(plain.trim()) {
+            Ok(hashed) => hashed,
+            Err(err) => {
+                error!("Couldn't hash the password of user with id of {}, skipping it: {}", user_id, err);
+                continue;
+            }
+        };
+        match diesel::update(users.filter(id.eq(user_id))).set(password_hash.eq(hashed)).execute(&conn) {
+            Ok(_) => info!("Rehashed plaintext password of user with id of {}.", user_id),
+            Err(err) => error!("Couldn't rehash the password of user with id of {}, skipping it: {}", user_id, err),
+        }
+    }
+}
@@ -0,0 +1,209 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use std::io::Write;
+
+use diesel;
+use diesel::deserialize::{self, FromSql};
+use diesel::pg::{Pg, PgConnection};
+#[allow(unused_imports)] // Throws errors without this import, but throws warning with it :/
+use diesel::prelude::*;
+use diesel::serialize::{self, IsNull, Output, ToSql};
+use diesel::sql_types::Text;
+
+#[allow(unused_imports)] // it's useful to have these in scope
+use log::{debug, error, info, warn};
+
+use super::CurrentUser;
+use crate::error::ApiError;
+use crate::students::{live, require_live};
+
+/// Stored in `users.role` as lowercase text.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, AsExpression, FromSqlRow)]
+#[serde(rename_all = "lowercase")]
+#[sql_type = "Text"]
+pub enum Role {
+    Admin,
+    Teacher,
+    Parent,
+    Student,
+}
+
+impl Role {
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            Role::Admin => "admin",
+            Role::Teacher => "teacher",
+            Role::Parent => "parent",
+            Role::Student => "student",
+        }
+    }
+}
+
+impl ToSql<Text, Pg> for Role {
+    fn to_sql<W: Write>(&self, out: &mut Output<W, Pg>) -> serialize::Result {
+        out.write_all(self.as_str().as_bytes())?;
+        Ok(IsNull::No)
+    }
+}
+
+impl FromSql<Text, Pg> for Role {
+    fn from_sql(bytes: Option<&[u8]>) -> deserialize::Result<Self> {
+        match not_none!(bytes) {
+            b"admin" => Ok(Role::Admin),
+            b"teacher" => Ok(Role::Teacher),
+            b"parent" => Ok(Role::Parent),
+            b"student" => Ok(Role::Student),
+            _ => Err("Unrecognized role".into()),
+        }
+    }
+}
+
+/// Fails with `ApiError::Forbidden` unless the user has one of the roles.
+pub fn require_role(user: &CurrentUser, roles: &[Role]) -> Result<(), ApiError> {
+    if roles.contains(&user.role) {
+        Ok(())
+    } else {
+        Err(ApiError::Forbidden(format!(
+            "Role `{}` is not allowed to do this.", user.role.as_str()
+        )))
+    }
+}
+
+/// Admins may edit every class, teachers only the ones they're the homeroom teacher of
+/// or are linked to in `teacher_classes`. Being assigned to teach a subject isn't enough,
+/// see `require_taught_class`.
+pub fn require_class(conn: &PgConnection, user: &CurrentUser, class: i32) -> Result<(), ApiError> {
+    use crate::schema::{classes, teacher_classes};
+    require_role(user, &[Role::Admin, Role::Teacher])?;
+    if user.role == Role::Admin {
+        return Ok(());
+    }
+
+    let linked = diesel::select(diesel::dsl::exists(
+        teacher_classes::table
+            .filter(teacher_classes::user_id.eq(user.id))
+            .filter(teacher_classes::class_id.eq(class))
+    )).get_result::<bool>(conn)?;
+    let homeroom = diesel::select(diesel::dsl::exists(
+        classes::table
+            .filter(classes::id.eq(class))
+            .filter(classes::homeroom_teacher_id.eq(user.id))
+    )).get_result::<bool>(conn)?;
+
+    if linked || homeroom {
+        Ok(())
+    } else {
+        Err(ApiError::Forbidden(format!("You aren't a teacher of class with id of {}.", class)))
+    }
+}
+
+/// Admins may see every class, teachers the ones they teach, see `taught_classes`.
+pub fn require_taught_class(conn: &PgConnection, user: &CurrentUser, class: i32) -> Result<(), ApiError> {
+    require_role(user, &[Role::Admin, Role::Teacher])?;
+    if user.role == Role::Admin || taught_classes(conn, user.id)?.contains(&class) {
+        Ok(())
+    } else {
+        Err(ApiError::Forbidden(format!("You don't teach class with id of {}.", class)))
+    }
+}
+
+/// The classes the teacher is in `teacher_classes` of, is the homeroom teacher of, or is assigned
+/// to teach any subject in during the class's school year. Ordered by id.
+pub fn taught_classes(conn: &PgConnection, teacher: i32) -> Result<Vec<i32>, ApiError> {
+    use crate::schema::{classes, teacher_classes, teaching_assignments};
+    let mut ids = teacher_classes::table
+        .filter(teacher_classes::user_id.eq(teacher))
+        .select(teacher_classes::class_id)
+        .load::<i32>(conn)?;
+    ids.extend(classes::table
+        .filter(classes::homeroom_teacher_id.eq(teacher))
+        .select(classes::id)
+        .load::<i32>(conn)?);
+    ids.extend(teaching_assignments::table
+        .inner_join(classes::table)
+        .filter(teaching_assignments::teacher_id.eq(teacher))
+        .filter(teaching_assignments::school_year.eq(classes::school_year))
+        .select(teaching_assignments::class_id)
+        .load::<i32>(conn)?);
+    ids.sort();
+    ids.dedup();
+    Ok(ids)
+}
+
+/// Admins and teachers see every student, parents only their children
+/// and students only themselves. 404 for deleted students, see `students::live`.
+pub fn require_student(conn: &PgConnection, user: &CurrentUser, student: i32) -> Result<(), ApiError> {
+    require_live(conn, student)?;
+    let allowed = match user.role {
+        Role::Admin | Role::Teacher => true,
+        Role::Parent => is_guardian(conn, user, student)?,
+        Role::Student => user.student_id == Some(student),
+    };
+
+    if allowed {
+        Ok(())
+    } else {
+        Err(ApiError::Forbidden(format!("You can't see student with id of {}.", student)))
+    }
+}
+
+/// Whether the user is one of the student's guardians.
+fn is_guardian(conn: &PgConnection, user: &CurrentUser, student: i32) -> Result<bool, ApiError> {
+    use crate::schema::guardians::dsl::*;
+    Ok(diesel::select(diesel::dsl::exists(
+        guardians.filter(user_id.eq(user.id)).filter(student_id.eq(student))
+    )).get_result::<bool>(conn)?)
+}
+
+/// Parents, and only of their own children that weren't deleted.
+pub fn require_guardian(conn: &PgConnection, user: &CurrentUser, student: i32) -> Result<(), ApiError> {
+    require_role(user, &[Role::Parent])?;
+    require_live(conn, student)?;
+    if is_guardian(conn, user, student)? {
+        Ok(())
+    } else {
+        Err(ApiError::Forbidden(format!("Student with id of {} isn't your child.", student)))
+    }
+}
+
+/// Admins, or teachers assigned to teach the subject in the class
+/// during the class's school year.
+pub fn require_assignment(conn: &PgConnection, user: &CurrentUser, class: i32, subject: i32) -> Result<(), ApiError> {
+    use crate::schema::{classes, teaching_assignments};
+    require_role(user, &[Role::Admin, Role::Teacher])?;
+    if user.role == Role::Admin {
+        return Ok(());
+    }
+
+    let teaches = diesel::select(diesel::dsl::exists(
+        teaching_assignments::table
+            .inner_join(classes::table)
+            .filter(teaching_assignments::teacher_id.eq(user.id))
+            .filter(teaching_assignments::class_id.eq(class))
+            .filter(teaching_assignments::subject_id.eq(subject))
+            .filter(teaching_assignments::school_year.eq(classes::school_year))
+    )).get_result::<bool>(conn)?;
+
+    if teaches {
+        Ok(())
+    } else {
+        Err(ApiError::Forbidden(format!(
+            "You don't teach subject with id of {} in class with id of {}.", subject, class
+        )))
+    }
+}
+
+/// `require_assignment` for the student's class, 404 for deleted students.
+pub fn require_student_assignment(conn: &PgConnection, user: &CurrentUser, student: i32, subject: i32) -> Result<(), ApiError> {
+    use crate::schema::students::dsl::*;
+    require_role(user, &[Role::Admin, Role::Teacher])?;
+    let class = students
+        .find(student)
+        .filter(live())
+        .select(class_id)
+        .first::<i32>(conn)
+        .optional()?
+        .ok_or_else(|| ApiError::NotFound(format!("Student with id of {} not found.", student)))?;
+    require_assignment(conn, user, class, subject)
+}
@@ -0,0 +1,180 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use actix_web::{
+    AsyncResponder,
+    HttpRequest,
+    HttpResponse,
+    actix::{Message, Handler}
+};
+use chrono::{Duration, NaiveDateTime, Utc};
+use diesel;
+use diesel::pg::PgConnection;
+#[allow(unused_imports)] // Throws errors without this import, but throws warning with it :/
+use diesel::prelude::*;
+use futures::future::Future;
+use rand::Rng;
+
+#[allow(unused_imports)] // it's useful to have these in scope
+use log::{debug, error, info, warn};
+
+use super::{CurrentUser, LoginResponse, Role};
+use crate::database::Database;
+use crate::error::ApiError;
+use crate::schema::sessions;
+use crate::students::live;
+use crate::State;
+
+/// How long a token stays valid after login or refresh.
+const SESSION_LIFETIME_HOURS: i64 = 12;
+
+#[derive(Queryable, Insertable, Serialize, Debug)]
+#[table_name="sessions"]
+pub struct Session {
+    pub token: String,
+    pub user_id: i32,
+    pub expires_at: NaiveDateTime,
+    pub created_at: NaiveDateTime,
+}
+
+/// 32 random bytes, hex encoded.
+pub fn generate_token() -> String {
+    let bytes: [u8; 32] = rand::thread_rng().gen();
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Issues a new token for the user.
+///
+/// Also drops the user's expired sessions so the table doesn't grow forever.
+pub fn create_session(conn: &PgConnection, user_id: i32) -> Result<Session, diesel::result::Error> {
+    let now = Utc::now().naive_utc();
+    diesel::delete(
+        sessions::table
+            .filter(sessions::user_id.eq(user_id))
+            .filter(sessions::expires_at.lt(now))
+    ).execute(conn)?;
+
+    let session = Session {
+        token: generate_token(),
+        user_id,
+        expires_at: now + Duration::hours(SESSION_LIFETIME_HOURS),
+        created_at: now,
+    };
+    diesel::insert_into(sessions::table).values(&session).get_result(conn)
+}
+
+/// Finds the user owning a token that hasn't expired yet, unless they're a deleted student.
+pub struct ValidateSession {
+    pub token: String,
+}
+
+impl Message for ValidateSession {
+    type Result = Result<Option<CurrentUser>, ApiError>;
+}
+
+impl Handler<ValidateSession> for Database {
+    type Result = Result<Option<CurrentUser>, ApiError>;
+
+    fn handle(&mut self, msg: ValidateSession, _: &mut Self::Context) -> Self::Result {
+        use crate::schema::{students, users};
+        let conn = self.conn()?;
+        let now = Utc::now().naive_utc();
+        let user = sessions::table
+            .inner_join(users::table)
+            .filter(sessions::token.eq(msg.token.as_str()))
+            .filter(sessions::expires_at.gt(now))
+            .filter(users::active.eq(true))
+            .filter(users::student_id.is_null().or(users::student_id.eq_any(
+                students::table.filter(live()).select(students::id.nullable())
+            )))
+            .select((users::id, users::login, users::role, users::student_id))
+            .first::<(i32, String, Role, Option<i32>)>(&conn)
+            .optional()?;
+        Ok(user.map(|(id, login, role, student_id)| {
+            CurrentUser { id, login, role, student_id, token: msg.token }
+        }))
+    }
+}
+
+pub struct DeleteSession {
+    pub token: String,
+}
+
+impl Message for DeleteSession {
+    type Result = Result<usize, ApiError>;
+}
+
+impl Handler<DeleteSession> for Database {
+    type Result = Result<usize, ApiError>;
+
+    fn handle(&mut self, msg: DeleteSession, _: &mut Self::Context) -> Self::Result {
+        let conn = self.conn()?;
+        Ok(diesel::delete(sessions::table.filter(sessions::token.eq(msg.token))).execute(&conn)?)
+    }
+}
+
+/// Swaps the current token for a new one with a fresh expiry.
+pub struct RefreshSession {
+    pub token: String,
+    pub user_id: i32,
+}
+
+impl Message for RefreshSession {
+    type Result = Result<Session, ApiError>;
+}
+
+impl Handler<RefreshSession> for Database {
+    type Result = Result<Session, ApiError>;
+
+    fn handle(&mut self, msg: RefreshSession, _: &mut Self::Context) -> Self::Result {
+        let conn = self.conn()?;
+        Ok(conn.transaction(|| {
+            diesel::delete(sessions::table.filter(sessions::token.eq(msg.token.as_str()))).execute(&conn)?;
+            create_session(&conn, msg.user_id)
+        })?)
+    }
+}
+
+/// This is the logout handler
+///
+/// Invalidates the token the request was made with.
+pub fn logout((request, user): (HttpRequest<State>, CurrentUser))
+    -> Box<Future<Item = HttpResponse, Error = actix_web::Error>>
+{
+    let user_id = user.id;
+    debug!("Request to logout user with id of {}.", user_id);
+    request.state().db
+        .send(DeleteSession{token: user.token})
+        .from_err()
+        .and_then(move |res| {
+            res.map(|_| {
+                info!("User with id of {} logged out.", user_id);
+                HttpResponse::Ok().json(LoginResponse {
+                    message: "Logged out.".to_string(),
+                    token: None,
+                    expires_at: None,
+                })
+            }).map_err(actix_web::Error::from)
+        }).responder()
+}
+
+/// This is the token refresh handler
+///
+/// Returns a new token, the old one stops working.
+pub fn refresh((request, user): (HttpRequest<State>, CurrentUser))
+    -> Box<Future<Item = HttpResponse, Error = actix_web::Error>>
+{
+    debug!("Request to refresh token of user with id of {}.", user.id);
+    request.state().db
+        .send(RefreshSession{token: user.token, user_id: user.id})
+        .from_err()
+        .and_then(|session| {
+            session.map(|session| {
+                HttpResponse::Ok().json(LoginResponse {
+                    message: "Token refreshed.".to_string(),
+                    token: Some(session.token),
+                    expires_at: Some(session.expires_at),
+                })
+            }).map_err(actix_web::Error::from)
+        }).responder()
+}
@@ -9,9 +9,8 @@ extern crate env_logger;
 extern crate sentry_actix;
 #[macro_use] extern crate diesel;
 
-use sentry::{Hub, Level};
 use std::default::Default;
-use sentry_actix::{SentryMiddleware, ActixWebHubExt};
+use sentry_actix::SentryMiddleware;
 
 #[allow(unused_imports)] // it's useful to have these in scope
 use log::{debug, error, info, warn};
@@ -22,12 +21,10 @@ use actix_web::{
     server, 
     App,
     http::Method,
-    middleware,
-    error,
     HttpRequest,
-    HttpResponse,
     middleware::cors::Cors,
     actix::{
+        Actor,
         SyncArbiter,
         Addr,
         System
@@ -37,45 +34,42 @@ use std::env;
 use dotenv::dotenv;
 
 mod students;
+mod users;
+mod subjects;
+mod grades;
+mod attendance;
+mod classes;
+mod guardians;
+mod assignments;
+mod timetable;
+mod lessons;
+mod messages;
+mod announcements;
+mod report_cards;
+mod school_years;
 mod login;
 mod schema;
 mod database;
+mod validation;
+mod error;
+mod pdf;
+mod export;
+mod access_log;
 
-#[derive(Deserialize, Serialize)]
-struct JsonError {
-    message: String,
-}
-
+use crate::error::ApiError;
 
 /// Handles returning info to client about errors
 /// regarding the json request body.
-fn json_error_handler(err: error::JsonPayloadError, req: &HttpRequest<State>) -> error::Error {
-    error!("Bad json data: {:?}", &err);
-    let message = format!("{}", err);
-    
-    let hub = Hub::from_request(req);
-    hub.capture_message(message.as_str(), Level::Error);
-
-    let description = JsonError{message};
-    error::InternalError::from_response(
-        err, HttpResponse::BadRequest().json(description)
-    ).into()
+fn json_error_handler(err: actix_web::error::JsonPayloadError, _req: &HttpRequest<State>) -> actix_web::Error {
+    warn!("Bad json data: {:?}", &err);
+    ApiError::BadRequest(format!("{}", err)).into()
 }
 
 /// Handles returning info to client about errors
 /// regarding the id supplied in the path.
-fn path_error_handler(err: serde::de::value::Error, req: &HttpRequest<State>) -> error::Error {
-    error!("Bad path id: {:?}", &err);
-    
-    let message = format!("{}", err);
-    
-    let hub = Hub::from_request(req);
-    hub.capture_message(message.as_str(), Level::Error);
-
-    let description = JsonError{message};
-    error::InternalError::from_response(
-        err, HttpResponse::BadRequest().json(description)
-    ).into()
+fn path_error_handler(err: serde::de::value::Error, _req: &HttpRequest<State>) -> actix_web::Error {
+    warn!("Bad path id: {:?}", &err);
+    ApiError::BadRequest(format!("{}", err)).into()
 }
 
 pub struct State {
@@ -116,33 +110,431 @@ fn main() {
     /* Database */
     let sys = System::new("dziennik");
     let pool = database::pool();
+    login::upgrade_plaintext_passwords(&pool);
     let addr = SyncArbiter::start(12, move || database::Database(pool.clone()));
 
+    /* Purge deleted students */
+    let retention_days: i64 = env::var("STUDENT_RETENTION_DAYS")
+        .unwrap_or_else(|_| "30".to_string())
+        .parse()
+        .expect("STUDENT_RETENTION_DAYS must be a number");
+    students::Purger {
+        db: addr.clone(),
+        retention: chrono::Duration::days(retention_days)
+    }.start();
+
     /* Start server */
     let mut server = server::new(move || {
         App::with_state(State {
             db: addr.clone()
         })
             .middleware(SentryMiddleware::new())
-            .middleware(middleware::Logger::default())
+            .middleware(access_log::AccessLog)
             .prefix("/api")
             .configure(|app| {
                 Cors::for_app(app)
                     .allowed_methods(vec!["GET", "POST", "PUT", "DELETE"])
                     .max_age(3600)
                     .resource("/students", |r| {
+                        r.middleware(login::RequireSession);
                         r.method(Method::POST).with_async_config(students::create, |cfg| {
-                            (cfg.0).1.error_handler(&json_error_handler);
+                            (cfg.0).2.error_handler(&json_error_handler);
                         });
-                        r.method(Method::GET).a(students::read);
+                        r.method(Method::GET).with_async(students::read);
+                    })
+                    .resource("/students/import", |r| {
+                        r.middleware(login::RequireSession);
+                        r.method(Method::POST).with_async_config(students::import, |cfg| {
+                            (cfg.0).3.limit(students::MAX_IMPORT_SIZE);
+                        });
+                    })
+                    .resource("/students/batch", |r| {
+                        r.middleware(login::RequireSession);
+                        r.method(Method::POST).with_async_config(students::batch_create, |cfg| {
+                            (cfg.0).3.error_handler(&json_error_handler);
+                        });
+                        r.method(Method::PUT).with_async_config(students::batch_update, |cfg| {
+                            (cfg.0).3.error_handler(&json_error_handler);
+                        });
+                        r.method(Method::DELETE).with_async_config(students::batch_delete, |cfg| {
+                            (cfg.0).3.error_handler(&json_error_handler);
+                        });
+                    })
+                    .resource("/students/export.csv", |r| {
+                        r.middleware(login::RequireSession);
+                        r.method(Method::GET).with_async(students::export_csv);
+                    })
+                    .resource("/students/export.xlsx", |r| {
+                        r.middleware(login::RequireSession);
+                        r.method(Method::GET).with_async(students::export_xlsx);
                     })
                     .resource("/students/{id}", |r| {       // register resource
+                        r.middleware(login::RequireSession);
+                        r.method(Method::GET).with_async_config(students::read_one, |cfg| {
+                            (cfg.0).2.error_handler(&path_error_handler);
+                        });
                         r.method(Method::PUT).with_async_config(students::update, |cfg| {
-                            (cfg.0).1.error_handler(&path_error_handler);
-                            (cfg.0).2.error_handler(&json_error_handler);
+                            (cfg.0).2.error_handler(&path_error_handler);
+                            (cfg.0).3.error_handler(&json_error_handler);
                         });
                         r.method(Method::DELETE).with_async_config(students::delete, |cfg| {
-                            (cfg.0).1.error_handler(&path_error_handler);
+                            (cfg.0).2.error_handler(&path_error_handler);
+                        });
+                    })
+                    .resource("/students/{id}/restore", |r| {
+                        r.middleware(login::RequireSession);
+                        r.method(Method::POST).with_async_config(students::restore, |cfg| {
+                            (cfg.0).2.error_handler(&path_error_handler);
+                        });
+                    })
+                    .resource("/students/{id}/grades", |r| {
+                        r.middleware(login::RequireSession);
+                        r.method(Method::GET).with_async_config(grades::read, |cfg| {
+                            (cfg.0).2.error_handler(&path_error_handler);
+                        });
+                    })
+                    .resource("/students/{id}/averages", |r| {
+                        r.middleware(login::RequireSession);
+                        r.method(Method::GET).with_async_config(grades::averages, |cfg| {
+                            (cfg.0).2.error_handler(&path_error_handler);
+                        });
+                    })
+                    .resource("/students/{id}/attendance", |r| {
+                        r.middleware(login::RequireSession);
+                        r.method(Method::GET).with_async_config(attendance::read, |cfg| {
+                            (cfg.0).2.error_handler(&path_error_handler);
+                        });
+                    })
+                    .resource("/students/{id}/final-grades", |r| {
+                        r.middleware(login::RequireSession);
+                        r.method(Method::PUT).with_async_config(report_cards::set_final_grade, |cfg| {
+                            (cfg.0).2.error_handler(&path_error_handler);
+                            (cfg.0).3.error_handler(&json_error_handler);
+                        });
+                    })
+                    .resource("/students/{id}/behavior", |r| {
+                        r.middleware(login::RequireSession);
+                        r.method(Method::PUT).with_async_config(report_cards::set_behavior, |cfg| {
+                            (cfg.0).2.error_handler(&path_error_handler);
+                            (cfg.0).3.error_handler(&json_error_handler);
+                        });
+                    })
+                    .resource("/students/{id}/report-card", |r| {
+                        r.middleware(login::RequireSession);
+                        r.method(Method::GET).with_async_config(report_cards::report_card, |cfg| {
+                            (cfg.0).2.error_handler(&path_error_handler);
+                        });
+                    })
+                    .resource("/students/{id}/report-card.pdf", |r| {
+                        r.middleware(login::RequireSession);
+                        r.method(Method::GET).with_async_config(report_cards::report_card_pdf, |cfg| {
+                            (cfg.0).2.error_handler(&path_error_handler);
+                        });
+                    })
+                    .resource("/students/{id}/guardians", |r| {
+                        r.middleware(login::RequireSession);
+                        r.method(Method::GET).with_async_config(guardians::read, |cfg| {
+                            (cfg.0).2.error_handler(&path_error_handler);
+                        });
+                        r.method(Method::POST).with_async_config(guardians::create, |cfg| {
+                            (cfg.0).2.error_handler(&path_error_handler);
+                            (cfg.0).3.error_handler(&json_error_handler);
+                        });
+                    })
+                    .resource("/students/{id}/guardians/{user_id}", |r| {
+                        r.middleware(login::RequireSession);
+                        r.method(Method::PUT).with_async_config(guardians::update, |cfg| {
+                            (cfg.0).2.error_handler(&path_error_handler);
+                            (cfg.0).3.error_handler(&json_error_handler);
+                        });
+                        r.method(Method::DELETE).with_async_config(guardians::delete, |cfg| {
+                            (cfg.0).2.error_handler(&path_error_handler);
+                        });
+                    })
+                    .resource("/me/classes", |r| {
+                        r.middleware(login::RequireSession);
+                        r.method(Method::GET).with_async(assignments::my_classes);
+                    })
+                    .resource("/me/announcements", |r| {
+                        r.middleware(login::RequireSession);
+                        r.method(Method::GET).with_async(announcements::feed);
+                    })
+                    .resource("/me/homework", |r| {
+                        r.middleware(login::RequireSession);
+                        r.method(Method::GET).with_async(lessons::homework);
+                    })
+                    .resource("/me/calendar-token", |r| {
+                        r.middleware(login::RequireSession);
+                        r.method(Method::POST).with_async(login::issue_calendar_token);
+                        r.method(Method::DELETE).with_async(login::revoke_calendar_token);
+                    })
+                    .resource("/me/children", |r| {
+                        r.middleware(login::RequireSession);
+                        r.method(Method::GET).with_async(guardians::children);
+                    })
+                    .resource("/me/children/{id}/grades", |r| {
+                        r.middleware(login::RequireSession);
+                        r.method(Method::GET).with_async_config(guardians::child_grades, |cfg| {
+                            (cfg.0).2.error_handler(&path_error_handler);
+                        });
+                    })
+                    .resource("/me/children/{id}/attendance", |r| {
+                        r.middleware(login::RequireSession);
+                        r.method(Method::GET).with_async_config(guardians::child_attendance, |cfg| {
+                            (cfg.0).2.error_handler(&path_error_handler);
+                        });
+                    })
+                    .resource("/subjects", |r| {
+                        r.middleware(login::RequireSession);
+                        r.method(Method::POST).with_async_config(subjects::create, |cfg| {
+                            (cfg.0).2.error_handler(&json_error_handler);
+                        });
+                        r.method(Method::GET).with_async(subjects::read);
+                    })
+                    .resource("/grades", |r| {
+                        r.middleware(login::RequireSession);
+                        r.method(Method::POST).with_async_config(grades::create, |cfg| {
+                            (cfg.0).2.error_handler(&json_error_handler);
+                        });
+                    })
+                    .resource("/grades/{id}", |r| {
+                        r.middleware(login::RequireSession);
+                        r.method(Method::PUT).with_async_config(grades::update, |cfg| {
+                            (cfg.0).2.error_handler(&path_error_handler);
+                            (cfg.0).3.error_handler(&json_error_handler);
+                        });
+                        r.method(Method::DELETE).with_async_config(grades::delete, |cfg| {
+                            (cfg.0).2.error_handler(&path_error_handler);
+                        });
+                    })
+                    .resource("/classes", |r| {
+                        r.middleware(login::RequireSession);
+                        r.method(Method::POST).with_async_config(classes::create, |cfg| {
+                            (cfg.0).2.error_handler(&json_error_handler);
+                        });
+                        r.method(Method::GET).with_async(classes::read);
+                    })
+                    .resource("/classes/{id}", |r| {
+                        r.middleware(login::RequireSession);
+                        r.method(Method::PUT).with_async_config(classes::update, |cfg| {
+                            (cfg.0).2.error_handler(&path_error_handler);
+                            (cfg.0).3.error_handler(&json_error_handler);
+                        });
+                        r.method(Method::DELETE).with_async_config(classes::delete, |cfg| {
+                            (cfg.0).2.error_handler(&path_error_handler);
+                        });
+                    })
+                    .resource("/classes/{id}/students", |r| {
+                        r.middleware(login::RequireSession);
+                        r.method(Method::GET).with_async_config(classes::students, |cfg| {
+                            (cfg.0).2.error_handler(&path_error_handler);
+                        });
+                    })
+                    .resource("/classes/{id}/teachers", |r| {
+                        r.middleware(login::RequireSession);
+                        r.method(Method::GET).with_async_config(classes::teachers, |cfg| {
+                            (cfg.0).2.error_handler(&path_error_handler);
+                        });
+                        r.method(Method::POST).with_async_config(classes::add_teacher, |cfg| {
+                            (cfg.0).2.error_handler(&path_error_handler);
+                            (cfg.0).3.error_handler(&json_error_handler);
+                        });
+                    })
+                    .resource("/classes/{id}/teachers/{user_id}", |r| {
+                        r.middleware(login::RequireSession);
+                        r.method(Method::DELETE).with_async_config(classes::remove_teacher, |cfg| {
+                            (cfg.0).2.error_handler(&path_error_handler);
+                        });
+                    })
+                    .resource("/classes/{id}/report-cards.zip", |r| {
+                        r.middleware(login::RequireSession);
+                        r.method(Method::GET).with_async_config(report_cards::class_report_cards, |cfg| {
+                            (cfg.0).2.error_handler(&path_error_handler);
+                        });
+                    })
+                    .resource("/classes/{id}/grades", |r| {
+                        r.middleware(login::RequireSession);
+                        r.method(Method::GET).with_async_config(grades::class_sheet, |cfg| {
+                            (cfg.0).2.error_handler(&path_error_handler);
+                        });
+                    })
+                    .resource("/classes/{id}/grades.csv", |r| {
+                        r.middleware(login::RequireSession);
+                        r.method(Method::GET).with_async_config(grades::class_sheet_csv, |cfg| {
+                            (cfg.0).2.error_handler(&path_error_handler);
+                        });
+                    })
+                    .resource("/classes/{id}/grades.xlsx", |r| {
+                        r.middleware(login::RequireSession);
+                        r.method(Method::GET).with_async_config(grades::class_sheet_xlsx, |cfg| {
+                            (cfg.0).2.error_handler(&path_error_handler);
+                        });
+                    })
+                    .resource("/classes/{id}/lessons", |r| {
+                        r.middleware(login::RequireSession);
+                        r.method(Method::GET).with_async_config(lessons::read, |cfg| {
+                            (cfg.0).2.error_handler(&path_error_handler);
+                        });
+                    })
+                    .resource("/classes/{id}/timetable", |r| {
+                        r.middleware(login::RequireSession);
+                        r.method(Method::GET).with_async_config(timetable::class_week, |cfg| {
+                            (cfg.0).2.error_handler(&path_error_handler);
+                        });
+                    })
+                    .resource("/classes/{id}/timetable.ics", |r| {
+                        r.middleware(login::RequireSessionOrCalendarToken);
+                        r.method(Method::GET).with_async_config(timetable::class_calendar, |cfg| {
+                            (cfg.0).2.error_handler(&path_error_handler);
+                        });
+                    })
+                    .resource("/teachers/{id}/timetable", |r| {
+                        r.middleware(login::RequireSession);
+                        r.method(Method::GET).with_async_config(timetable::teacher_week, |cfg| {
+                            (cfg.0).2.error_handler(&path_error_handler);
+                        });
+                    })
+                    .resource("/teachers/{id}/timetable.ics", |r| {
+                        r.middleware(login::RequireSessionOrCalendarToken);
+                        r.method(Method::GET).with_async_config(timetable::teacher_calendar, |cfg| {
+                            (cfg.0).2.error_handler(&path_error_handler);
+                        });
+                    })
+                    .resource("/timetable", |r| {
+                        r.middleware(login::RequireSession);
+                        r.method(Method::POST).with_async_config(timetable::create, |cfg| {
+                            (cfg.0).2.error_handler(&json_error_handler);
+                        });
+                    })
+                    .resource("/timetable/{id}", |r| {
+                        r.middleware(login::RequireSession);
+                        r.method(Method::PUT).with_async_config(timetable::update, |cfg| {
+                            (cfg.0).2.error_handler(&path_error_handler);
+                            (cfg.0).3.error_handler(&json_error_handler);
+                        });
+                        r.method(Method::DELETE).with_async_config(timetable::delete, |cfg| {
+                            (cfg.0).2.error_handler(&path_error_handler);
+                        });
+                    })
+                    .resource("/lessons", |r| {
+                        r.middleware(login::RequireSession);
+                        r.method(Method::POST).with_async_config(lessons::create, |cfg| {
+                            (cfg.0).2.error_handler(&json_error_handler);
+                        });
+                    })
+                    .resource("/lessons/{id}", |r| {
+                        r.middleware(login::RequireSession);
+                        r.method(Method::PUT).with_async_config(lessons::update, |cfg| {
+                            (cfg.0).2.error_handler(&path_error_handler);
+                            (cfg.0).3.error_handler(&json_error_handler);
+                        });
+                        r.method(Method::DELETE).with_async_config(lessons::delete, |cfg| {
+                            (cfg.0).2.error_handler(&path_error_handler);
+                        });
+                    })
+                    .resource("/assignments", |r| {
+                        r.middleware(login::RequireSession);
+                        r.method(Method::POST).with_async_config(assignments::create, |cfg| {
+                            (cfg.0).2.error_handler(&json_error_handler);
+                        });
+                        r.method(Method::GET).with_async(assignments::read);
+                    })
+                    .resource("/assignments/{id}", |r| {
+                        r.middleware(login::RequireSession);
+                        r.method(Method::DELETE).with_async_config(assignments::delete, |cfg| {
+                            (cfg.0).2.error_handler(&path_error_handler);
+                        });
+                    })
+                    .resource("/announcements", |r| {
+                        r.middleware(login::RequireSession);
+                        r.method(Method::POST).with_async_config(announcements::create, |cfg| {
+                            (cfg.0).2.error_handler(&json_error_handler);
+                        });
+                        r.method(Method::GET).with_async(announcements::read);
+                    })
+                    .resource("/announcements/{id}", |r| {
+                        r.middleware(login::RequireSession);
+                        r.method(Method::PUT).with_async_config(announcements::update, |cfg| {
+                            (cfg.0).2.error_handler(&path_error_handler);
+                            (cfg.0).3.error_handler(&json_error_handler);
+                        });
+                        r.method(Method::DELETE).with_async_config(announcements::delete, |cfg| {
+                            (cfg.0).2.error_handler(&path_error_handler);
+                        });
+                    })
+                    .resource("/messages", |r| {
+                        r.middleware(login::RequireSession);
+                        r.method(Method::POST).with_async_config(messages::send, |cfg| {
+                            (cfg.0).2.error_handler(&json_error_handler);
+                        });
+                    })
+                    .resource("/messages/inbox", |r| {
+                        r.middleware(login::RequireSession);
+                        r.method(Method::GET).with_async(messages::inbox);
+                    })
+                    .resource("/messages/outbox", |r| {
+                        r.middleware(login::RequireSession);
+                        r.method(Method::GET).with_async(messages::outbox);
+                    })
+                    .resource("/messages/{id}", |r| {
+                        r.middleware(login::RequireSession);
+                        r.method(Method::PUT).with_async_config(messages::update, |cfg| {
+                            (cfg.0).2.error_handler(&path_error_handler);
+                            (cfg.0).3.error_handler(&json_error_handler);
+                        });
+                    })
+                    .resource("/messages/{id}/thread", |r| {
+                        r.middleware(login::RequireSession);
+                        r.method(Method::GET).with_async_config(messages::thread, |cfg| {
+                            (cfg.0).2.error_handler(&path_error_handler);
+                        });
+                    })
+                    .resource("/attendance", |r| {
+                        r.middleware(login::RequireSession);
+                        r.method(Method::POST).with_async_config(attendance::create, |cfg| {
+                            (cfg.0).2.error_handler(&json_error_handler);
+                        });
+                    })
+                    .resource("/attendance/summary", |r| {
+                        r.middleware(login::RequireSession);
+                        r.method(Method::GET).with_async(attendance::summary);
+                    })
+                    .resource("/attendance/summary.csv", |r| {
+                        r.middleware(login::RequireSession);
+                        r.method(Method::GET).with_async(attendance::summary_csv);
+                    })
+                    .resource("/attendance/summary.xlsx", |r| {
+                        r.middleware(login::RequireSession);
+                        r.method(Method::GET).with_async(attendance::summary_xlsx);
+                    })
+                    .resource("/attendance/{id}", |r| {
+                        r.middleware(login::RequireSession);
+                        r.method(Method::PUT).with_async_config(attendance::update, |cfg| {
+                            (cfg.0).2.error_handler(&path_error_handler);
+                            (cfg.0).3.error_handler(&json_error_handler);
+                        });
+                    })
+                    .resource("/school-years/rollover", |r| {
+                        r.middleware(login::RequireSession);
+                        r.method(Method::POST).with_async_config(school_years::rollover, |cfg| {
+                            (cfg.0).3.error_handler(&json_error_handler);
+                        });
+                    })
+                    .resource("/users", |r| {
+                        r.middleware(login::RequireSession);
+                        r.method(Method::POST).with_async_config(users::create, |cfg| {
+                            (cfg.0).2.error_handler(&json_error_handler);
+                        });
+                        r.method(Method::GET).with_async(users::read);
+                    })
+                    .resource("/users/{id}", |r| {
+                        r.middleware(login::RequireSession);
+                        r.method(Method::PUT).with_async_config(users::update, |cfg| {
+                            (cfg.0).2.error_handler(&path_error_handler);
+                            (cfg.0).3.error_handler(&json_error_handler);
+                        });
+                        r.method(Method::DELETE).with_async_config(users::delete, |cfg| {
+                            (cfg.0).2.error_handler(&path_error_handler);
                         });
                     })
                     .resource("/login", |r| {       // register resource
@@ -150,6 +542,14 @@ fn main() {
                             (cfg.0).1.error_handler(&json_error_handler);
                         })
                     })
+                    .resource("/logout", |r| {
+                        r.middleware(login::RequireSession);
+                        r.method(Method::POST).with_async(login::logout);
+                    })
+                    .resource("/refresh", |r| {
+                        r.middleware(login::RequireSession);
+                        r.method(Method::POST).with_async(login::refresh);
+                    })
                     .register()
             })
     });
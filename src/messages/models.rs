@@ -0,0 +1,74 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use crate::schema::{messages, message_recipients};
+use crate::database::Database;
+use crate::error::ApiError;
+use actix_web::actix::{Message, Handler};
+use chrono::NaiveDateTime;
+use diesel;
+use diesel::pg::PgConnection;
+
+#[allow(unused_imports)] // Throws errors without this import, but throws warning with it :/
+use diesel::prelude::*;
+
+mod imports;
+
+/// A sent message, named so it doesn't clash with actix's `Message`.
+///
+/// The sender is `None` once their account is deleted.
+#[derive(Queryable, Serialize, Deserialize, Debug)]
+pub struct Mail {
+    pub id: i32,
+    pub thread_id: Option<i32>,
+    pub sender_id: Option<i32>,
+    pub sender_login: Option<String>,
+    pub subject: String,
+    pub body: String,
+    pub sent_at: NaiveDateTime
+}
+
+pub const MAX_SUBJECT_LENGTH: usize = 200;
+pub const MAX_BODY_LENGTH: usize = 10000;
+
+/// Whether the user sent or got the message, `None` if it doesn't exist.
+fn participates(conn: &PgConnection, user: i32, message: i32) -> Result<Option<bool>, ApiError> {
+    let sender = messages::table
+        .find(message)
+        .select(messages::sender_id)
+        .first::<Option<i32>>(conn)
+        .optional()?;
+    match sender {
+        None => Ok(None),
+        Some(Some(sender)) if sender == user => Ok(Some(true)),
+        Some(_) => Ok(Some(diesel::select(diesel::dsl::exists(
+            message_recipients::table
+                .filter(message_recipients::message_id.eq(message))
+                .filter(message_recipients::user_id.eq(user))
+        )).get_result::<bool>(conn)?)),
+    }
+}
+
+/// 404 unless the user sent or got the message, so others can't tell it exists.
+fn require_participant(conn: &PgConnection, user: i32, message: i32) -> Result<(), ApiError> {
+    match participates(conn, user, message)? {
+        Some(true) => Ok(()),
+        _ => Err(ApiError::NotFound(format!("Message with id of {} not found.", message))),
+    }
+}
+
+/* Send */
+mod send;
+pub use send::*;
+
+/* Inbox and outbox */
+mod read;
+pub use read::*;
+
+/* Thread */
+mod thread;
+pub use thread::*;
+
+/* Read receipts and archive */
+mod update;
+pub use update::*;
@@ -0,0 +1,195 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use super::*;
+use super::imports::*;
+
+use std::collections::BTreeMap;
+
+/// Messages the logged in user got, newest first, e.g. `?unread=true&limit=20`.
+pub fn inbox((request, user, query): (HttpRequest<State>, CurrentUser, Query<MailboxQuery>))
+    -> Box<Future<Item = Json<Vec<InboxEntry>>, Error = actix_web::Error>>
+{
+    debug!("Request to read inbox of user with id of {}: {:?}", user.id, &*query);
+    request.state().db
+        .send(InboxRequest{query: query.into_inner(), user})
+        .from_err()
+        .and_then(|res| res.map(Json).map_err(actix_web::Error::from))
+        .responder()
+}
+
+/// Messages the logged in user sent with who has read them, newest first.
+pub fn outbox((request, user, query): (HttpRequest<State>, CurrentUser, Query<MailboxQuery>))
+    -> Box<Future<Item = Json<Vec<OutboxEntry>>, Error = actix_web::Error>>
+{
+    debug!("Request to read outbox of user with id of {}: {:?}", user.id, &*query);
+    request.state().db
+        .send(OutboxRequest{query: query.into_inner(), user})
+        .from_err()
+        .and_then(|res| res.map(Json).map_err(actix_web::Error::from))
+        .responder()
+}
+
+const DEFAULT_LIMIT: i64 = 50;
+const MAX_LIMIT: i64 = 500;
+
+fn default_limit() -> i64 {
+    DEFAULT_LIMIT
+}
+
+/// Archived messages are only listed with `?archived=true`.
+#[derive(Deserialize, Serialize, Debug)]
+pub struct MailboxQuery {
+    #[serde(default)]
+    pub archived: bool,
+    /// Inbox only
+    #[serde(default)]
+    pub unread: bool,
+    /// At most 500
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+    #[serde(default)]
+    pub offset: i64
+}
+
+impl Validate for MailboxQuery {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut validator = Validator::new();
+        validator.range("limit", self.limit, 1, MAX_LIMIT);
+        validator.check("offset", self.offset >= 0, "must not be negative");
+        validator.finish()
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct InboxEntry {
+    #[serde(flatten)]
+    pub mail: Mail,
+    pub read_at: Option<NaiveDateTime>,
+    pub archived: bool
+}
+
+/// A recipient of a sent message, `read_at` is the read receipt.
+#[derive(Queryable, Serialize, Debug)]
+pub struct Receipt {
+    pub user_id: i32,
+    pub login: String,
+    pub read_at: Option<NaiveDateTime>
+}
+
+#[derive(Serialize, Debug)]
+pub struct OutboxEntry {
+    #[serde(flatten)]
+    pub mail: Mail,
+    pub recipients: Vec<Receipt>
+}
+
+pub struct InboxRequest {
+    pub query: MailboxQuery,
+    pub user: CurrentUser,
+}
+
+impl Message for InboxRequest {
+    type Result = Result<Vec<InboxEntry>, ApiError>;
+}
+
+impl Handler<InboxRequest> for Database {
+    type Result = Result<Vec<InboxEntry>, ApiError>;
+
+    fn handle(&mut self, msg: InboxRequest, _: &mut Self::Context) -> Self::Result {
+        use crate::schema::users;
+        msg.query.validate()?;
+        let conn = self.conn()?;
+
+        let mut query = message_recipients::table
+            .inner_join(messages::table.left_join(users::table))
+            .filter(message_recipients::user_id.eq(msg.user.id))
+            .filter(message_recipients::archived.eq(msg.query.archived))
+            .select((
+                (
+                    messages::id,
+                    messages::thread_id,
+                    messages::sender_id,
+                    users::login.nullable(),
+                    messages::subject,
+                    messages::body,
+                    messages::sent_at,
+                ),
+                message_recipients::read_at,
+                message_recipients::archived,
+            ))
+            .order((messages::sent_at.desc(), messages::id.desc()))
+            .limit(msg.query.limit)
+            .offset(msg.query.offset)
+            .into_boxed();
+        if msg.query.unread {
+            query = query.filter(message_recipients::read_at.is_null());
+        }
+        Ok(query
+            .load::<(Mail, Option<NaiveDateTime>, bool)>(&conn)?
+            .into_iter()
+            .map(|(mail, read_at, archived)| InboxEntry { mail, read_at, archived })
+            .collect())
+    }
+}
+
+pub struct OutboxRequest {
+    pub query: MailboxQuery,
+    pub user: CurrentUser,
+}
+
+impl Message for OutboxRequest {
+    type Result = Result<Vec<OutboxEntry>, ApiError>;
+}
+
+impl Handler<OutboxRequest> for Database {
+    type Result = Result<Vec<OutboxEntry>, ApiError>;
+
+    fn handle(&mut self, msg: OutboxRequest, _: &mut Self::Context) -> Self::Result {
+        use crate::schema::users;
+        msg.query.validate()?;
+        let conn = self.conn()?;
+
+        let sent = messages::table
+            .filter(messages::sender_id.eq(msg.user.id))
+            .filter(messages::sender_archived.eq(msg.query.archived))
+            .select((
+                messages::id,
+                messages::thread_id,
+                messages::subject,
+                messages::body,
+                messages::sent_at,
+            ))
+            .order((messages::sent_at.desc(), messages::id.desc()))
+            .limit(msg.query.limit)
+            .offset(msg.query.offset)
+            .load::<(i32, Option<i32>, String, String, NaiveDateTime)>(&conn)?;
+        let ids: Vec<i32> = sent.iter().map(|&(id, ..)| id).collect();
+        let mut receipts: BTreeMap<i32, Vec<Receipt>> = BTreeMap::new();
+        for (message, receipt) in message_recipients::table
+            .inner_join(users::table)
+            .filter(message_recipients::message_id.eq_any(ids))
+            .select((message_recipients::message_id, (users::id, users::login, message_recipients::read_at)))
+            .order((message_recipients::message_id, users::login))
+            .load::<(i32, Receipt)>(&conn)?
+        {
+            receipts.entry(message).or_insert_with(Vec::new).push(receipt);
+        }
+
+        Ok(sent
+            .into_iter()
+            .map(|(id, thread_id, subject, body, sent_at)| OutboxEntry {
+                mail: Mail {
+                    id,
+                    thread_id,
+                    sender_id: Some(msg.user.id),
+                    sender_login: Some(msg.user.login.clone()),
+                    subject,
+                    body,
+                    sent_at,
+                },
+                recipients: receipts.remove(&id).unwrap_or_default(),
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,209 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use super::*;
+use super::imports::*;
+
+use std::collections::BTreeSet;
+use chrono::Utc;
+use crate::students::live;
+
+/// Sends a message to users and/or the guardians of every student in classes.
+///
+/// Parents and students can only write to teachers and admins. Only admins and the
+/// teachers of a class, including the ones assigned to teach in it, can write to a class.
+pub fn send((request, user, new_message): (HttpRequest<State>, CurrentUser, Json<SendRequest>))
+    -> Box<Future<Item = Json<SendResponse>, Error = actix_web::Error>>
+{
+    debug!("Request to send message by user with id of {}.", user.id);
+    request.state().db
+        .send(SendMessage {
+            user,
+            fields: new_message.into_inner()
+        })
+        .from_err()
+        .and_then(|res| {
+            res.map(|(mail, recipients)| {
+                info!("Successfully sent message to {} recipients", recipients);
+                Json(SendResponse {
+                    message: "Success!".to_string(),
+                    mail: Some(mail),
+                    recipients
+                })
+            }).map_err(actix_web::Error::from)
+        })
+        .responder()
+}
+
+/// Replies go to the sender of `reply_to`, or to its recipients
+/// if it's your own message, unless the recipients are set.
+#[derive(Deserialize, Serialize, Debug)]
+pub struct SendRequest {
+    #[serde(default)]
+    pub user_ids: Vec<i32>,
+    /// The guardians of every student in each class
+    #[serde(default)]
+    pub class_ids: Vec<i32>,
+    /// `Re: ` and the subject of `reply_to` if not set
+    pub subject: Option<String>,
+    pub body: String,
+    pub reply_to: Option<i32>
+}
+
+impl Validate for SendRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut validator = Validator::new();
+        match self.subject {
+            Some(ref subject) => validator.text("subject", subject, MAX_SUBJECT_LENGTH),
+            None => validator.check("subject", self.reply_to.is_some(), "is required unless replying"),
+        };
+        validator.text("body", &self.body, MAX_BODY_LENGTH);
+        validator.check(
+            "user_ids",
+            self.reply_to.is_some() || !self.user_ids.is_empty() || !self.class_ids.is_empty(),
+            "there has to be at least one recipient"
+        );
+        validator.finish()
+    }
+}
+
+#[derive(Insertable)]
+#[table_name="messages"]
+struct NewMessage {
+    sender_id: Option<i32>,
+    thread_id: Option<i32>,
+    subject: String,
+    body: String,
+    sent_at: NaiveDateTime
+}
+
+#[derive(Insertable)]
+#[table_name="message_recipients"]
+struct NewRecipient {
+    message_id: i32,
+    user_id: i32
+}
+
+#[derive(Serialize)]
+pub struct SendResponse {
+    pub message: String,
+    pub mail: Option<Mail>,
+    /// How many users got it
+    pub recipients: usize
+}
+
+pub struct SendMessage {
+    pub user: CurrentUser,
+    pub fields: SendRequest,
+}
+
+impl Message for SendMessage {
+    type Result = Result<(Mail, usize), ApiError>;
+}
+
+impl Handler<SendMessage> for Database {
+    type Result = Result<(Mail, usize), ApiError>;
+
+    fn handle(&mut self, msg: SendMessage, _: &mut Self::Context) -> Self::Result {
+        use crate::schema::{guardians, students, users};
+        msg.fields.validate()?;
+        let conn = self.conn()?;
+        let user = msg.user;
+        let fields = msg.fields;
+
+        let mut recipients: BTreeSet<i32> = fields.user_ids.iter().cloned().collect();
+        let mut thread_id = None;
+        let mut subject = fields.subject.map(|subject| subject.trim().to_string());
+        if let Some(reply_to) = fields.reply_to {
+            require_participant(&conn, user.id, reply_to)?;
+            let (root, original_sender, original_subject) = messages::table
+                .find(reply_to)
+                .select((messages::thread_id, messages::sender_id, messages::subject))
+                .first::<(Option<i32>, Option<i32>, String)>(&conn)?;
+            thread_id = Some(root.unwrap_or(reply_to));
+            if subject.is_none() {
+                subject = Some(if original_subject.starts_with("Re: ") {
+                    original_subject
+                } else {
+                    format!("Re: {}", original_subject)
+                });
+            }
+            if recipients.is_empty() && fields.class_ids.is_empty() {
+                match original_sender {
+                    Some(sender) if sender != user.id => {
+                        recipients.insert(sender);
+                    }
+                    _ => {
+                        recipients.extend(message_recipients::table
+                            .filter(message_recipients::message_id.eq(reply_to))
+                            .select(message_recipients::user_id)
+                            .load::<i32>(&conn)?);
+                    }
+                }
+            }
+        }
+
+        // Only staff can write to anyone, parents and students reply to them
+        let staff = user.role == Role::Admin || user.role == Role::Teacher;
+        if !fields.class_ids.is_empty() {
+            require_role(&user, &[Role::Admin, Role::Teacher])?;
+            for &class in &fields.class_ids {
+                require_class(&conn, &user, class)?;
+            }
+            recipients.extend(guardians::table
+                .inner_join(students::table)
+                .filter(students::class_id.eq_any(&fields.class_ids))
+                .filter(live())
+                .select(guardians::user_id)
+                .load::<i32>(&conn)?);
+        }
+        recipients.remove(&user.id);
+
+        let found = users::table
+            .filter(users::id.eq_any(recipients.iter().cloned().collect::<Vec<_>>()))
+            .filter(users::active.eq(true))
+            .select((users::id, users::role))
+            .load::<(i32, Role)>(&conn)?;
+        if let Some(missing) = recipients.iter().find(|&&id| !found.iter().any(|&(found_id, _)| found_id == id)) {
+            return Err(ApiError::NotFound(format!("User with id of {} not found.", missing)));
+        }
+        if !staff {
+            if let Some(&(id, role)) = found.iter().find(|&&(_, role)| role != Role::Admin && role != Role::Teacher) {
+                return Err(ApiError::Forbidden(format!(
+                    "You can only write to teachers and admins, user with id of {} is a `{}`.", id, role.as_str()
+                )));
+            }
+        }
+        if recipients.is_empty() {
+            return Err(ApiError::BadRequest("There's nobody to send the message to.".to_string()));
+        }
+
+        let new_message = NewMessage {
+            sender_id: Some(user.id),
+            thread_id,
+            subject: subject.unwrap_or_default(),
+            body: fields.body.trim().to_string(),
+            sent_at: Utc::now().naive_utc(),
+        };
+        conn.transaction(|| {
+            let id = diesel::insert_into(messages::table)
+                .values(&new_message)
+                .returning(messages::id)
+                .get_result::<i32>(&conn)?;
+            let rows: Vec<NewRecipient> = recipients
+                .iter()
+                .map(|&user_id| NewRecipient { message_id: id, user_id })
+                .collect();
+            diesel::insert_into(message_recipients::table).values(&rows).execute(&conn)?;
+            Ok((Mail {
+                id,
+                thread_id: new_message.thread_id,
+                sender_id: new_message.sender_id,
+                sender_login: Some(user.login.clone()),
+                subject: new_message.subject.clone(),
+                body: new_message.body.clone(),
+                sent_at: new_message.sent_at,
+            }, rows.len()))
+        })
+    }
+}
@@ -0,0 +1,62 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use super::*;
+use super::imports::*;
+
+/// The whole conversation the message with the id from the path belongs to, oldest first.
+///
+/// Only the messages the logged in user sent or got are included.
+pub fn thread((request, user, id): (HttpRequest<State>, CurrentUser, Path<i32>))
+    -> Box<Future<Item = Json<Vec<Mail>>, Error = actix_web::Error>>
+{
+    debug!("Request to read thread of message with id of {}.", id.as_ref());
+    request.state().db
+        .send(ThreadRequest{id: id.into_inner(), user})
+        .from_err()
+        .and_then(|res| res.map(Json).map_err(actix_web::Error::from))
+        .responder()
+}
+
+pub struct ThreadRequest {
+    pub id: i32,
+    pub user: CurrentUser,
+}
+
+impl Message for ThreadRequest {
+    type Result = Result<Vec<Mail>, ApiError>;
+}
+
+impl Handler<ThreadRequest> for Database {
+    type Result = Result<Vec<Mail>, ApiError>;
+
+    fn handle(&mut self, msg: ThreadRequest, _: &mut Self::Context) -> Self::Result {
+        use crate::schema::users;
+        let conn = self.conn()?;
+        require_participant(&conn, msg.user.id, msg.id)?;
+
+        let root = messages::table
+            .find(msg.id)
+            .select(messages::thread_id)
+            .first::<Option<i32>>(&conn)?
+            .unwrap_or(msg.id);
+        let received = message_recipients::table
+            .filter(message_recipients::user_id.eq(msg.user.id))
+            .select(message_recipients::message_id);
+        Ok(messages::table
+            .left_join(users::table)
+            .filter(messages::id.eq(root).or(messages::thread_id.eq(root)))
+            .filter(messages::sender_id.eq(msg.user.id).or(messages::id.eq_any(received)))
+            .select((
+                messages::id,
+                messages::thread_id,
+                messages::sender_id,
+                users::login.nullable(),
+                messages::subject,
+                messages::body,
+                messages::sent_at,
+            ))
+            .order((messages::sent_at, messages::id))
+            .load::<Mail>(&conn)?)
+    }
+}
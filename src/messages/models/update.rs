@@ -0,0 +1,92 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use super::*;
+use super::imports::*;
+
+use chrono::Utc;
+
+/// Marks the message with the id from the path as read or unread,
+/// and/or moves it to or out of the archive.
+///
+/// Only for the logged in user, 404 if they didn't send or get it.
+pub fn update((request, user, id, changes): (HttpRequest<State>, CurrentUser, Path<i32>, Json<UpdateRequest>))
+    -> Box<Future<Item = HttpResponse, Error = actix_web::Error>>
+{
+    request.state().db
+        .send(UpdateMessage {
+            id: id.clone(),
+            user,
+            fields: changes.into_inner()
+        })
+        .from_err()
+        .and_then(move |res| {
+            res.map(|_| {
+                HttpResponse::Ok().json(UpdateResponse {
+                    message: format!("Updated message with id: {:?}.", id)
+                })
+            }).map_err(actix_web::Error::from)
+        }).responder()
+}
+
+/// `read` only applies to messages you got, `archived` to both sent and got ones.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct UpdateRequest {
+    pub read: Option<bool>,
+    pub archived: Option<bool>
+}
+
+pub struct UpdateMessage {
+    pub id: i32,
+    pub user: CurrentUser,
+    pub fields: UpdateRequest,
+}
+
+impl Message for UpdateMessage {
+    type Result = Result<(), ApiError>;
+}
+
+impl Handler<UpdateMessage> for Database {
+    type Result = Result<(), ApiError>;
+
+    /// Reading a message again keeps the time it was first read.
+    fn handle(&mut self, msg: UpdateMessage, _: &mut Self::Context) -> Self::Result {
+        let conn = self.conn()?;
+        require_participant(&conn, msg.user.id, msg.id)?;
+        let received = || message_recipients::table
+            .filter(message_recipients::message_id.eq(msg.id))
+            .filter(message_recipients::user_id.eq(msg.user.id));
+
+        conn.transaction(|| {
+            match msg.fields.read {
+                Some(true) => {
+                    diesel::update(received().filter(message_recipients::read_at.is_null()))
+                        .set(message_recipients::read_at.eq(Utc::now().naive_utc()))
+                        .execute(&conn)?;
+                }
+                Some(false) => {
+                    diesel::update(received())
+                        .set(message_recipients::read_at.eq(None::<NaiveDateTime>))
+                        .execute(&conn)?;
+                }
+                None => {}
+            }
+            if let Some(archived) = msg.fields.archived {
+                diesel::update(received())
+                    .set(message_recipients::archived.eq(archived))
+                    .execute(&conn)?;
+                diesel::update(messages::table
+                    .filter(messages::id.eq(msg.id))
+                    .filter(messages::sender_id.eq(msg.user.id)))
+                    .set(messages::sender_archived.eq(archived))
+                    .execute(&conn)?;
+            }
+            Ok(())
+        })
+    }
+}
+
+#[derive(Serialize)]
+pub struct UpdateResponse {
+    pub message: String,
+}
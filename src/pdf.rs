@@ -0,0 +1,187 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+//!
+//! A minimal PDF 1.4 writer, just text and lines on A4 pages.
+//!
+//! Only the built-in Helvetica fonts are used, so nothing has to be embedded.
+//! Their encoding is WinAnsi with the Polish letters put in place of
+//! the unused 128-143 codes, other characters outside of it become `?`.
+
+use std::fmt::Write as FmtWrite;
+
+/// A4 in points
+pub const WIDTH: f64 = 595.0;
+pub const HEIGHT: f64 = 842.0;
+
+/// The Polish letters WinAnsi doesn't have, starting at code 128.
+const POLISH: [(char, &str); 16] = [
+    ('Ą', "Aogonek"), ('ą', "aogonek"),
+    ('Ć', "Cacute"), ('ć', "cacute"),
+    ('Ę', "Eogonek"), ('ę', "eogonek"),
+    ('Ł', "Lslash"), ('ł', "lslash"),
+    ('Ń', "Nacute"), ('ń', "nacute"),
+    ('Ś', "Sacute"), ('ś', "sacute"),
+    ('Ź', "Zacute"), ('ź', "zacute"),
+    ('Ż', "Zdotaccent"), ('ż', "zdotaccent"),
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Font {
+    Regular,
+    Bold,
+}
+
+impl Font {
+    fn resource(self) -> &'static str {
+        match self {
+            Font::Regular => "F1",
+            Font::Bold => "F2",
+        }
+    }
+}
+
+/// The drawing operators of one page, (0, 0) is the bottom left corner.
+#[derive(Default)]
+pub struct Page {
+    content: String,
+}
+
+impl Page {
+    pub fn new() -> Self {
+        Page::default()
+    }
+
+    /// Writes a line of text with its baseline starting at (x, y).
+    pub fn text(&mut self, x: f64, y: f64, size: f64, font: Font, text: &str) -> &mut Self {
+        let _ = writeln!(
+            self.content,
+            "BT /{} {:.1} Tf {:.2} {:.2} Td ({}) Tj ET",
+            font.resource(), size, x, y, encode(text)
+        );
+        self
+    }
+
+    /// Strokes a thin line.
+    pub fn line(&mut self, x1: f64, y1: f64, x2: f64, y2: f64) -> &mut Self {
+        let _ = writeln!(self.content, "0.5 w {:.2} {:.2} m {:.2} {:.2} l S", x1, y1, x2, y2);
+        self
+    }
+}
+
+#[derive(Default)]
+pub struct Document {
+    pages: Vec<Page>,
+}
+
+impl Document {
+    pub fn new() -> Self {
+        Document::default()
+    }
+
+    pub fn push(&mut self, page: Page) -> &mut Self {
+        self.pages.push(page);
+        self
+    }
+
+    /// The whole file, with the cross-reference table pointing at every object.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        // 1 catalog, 2 page tree, 3 encoding, 4 and 5 fonts, then a page and its content per page
+        let page_ids: Vec<usize> = (0..self.pages.len()).map(|i| 6 + 2 * i).collect();
+        let mut objects: Vec<String> = Vec::with_capacity(5 + 2 * self.pages.len());
+
+        objects.push("<< /Type /Catalog /Pages 2 0 R >>".to_string());
+        objects.push(format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            page_ids.iter().map(|id| format!("{} 0 R", id)).collect::<Vec<_>>().join(" "),
+            self.pages.len()
+        ));
+        objects.push(format!(
+            "<< /Type /Encoding /BaseEncoding /WinAnsiEncoding /Differences [128 {}] >>",
+            POLISH.iter().map(|&(_, name)| format!("/{}", name)).collect::<Vec<_>>().join(" ")
+        ));
+        objects.push("<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding 3 0 R >>".to_string());
+        objects.push("<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold /Encoding 3 0 R >>".to_string());
+        for (page, &id) in self.pages.iter().zip(&page_ids) {
+            objects.push(format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] \
+                 /Resources << /Font << /F1 4 0 R /F2 5 0 R >> >> /Contents {} 0 R >>",
+                WIDTH, HEIGHT, id + 1
+            ));
+            // The content is plain ASCII, so its length in bytes is its length in chars
+            objects.push(format!(
+                "<< /Length {} >>\nstream\n{}endstream",
+                page.content.len(), page.content
+            ));
+        }
+
+        let mut out = String::from("%PDF-1.4\n");
+        let mut offsets = Vec::with_capacity(objects.len());
+        for (i, object) in objects.iter().enumerate() {
+            offsets.push(out.len());
+            let _ = writeln!(out, "{} 0 obj\n{}\nendobj", i + 1, object);
+        }
+        let xref = out.len();
+        let _ = writeln!(out, "xref\n0 {}\n0000000000 65535 f ", objects.len() + 1);
+        for offset in offsets {
+            let _ = writeln!(out, "{:010} 00000 n ", offset);
+        }
+        let _ = writeln!(
+            out,
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF",
+            objects.len() + 1, xref
+        );
+        out.into_bytes()
+    }
+}
+
+/// The inside of a literal string, anything past ASCII as octal escapes.
+fn encode(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    for c in text.chars() {
+        let code = match POLISH.iter().position(|&(letter, _)| letter == c) {
+            Some(position) => 128 + position as u32,
+            None => match c as u32 {
+                code @ 0x20..=0x7e | code @ 0xa0..=0xff => code,
+                _ => '?' as u32,
+            },
+        };
+        match code {
+            0x28 | 0x29 | 0x5c => {
+                encoded.push('\\');
+                encoded.push(code as u8 as char);
+            }
+            0x20..=0x7e => encoded.push(code as u8 as char),
+            _ => {
+                let _ = write!(encoded, "\\{:03o}", code);
+            }
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ascii_stays_as_is() {
+        assert_eq!(encode("Wychowawca: Jan Kowalski"), "Wychowawca: Jan Kowalski");
+    }
+
+    #[test]
+    fn escapes_string_delimiters() {
+        assert_eq!(encode(r"(a\b)"), r"\(a\\b\)");
+    }
+
+    #[test]
+    fn polish_letters_use_the_free_codes() {
+        assert_eq!(encode("Ą"), "\\200");
+        assert_eq!(encode("żółć"), "\\217\\363\\207\\203");
+    }
+
+    #[test]
+    fn latin1_is_octal_and_the_rest_is_replaced() {
+        assert_eq!(encode("é"), "\\351");
+        assert_eq!(encode("€\n"), "??");
+    }
+}
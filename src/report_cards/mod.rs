@@ -0,0 +1,15 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+extern crate serde_derive;
+extern crate actix_web;
+
+mod models;
+
+pub use models::{
+    set_final_grade,
+    set_behavior,
+    report_card,
+    report_card_pdf,
+    class_report_cards
+};
@@ -0,0 +1,117 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use crate::schema::{behavior_grades, final_grades};
+use crate::database::Database;
+use crate::classes::{Class, school_year_bounds};
+use crate::error::ApiError;
+use crate::students::{Student, live};
+use crate::validation::{Validate, ValidationErrors, Validator};
+use actix_web::actix::{Message, Handler};
+use chrono::{Datelike, NaiveDate};
+use diesel;
+use diesel::pg::PgConnection;
+
+#[allow(unused_imports)] // Throws errors without this import, but throws warning with it :/
+use diesel::prelude::*;
+
+mod imports;
+
+mod behavior;
+pub use behavior::Behavior;
+
+/// The first term ends with January, the second one with the school year.
+pub fn term_bounds(school_year: &str, term: i16) -> Option<(NaiveDate, NaiveDate)> {
+    let (first_day, last_day) = school_year_bounds(school_year)?;
+    match term {
+        1 => Some((first_day, NaiveDate::from_ymd(last_day.year(), 1, 31))),
+        2 => Some((NaiveDate::from_ymd(last_day.year(), 2, 1), last_day)),
+        _ => None,
+    }
+}
+
+/// e.g. `?term=2&school_year=2018/2019`, the school year defaults to the class's.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct TermQuery {
+    pub school_year: Option<String>,
+    pub term: i16
+}
+
+impl Validate for TermQuery {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut validator = Validator::new();
+        validator.range("term", self.term.into(), 1, 2);
+        if let Some(ref year) = self.school_year {
+            validator.school_year("school_year", year);
+        }
+        validator.finish()
+    }
+}
+
+/// The grade a subject ends the term with (ocena klasyfikacyjna).
+#[derive(Queryable, Insertable, Serialize, Deserialize, Debug)]
+#[table_name="final_grades"]
+pub struct FinalGrade {
+    pub student_id: i32,
+    pub subject_id: i32,
+    pub school_year: String,
+    pub term: i16,
+    pub value: i16,
+    pub teacher_id: Option<i32>
+}
+
+#[derive(Queryable, Insertable, Serialize, Deserialize, Debug)]
+#[table_name="behavior_grades"]
+pub struct BehaviorGrade {
+    pub student_id: i32,
+    pub school_year: String,
+    pub term: i16,
+    pub grade: Behavior,
+    pub teacher_id: Option<i32>
+}
+
+/// The student and the class they were in during the school year, by default the one they're in now.
+///
+/// 404 if there's no such student or they weren't in any class that year.
+fn student_and_class(conn: &PgConnection, student: i32, school_year: Option<&str>) -> Result<(Student, Class), ApiError> {
+    use crate::schema::{classes, past_classes, students};
+    let (found, current) = students::table
+        .inner_join(classes::table)
+        .filter(students::id.eq(student))
+        .filter(live())
+        .first::<(Student, Class)>(conn)
+        .optional()?
+        .ok_or_else(|| ApiError::NotFound(format!("Student with id of {} not found.", student)))?;
+    match school_year {
+        Some(year) if year != current.school_year => {
+            let past = past_classes::table
+                .inner_join(classes::table)
+                .filter(past_classes::student_id.eq(student))
+                .filter(classes::school_year.eq(year))
+                .select(classes::all_columns)
+                .first::<Class>(conn)
+                .optional()?
+                .ok_or_else(|| ApiError::NotFound(format!(
+                    "Student with id of {} wasn't in any class in {}.", student, year
+                )))?;
+            Ok((found, past))
+        }
+        _ => Ok((found, current)),
+    }
+}
+
+/* Final grades */
+mod final_grade;
+pub use final_grade::*;
+
+/* Behavior grades */
+mod behavior_grade;
+pub use behavior_grade::*;
+
+/* Report card contents and layout */
+mod card;
+pub use card::*;
+
+/* Downloads */
+mod download;
+pub use download::*;
@@ -0,0 +1,68 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use std::io::Write;
+
+use diesel::deserialize::{self, FromSql};
+use diesel::pg::Pg;
+use diesel::serialize::{self, IsNull, Output, ToSql};
+use diesel::sql_types::Text;
+
+/// The behavior grade (ocena z zachowania), best first.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, AsExpression, FromSqlRow)]
+#[serde(rename_all = "snake_case")]
+#[sql_type = "Text"]
+pub enum Behavior {
+    Exemplary,
+    VeryGood,
+    Good,
+    Acceptable,
+    Unacceptable,
+    Reprehensible,
+}
+
+impl Behavior {
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            Behavior::Exemplary => "exemplary",
+            Behavior::VeryGood => "very_good",
+            Behavior::Good => "good",
+            Behavior::Acceptable => "acceptable",
+            Behavior::Unacceptable => "unacceptable",
+            Behavior::Reprehensible => "reprehensible",
+        }
+    }
+
+    /// As printed on report cards.
+    pub fn label(&self) -> &'static str {
+        match *self {
+            Behavior::Exemplary => "wzorowe",
+            Behavior::VeryGood => "bardzo dobre",
+            Behavior::Good => "dobre",
+            Behavior::Acceptable => "poprawne",
+            Behavior::Unacceptable => "nieodpowiednie",
+            Behavior::Reprehensible => "naganne",
+        }
+    }
+}
+
+impl ToSql<Text, Pg> for Behavior {
+    fn to_sql<W: Write>(&self, out: &mut Output<W, Pg>) -> serialize::Result {
+        out.write_all(self.as_str().as_bytes())?;
+        Ok(IsNull::No)
+    }
+}
+
+impl FromSql<Text, Pg> for Behavior {
+    fn from_sql(bytes: Option<&[u8]>) -> deserialize::Result<Self> {
+        match not_none!(bytes) {
+            b"exemplary" => Ok(Behavior::Exemplary),
+            b"very_good" => Ok(Behavior::VeryGood),
+            b"good" => Ok(Behavior::Good),
+            b"acceptable" => Ok(Behavior::Acceptable),
+            b"unacceptable" => Ok(Behavior::Unacceptable),
+            b"reprehensible" => Ok(Behavior::Reprehensible),
+            _ => Err("Unrecognized behavior grade".into()),
+        }
+    }
+}
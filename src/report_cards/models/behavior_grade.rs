@@ -0,0 +1,88 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use super::*;
+use super::imports::*;
+
+/// Sets the behavior grade of the student with the id from the path, replacing the previous one.
+///
+/// Admins, or the homeroom teacher of the class the student was in that school year.
+pub fn set_behavior((request, user, student_id, grade): (HttpRequest<State>, CurrentUser, Path<i32>, Json<BehaviorRequest>))
+    -> Box<Future<Item = Json<BehaviorGrade>, Error = actix_web::Error>>
+{
+    debug!("Request to set behavior grade of student with id of {}: {:?}", student_id.as_ref(), &grade);
+    request.state().db
+        .send(SetBehavior {
+            student_id: student_id.into_inner(),
+            user,
+            fields: grade.into_inner()
+        })
+        .from_err()
+        .and_then(|res| res.map(Json).map_err(actix_web::Error::from))
+        .responder()
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct BehaviorRequest {
+    /// Defaults to the class's
+    pub school_year: Option<String>,
+    pub term: i16,
+    pub grade: Behavior
+}
+
+impl Validate for BehaviorRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut validator = Validator::new();
+        validator.range("term", self.term.into(), 1, 2);
+        if let Some(ref year) = self.school_year {
+            validator.school_year("school_year", year);
+        }
+        validator.finish()
+    }
+}
+
+pub struct SetBehavior {
+    pub student_id: i32,
+    pub user: CurrentUser,
+    pub fields: BehaviorRequest,
+}
+
+impl Message for SetBehavior {
+    type Result = Result<BehaviorGrade, ApiError>;
+}
+
+impl Handler<SetBehavior> for Database {
+    type Result = Result<BehaviorGrade, ApiError>;
+
+    fn handle(&mut self, msg: SetBehavior, _: &mut Self::Context) -> Self::Result {
+        require_role(&msg.user, &[Role::Admin, Role::Teacher])?;
+        msg.fields.validate()?;
+        let conn = self.conn()?;
+        let (_, class) = student_and_class(&conn, msg.student_id, msg.fields.school_year.as_ref().map(String::as_str))?;
+        if msg.user.role != Role::Admin && class.homeroom_teacher_id != Some(msg.user.id) {
+            return Err(ApiError::Forbidden(format!(
+                "Only the homeroom teacher of class with id of {} grades behavior.", class.id
+            )));
+        }
+
+        let school_year = class.school_year;
+        require_open(&conn, &school_year)?;
+
+        let grade = BehaviorGrade {
+            student_id: msg.student_id,
+            school_year,
+            term: msg.fields.term,
+            grade: msg.fields.grade,
+            teacher_id: Some(msg.user.id),
+        };
+        Ok(diesel::insert_into(behavior_grades::table)
+            .values(&grade)
+            .on_conflict((behavior_grades::student_id, behavior_grades::school_year, behavior_grades::term))
+            .do_update()
+            .set((
+                behavior_grades::grade.eq(grade.grade),
+                behavior_grades::teacher_id.eq(grade.teacher_id),
+            ))
+            .get_result::<BehaviorGrade>(&conn)?)
+    }
+}
@@ -0,0 +1,196 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use super::*;
+use super::imports::*;
+
+use std::collections::BTreeMap;
+use chrono::Local;
+use crate::attendance::{Counts, Status};
+use crate::pdf::{Document, Font, Page, HEIGHT};
+
+/// Everything printed on a report card.
+#[derive(Serialize, Debug)]
+pub struct ReportCard {
+    pub student_id: i32,
+    pub first_name: String,
+    pub last_name: String,
+    pub class_id: i32,
+    pub class_name: String,
+    pub school_year: String,
+    pub term: i16,
+    /// Their name, or their login if it wasn't filled in
+    pub homeroom_teacher: Option<String>,
+    pub subjects: Vec<SubjectGrade>,
+    pub behavior: Option<Behavior>,
+    pub attendance: Counts
+}
+
+/// `value` is `None` until the teacher sets the final grade.
+#[derive(Serialize, Debug)]
+pub struct SubjectGrade {
+    pub subject_id: i32,
+    pub subject: String,
+    pub value: Option<i16>
+}
+
+/// The card of the class's school year. Every subject taught to the class during it
+/// is listed, and any other one the student got a final grade in.
+pub fn load_card(conn: &PgConnection, student: Student, class: &Class, term: &TermQuery) -> Result<ReportCard, ApiError> {
+    use crate::schema::{attendance, subjects, teaching_assignments, users};
+    let school_year = class.school_year.clone();
+    let (from, to) = term_bounds(&school_year, term.term)
+        .ok_or_else(|| ApiError::BadRequest(format!("There's no term {} in {}.", term.term, school_year)))?;
+
+    let homeroom_teacher = match class.homeroom_teacher_id {
+        Some(teacher) => users::table
+            .find(teacher)
+            .select((users::login, users::first_name, users::last_name))
+            .first::<(String, Option<String>, Option<String>)>(conn)
+            .optional()?
+            .map(|(login, first_name, last_name)| display_name(login, first_name, last_name)),
+        None => None,
+    };
+
+    let mut grades: BTreeMap<i32, SubjectGrade> = BTreeMap::new();
+    for (subject_id, subject) in teaching_assignments::table
+        .inner_join(subjects::table)
+        .filter(teaching_assignments::class_id.eq(class.id))
+        .filter(teaching_assignments::school_year.eq(&school_year))
+        .select((subjects::id, subjects::name))
+        .load::<(i32, String)>(conn)?
+    {
+        grades.insert(subject_id, SubjectGrade { subject_id, subject, value: None });
+    }
+    for (subject_id, subject, value) in final_grades::table
+        .inner_join(subjects::table)
+        .filter(final_grades::student_id.eq(student.id))
+        .filter(final_grades::school_year.eq(&school_year))
+        .filter(final_grades::term.eq(term.term))
+        .select((final_grades::subject_id, subjects::name, final_grades::value))
+        .load::<(i32, String, i16)>(conn)?
+    {
+        grades.insert(subject_id, SubjectGrade { subject_id, subject, value: Some(value) });
+    }
+    let mut subjects: Vec<SubjectGrade> = grades.into_iter().map(|(_, grade)| grade).collect();
+    subjects.sort_by(|a, b| a.subject.cmp(&b.subject));
+
+    let behavior = behavior_grades::table
+        .filter(behavior_grades::student_id.eq(student.id))
+        .filter(behavior_grades::school_year.eq(&school_year))
+        .filter(behavior_grades::term.eq(term.term))
+        .select(behavior_grades::grade)
+        .first::<Behavior>(conn)
+        .optional()?;
+
+    let mut counts = Counts::default();
+    for status in attendance::table
+        .filter(attendance::student_id.eq(student.id))
+        .filter(attendance::date.between(from, to))
+        .select(attendance::status)
+        .load::<Status>(conn)?
+    {
+        counts.add(status);
+    }
+    counts.finish();
+
+    Ok(ReportCard {
+        student_id: student.id,
+        first_name: student.first_name,
+        last_name: student.last_name,
+        class_id: class.id,
+        class_name: class.name.clone(),
+        school_year,
+        term: term.term,
+        homeroom_teacher,
+        subjects,
+        behavior,
+        attendance: counts,
+    })
+}
+
+/// The Polish name of the grade, as printed on report cards.
+fn grade_name(value: i16) -> &'static str {
+    match value {
+        6 => "celujący",
+        5 => "bardzo dobry",
+        4 => "dobry",
+        3 => "dostateczny",
+        2 => "dopuszczający",
+        _ => "niedostateczny",
+    }
+}
+
+const LEFT: f64 = 60.0;
+const GRADE_COLUMN: f64 = 360.0;
+const RIGHT: f64 = 535.0;
+const ROW: f64 = 18.0;
+const BOTTOM: f64 = 80.0;
+
+/// Lays the card out on as many A4 pages as it takes, in Polish.
+pub fn render(card: &ReportCard) -> Vec<u8> {
+    let mut document = Document::new();
+    let mut page = Page::new();
+    let mut y = HEIGHT - 80.0;
+
+    let title = if card.term == 2 { "Świadectwo szkolne" } else { "Wyniki klasyfikacji śródrocznej" };
+    page.text(LEFT, y, 20.0, Font::Bold, title);
+    y -= 36.0;
+    page.text(LEFT, y, 12.0, Font::Regular, &format!("Uczeń: {} {}", card.first_name, card.last_name));
+    y -= ROW;
+    page.text(LEFT, y, 12.0, Font::Regular, &format!(
+        "Klasa: {}, rok szkolny {}, semestr {}", card.class_name, card.school_year, card.term
+    ));
+    y -= ROW;
+    page.text(LEFT, y, 12.0, Font::Regular, &format!(
+        "Wychowawca: {}", card.homeroom_teacher.as_ref().map_or("-", String::as_str)
+    ));
+    y -= 2.0 * ROW;
+
+    page.text(LEFT, y, 12.0, Font::Bold, "Przedmiot");
+    page.text(GRADE_COLUMN, y, 12.0, Font::Bold, "Ocena");
+    y -= 6.0;
+    page.line(LEFT, y, RIGHT, y);
+    y -= ROW;
+    for grade in &card.subjects {
+        if y < BOTTOM {
+            document.push(page);
+            page = Page::new();
+            y = HEIGHT - 80.0;
+        }
+        let value = match grade.value {
+            Some(value) => format!("{} ({})", grade_name(value), value),
+            None => "-".to_string(),
+        };
+        page.text(LEFT, y, 11.0, Font::Regular, &grade.subject);
+        page.text(GRADE_COLUMN, y, 11.0, Font::Regular, &value);
+        y -= ROW;
+    }
+
+    // Behavior, attendance and the footer stay together
+    if y - 5.0 * ROW < BOTTOM {
+        document.push(page);
+        page = Page::new();
+        y = HEIGHT - 80.0;
+    }
+    y -= ROW;
+    page.text(LEFT, y, 12.0, Font::Bold, "Zachowanie:");
+    page.text(GRADE_COLUMN, y, 12.0, Font::Regular, card.behavior.map_or("-", |behavior| behavior.label()));
+    y -= 1.5 * ROW;
+    let attendance = &card.attendance;
+    page.text(LEFT, y, 12.0, Font::Bold, "Frekwencja:");
+    page.text(GRADE_COLUMN, y, 12.0, Font::Regular, &match attendance.percentage {
+        Some(percentage) => format!("{:.2}%", percentage),
+        None => "-".to_string(),
+    });
+    y -= ROW;
+    page.text(LEFT, y, 10.0, Font::Regular, &format!(
+        "obecności: {}, spóźnienia: {}, nieobecności: {}, w tym usprawiedliwione: {}",
+        attendance.present, attendance.late, attendance.absent + attendance.excused, attendance.excused
+    ));
+    y -= 2.0 * ROW;
+    page.text(LEFT, y, 9.0, Font::Regular, &format!("Wygenerowano {}", Local::today().format("%d.%m.%Y")));
+
+    document.push(page);
+    document.to_bytes()
+}
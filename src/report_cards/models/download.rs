@@ -0,0 +1,145 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use super::*;
+use super::imports::*;
+
+use std::io::{Cursor, Write};
+use zip::write::{FileOptions, ZipWriter};
+
+/// The report card of the student with the id from the path, e.g. `?term=1`.
+///
+/// Same access as the student.
+pub fn report_card((request, user, student_id, term): (HttpRequest<State>, CurrentUser, Path<i32>, Query<TermQuery>))
+    -> Box<Future<Item = Json<ReportCard>, Error = actix_web::Error>>
+{
+    debug!("Request to read report card of student with id of {}: {:?}", student_id.as_ref(), &*term);
+    request.state().db
+        .send(ReportCardRequest{student_id: student_id.into_inner(), term: term.into_inner(), user})
+        .from_err()
+        .and_then(|res| res.map(Json).map_err(actix_web::Error::from))
+        .responder()
+}
+
+/// The report card as a PDF, see `report_card`.
+pub fn report_card_pdf((request, user, student_id, term): (HttpRequest<State>, CurrentUser, Path<i32>, Query<TermQuery>))
+    -> Box<Future<Item = HttpResponse, Error = actix_web::Error>>
+{
+    debug!("Request to download report card of student with id of {}: {:?}", student_id.as_ref(), &*term);
+    request.state().db
+        .send(ReportCardRequest{student_id: student_id.into_inner(), term: term.into_inner(), user})
+        .from_err()
+        .and_then(|res| {
+            res.map(|card| {
+                HttpResponse::Ok()
+                    .content_type("application/pdf")
+                    .header("Content-Disposition", format!("attachment; filename=\"{}\"", file_name(&card)))
+                    .body(render(&card))
+            }).map_err(actix_web::Error::from)
+        })
+        .responder()
+}
+
+/// The report cards of every student in the class with the id from the path, one PDF each in a zip.
+///
+/// Admins, or teachers and the homeroom teacher of the class.
+pub fn class_report_cards((request, user, class_id, term): (HttpRequest<State>, CurrentUser, Path<i32>, Query<TermQuery>))
+    -> Box<Future<Item = HttpResponse, Error = actix_web::Error>>
+{
+    debug!("Request to download report cards of class with id of {}: {:?}", class_id.as_ref(), &*term);
+    let class_id = class_id.into_inner();
+    request.state().db
+        .send(ClassReportCardsRequest{class_id, term: term.into_inner(), user})
+        .from_err()
+        .and_then(move |res| {
+            res.map(|zip| {
+                HttpResponse::Ok()
+                    .content_type("application/zip")
+                    .header("Content-Disposition", format!("attachment; filename=\"report-cards-{}.zip\"", class_id))
+                    .body(zip)
+            }).map_err(actix_web::Error::from)
+        })
+        .responder()
+}
+
+/// e.g. `3d_Kowalski_Jan_12.pdf`, without characters that aren't allowed in file names.
+fn file_name(card: &ReportCard) -> String {
+    format!("{}_{}_{}_{}.pdf", card.class_name, card.last_name, card.first_name, card.student_id)
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '.' || c == '-' { c } else { '_' })
+        .collect()
+}
+
+pub struct ReportCardRequest {
+    pub student_id: i32,
+    pub term: TermQuery,
+    pub user: CurrentUser,
+}
+
+impl Message for ReportCardRequest {
+    type Result = Result<ReportCard, ApiError>;
+}
+
+impl Handler<ReportCardRequest> for Database {
+    type Result = Result<ReportCard, ApiError>;
+
+    fn handle(&mut self, msg: ReportCardRequest, _: &mut Self::Context) -> Self::Result {
+        msg.term.validate()?;
+        let conn = self.conn()?;
+        require_student(&conn, &msg.user, msg.student_id)?;
+        let (student, class) = student_and_class(&conn, msg.student_id, msg.term.school_year.as_ref().map(String::as_str))?;
+        load_card(&conn, student, &class, &msg.term)
+    }
+}
+
+pub struct ClassReportCardsRequest {
+    pub class_id: i32,
+    pub term: TermQuery,
+    pub user: CurrentUser,
+}
+
+impl Message for ClassReportCardsRequest {
+    type Result = Result<Vec<u8>, ApiError>;
+}
+
+impl Handler<ClassReportCardsRequest> for Database {
+    type Result = Result<Vec<u8>, ApiError>;
+
+    /// Students ordered like in the class register, including the ones who have moved on
+    /// to another class since.
+    fn handle(&mut self, msg: ClassReportCardsRequest, _: &mut Self::Context) -> Self::Result {
+        use crate::schema::{classes, past_classes, students};
+        msg.term.validate()?;
+        let conn = self.conn()?;
+        require_taught_class(&conn, &msg.user, msg.class_id)?;
+        let class = classes::table
+            .find(msg.class_id)
+            .first::<Class>(&conn)
+            .optional()?
+            .ok_or_else(|| ApiError::NotFound(format!("Class with id of {} not found.", msg.class_id)))?;
+        if let Some(ref year) = msg.term.school_year {
+            if *year != class.school_year {
+                return Err(ApiError::BadRequest(format!(
+                    "Class with id of {} is of school year {}, not {}.", class.id, class.school_year, year
+                )));
+            }
+        }
+        let former = past_classes::table
+            .filter(past_classes::class_id.eq(msg.class_id))
+            .select(past_classes::student_id);
+        let roster = students::table
+            .filter(students::class_id.eq(msg.class_id).or(students::id.eq_any(former)))
+            .filter(live())
+            .order((students::last_name, students::first_name, students::id))
+            .load::<Student>(&conn)?;
+
+        let zip_error = |err| ApiError::Internal(format!("Couldn't zip report cards: {}", err));
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        for student in roster {
+            let card = load_card(&conn, student, &class, &msg.term)?;
+            zip.start_file(file_name(&card), FileOptions::default()).map_err(zip_error)?;
+            zip.write_all(&render(&card)).map_err(|err| zip_error(err.into()))?;
+        }
+        Ok(zip.finish().map_err(zip_error)?.into_inner())
+    }
+}
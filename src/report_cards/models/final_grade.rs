@@ -0,0 +1,91 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use super::*;
+use super::imports::*;
+
+/// Sets the final grade of the student with the id from the path, replacing the previous one.
+///
+/// Admins, or teachers assigned to teach the subject in the class the student was in that school year.
+pub fn set_final_grade((request, user, student_id, grade): (HttpRequest<State>, CurrentUser, Path<i32>, Json<FinalGradeRequest>))
+    -> Box<Future<Item = Json<FinalGrade>, Error = actix_web::Error>>
+{
+    debug!("Request to set final grade of student with id of {}: {:?}", student_id.as_ref(), &grade);
+    request.state().db
+        .send(SetFinalGrade {
+            student_id: student_id.into_inner(),
+            user,
+            fields: grade.into_inner()
+        })
+        .from_err()
+        .and_then(|res| res.map(Json).map_err(actix_web::Error::from))
+        .responder()
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct FinalGradeRequest {
+    pub subject_id: i32,
+    /// Defaults to the class's
+    pub school_year: Option<String>,
+    pub term: i16,
+    pub value: i16
+}
+
+impl Validate for FinalGradeRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut validator = Validator::new();
+        validator.range("term", self.term.into(), 1, 2);
+        validator.range("value", self.value.into(), 1, 6);
+        if let Some(ref year) = self.school_year {
+            validator.school_year("school_year", year);
+        }
+        validator.finish()
+    }
+}
+
+pub struct SetFinalGrade {
+    pub student_id: i32,
+    pub user: CurrentUser,
+    pub fields: FinalGradeRequest,
+}
+
+impl Message for SetFinalGrade {
+    type Result = Result<FinalGrade, ApiError>;
+}
+
+impl Handler<SetFinalGrade> for Database {
+    type Result = Result<FinalGrade, ApiError>;
+
+    fn handle(&mut self, msg: SetFinalGrade, _: &mut Self::Context) -> Self::Result {
+        msg.fields.validate()?;
+        let conn = self.conn()?;
+        let (_, class) = student_and_class(&conn, msg.student_id, msg.fields.school_year.as_ref().map(String::as_str))?;
+        require_assignment(&conn, &msg.user, class.id, msg.fields.subject_id)?;
+
+        let school_year = class.school_year;
+        require_open(&conn, &school_year)?;
+
+        let grade = FinalGrade {
+            student_id: msg.student_id,
+            subject_id: msg.fields.subject_id,
+            school_year,
+            term: msg.fields.term,
+            value: msg.fields.value,
+            teacher_id: Some(msg.user.id),
+        };
+        Ok(diesel::insert_into(final_grades::table)
+            .values(&grade)
+            .on_conflict((
+                final_grades::student_id,
+                final_grades::subject_id,
+                final_grades::school_year,
+                final_grades::term,
+            ))
+            .do_update()
+            .set((
+                final_grades::value.eq(grade.value),
+                final_grades::teacher_id.eq(grade.teacher_id),
+            ))
+            .get_result::<FinalGrade>(&conn)?)
+    }
+}
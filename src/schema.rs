@@ -1,10 +1,208 @@
+table! {
+    announcements (id) {
+        id -> Int4,
+        author_id -> Nullable<Int4>,
+        audience -> Text,
+        class_id -> Nullable<Int4>,
+        role -> Nullable<Text>,
+        title -> Text,
+        body -> Text,
+        pinned -> Bool,
+        publish_at -> Timestamp,
+        expires_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+    }
+}
+
+table! {
+    attendance (id) {
+        id -> Int4,
+        student_id -> Int4,
+        subject_id -> Int4,
+        date -> Date,
+        lesson_number -> Int2,
+        status -> Text,
+        teacher_id -> Nullable<Int4>,
+        timetable_id -> Nullable<Int4>,
+    }
+}
+
+table! {
+    behavior_grades (student_id, school_year, term) {
+        student_id -> Int4,
+        school_year -> Text,
+        term -> Int2,
+        grade -> Text,
+        teacher_id -> Nullable<Int4>,
+    }
+}
+
+table! {
+    calendar_tokens (token_hash) {
+        token_hash -> Text,
+        user_id -> Int4,
+        created_at -> Timestamp,
+    }
+}
+
+table! {
+    classes (id) {
+        id -> Int4,
+        name -> Text,
+        school_year -> Text,
+        homeroom_teacher_id -> Nullable<Int4>,
+    }
+}
+
+table! {
+    closed_school_years (school_year) {
+        school_year -> Text,
+        closed_at -> Timestamp,
+        closed_by -> Nullable<Int4>,
+    }
+}
+
+table! {
+    final_grades (student_id, subject_id, school_year, term) {
+        student_id -> Int4,
+        subject_id -> Int4,
+        school_year -> Text,
+        term -> Int2,
+        value -> Int2,
+        teacher_id -> Nullable<Int4>,
+    }
+}
+
+table! {
+    grades (id) {
+        id -> Int4,
+        student_id -> Int4,
+        subject_id -> Int4,
+        value -> Int2,
+        modifier -> Nullable<Text>,
+        weight -> Int2,
+        category -> Text,
+        date -> Date,
+        teacher_id -> Nullable<Int4>,
+    }
+}
+
+table! {
+    guardians (user_id, student_id) {
+        user_id -> Int4,
+        student_id -> Int4,
+        relationship -> Text,
+        phone_number -> Nullable<Text>,
+        email -> Nullable<Text>,
+    }
+}
+
+table! {
+    lesson_times (lesson_number) {
+        lesson_number -> Int2,
+        starts_at -> Time,
+        ends_at -> Time,
+    }
+}
+
+table! {
+    lessons (id) {
+        id -> Int4,
+        class_id -> Int4,
+        subject_id -> Int4,
+        teacher_id -> Nullable<Int4>,
+        date -> Date,
+        lesson_number -> Int2,
+        topic -> Text,
+        homework -> Nullable<Text>,
+        homework_due -> Nullable<Date>,
+        timetable_id -> Nullable<Int4>,
+    }
+}
+
+table! {
+    message_recipients (message_id, user_id) {
+        message_id -> Int4,
+        user_id -> Int4,
+        read_at -> Nullable<Timestamp>,
+        archived -> Bool,
+    }
+}
+
+table! {
+    messages (id) {
+        id -> Int4,
+        sender_id -> Nullable<Int4>,
+        thread_id -> Nullable<Int4>,
+        subject -> Text,
+        body -> Text,
+        sent_at -> Timestamp,
+        sender_archived -> Bool,
+    }
+}
+
+table! {
+    past_classes (student_id, class_id) {
+        student_id -> Int4,
+        class_id -> Int4,
+    }
+}
+
+table! {
+    sessions (token) {
+        token -> Text,
+        user_id -> Int4,
+        expires_at -> Timestamp,
+        created_at -> Timestamp,
+    }
+}
+
 table! {
     students (id) {
         id -> Int4,
         first_name -> Text,
         last_name -> Text,
-        class -> Text,
-        phone_number -> Int4,
+        phone_number -> Text,
+        class_id -> Int4,
+        graduated_in -> Nullable<Text>,
+        deleted_at -> Nullable<Timestamp>,
+    }
+}
+
+table! {
+    subjects (id) {
+        id -> Int4,
+        name -> Text,
+    }
+}
+
+table! {
+    teacher_classes (user_id, class_id) {
+        user_id -> Int4,
+        class_id -> Int4,
+    }
+}
+
+table! {
+    teaching_assignments (id) {
+        id -> Int4,
+        teacher_id -> Int4,
+        subject_id -> Int4,
+        class_id -> Int4,
+        school_year -> Text,
+    }
+}
+
+table! {
+    timetable (id) {
+        id -> Int4,
+        class_id -> Int4,
+        school_year -> Text,
+        weekday -> Int2,
+        lesson_number -> Int2,
+        subject_id -> Int4,
+        teacher_id -> Int4,
+        room -> Nullable<Text>,
     }
 }
 
@@ -12,11 +210,78 @@ table! {
     users (id) {
         id -> Int4,
         login -> Text,
-        password -> Text,
+        password_hash -> Text,
+        role -> Text,
+        student_id -> Nullable<Int4>,
+        active -> Bool,
+        first_name -> Nullable<Text>,
+        last_name -> Nullable<Text>,
     }
 }
 
+joinable!(announcements -> classes (class_id));
+joinable!(announcements -> users (author_id));
+joinable!(attendance -> lesson_times (lesson_number));
+joinable!(attendance -> students (student_id));
+joinable!(attendance -> subjects (subject_id));
+joinable!(attendance -> timetable (timetable_id));
+joinable!(attendance -> users (teacher_id));
+joinable!(behavior_grades -> students (student_id));
+joinable!(behavior_grades -> users (teacher_id));
+joinable!(calendar_tokens -> users (user_id));
+joinable!(classes -> users (homeroom_teacher_id));
+joinable!(closed_school_years -> users (closed_by));
+joinable!(final_grades -> students (student_id));
+joinable!(final_grades -> subjects (subject_id));
+joinable!(final_grades -> users (teacher_id));
+joinable!(grades -> students (student_id));
+joinable!(grades -> subjects (subject_id));
+joinable!(grades -> users (teacher_id));
+joinable!(guardians -> students (student_id));
+joinable!(guardians -> users (user_id));
+joinable!(lessons -> classes (class_id));
+joinable!(lessons -> lesson_times (lesson_number));
+joinable!(lessons -> subjects (subject_id));
+joinable!(lessons -> timetable (timetable_id));
+joinable!(lessons -> users (teacher_id));
+joinable!(message_recipients -> messages (message_id));
+joinable!(message_recipients -> users (user_id));
+joinable!(messages -> users (sender_id));
+joinable!(past_classes -> classes (class_id));
+joinable!(past_classes -> students (student_id));
+joinable!(sessions -> users (user_id));
+joinable!(students -> classes (class_id));
+joinable!(teacher_classes -> classes (class_id));
+joinable!(teacher_classes -> users (user_id));
+joinable!(teaching_assignments -> classes (class_id));
+joinable!(teaching_assignments -> subjects (subject_id));
+joinable!(teaching_assignments -> users (teacher_id));
+joinable!(timetable -> classes (class_id));
+joinable!(timetable -> lesson_times (lesson_number));
+joinable!(timetable -> subjects (subject_id));
+joinable!(timetable -> users (teacher_id));
+joinable!(users -> students (student_id));
+
 allow_tables_to_appear_in_same_query!(
+    announcements,
+    attendance,
+    behavior_grades,
+    calendar_tokens,
+    classes,
+    closed_school_years,
+    final_grades,
+    grades,
+    guardians,
+    lesson_times,
+    lessons,
+    message_recipients,
+    messages,
+    past_classes,
+    sessions,
     students,
+    subjects,
+    teacher_classes,
+    teaching_assignments,
+    timetable,
     users,
 );
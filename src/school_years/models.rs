@@ -0,0 +1,48 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use crate::schema::closed_school_years;
+use crate::database::Database;
+use crate::error::ApiError;
+use actix_web::actix::{Message, Handler};
+use diesel;
+use diesel::pg::PgConnection;
+
+#[allow(unused_imports)] // Throws errors without this import, but throws warning with it :/
+use diesel::prelude::*;
+
+mod imports;
+
+/// Grades, attendance and term grades of a school year that was rolled over can't be changed.
+pub fn require_open(conn: &PgConnection, school_year: &str) -> Result<(), ApiError> {
+    if is_closed(conn, school_year)? {
+        Err(ApiError::Conflict(format!(
+            "School year {} is closed, its grades and attendance can't be changed.", school_year
+        )))
+    } else {
+        Ok(())
+    }
+}
+
+fn is_closed(conn: &PgConnection, school_year: &str) -> Result<bool, ApiError> {
+    Ok(diesel::select(diesel::dsl::exists(
+        closed_school_years::table.filter(closed_school_years::school_year.eq(school_year))
+    )).get_result::<bool>(conn)?)
+}
+
+/// e.g. `2019/2020` after `2018/2019`, `None` if the year isn't like that.
+fn next_school_year(school_year: &str) -> Option<String> {
+    let first: i32 = school_year.split('/').next()?.parse().ok()?;
+    Some(format!("{}/{}", first + 1, first + 2))
+}
+
+/// The grade a class name starts with and the rest of it, e.g. `(3, "d")` for `3d`.
+fn split_name(name: &str) -> Option<(u8, &str)> {
+    let digits = name.chars().take_while(|c| c.is_ascii_digit()).count();
+    let grade = name[..digits].parse().ok()?;
+    Some((grade, &name[digits..]))
+}
+
+/* Rollover */
+mod rollover;
+pub use rollover::*;
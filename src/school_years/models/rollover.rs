@@ -0,0 +1,277 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use super::*;
+use super::imports::*;
+
+use std::collections::BTreeMap;
+use diesel::result::{DatabaseErrorKind, Error::DatabaseError};
+use crate::classes::Class;
+use crate::students::live;
+
+/// Moves every class of a school year on to the next one, e.g. `?dry_run=true`.
+///
+/// `3d` of `2018/2019` becomes `4d` of `2019/2020` with all of its students, while
+/// the students of the final grade's classes graduate and stay behind. Then the school year
+/// is closed, so its grades and attendance can't be changed anymore. All of it happens in
+/// one transaction, or with `dry_run` is only planned.
+///
+/// Admins only.
+pub fn rollover((request, user, query, fields): (HttpRequest<State>, CurrentUser, Query<RolloverQuery>, Json<RolloverRequest>))
+    -> Box<Future<Item = Json<RolloverResponse>, Error = actix_web::Error>>
+{
+    debug!("Request to roll over school year: {:?} {:?}", &*query, &*fields);
+    request.state().db
+        .send(Rollover {
+            user,
+            dry_run: query.dry_run,
+            fields: fields.into_inner()
+        })
+        .from_err()
+        .and_then(|res| res.map(Json).map_err(actix_web::Error::from))
+        .responder()
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct RolloverQuery {
+    #[serde(default)]
+    pub dry_run: bool
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct RolloverRequest {
+    /// The school year that's ending, e.g. `2018/2019`. Required, since a rollover in
+    /// September would otherwise close the year that's just started.
+    pub school_year: String,
+    /// Classes of this grade graduate, e.g. `4` in a four year high school
+    pub final_grade: u8,
+    /// New names of some of the classes by their id, instead of the next grade's.
+    /// A class named here moves on even if it's in the final grade.
+    #[serde(default)]
+    pub names: BTreeMap<i32, String>
+}
+
+impl Validate for RolloverRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut validator = Validator::new();
+        validator.school_year("school_year", &self.school_year);
+        validator.range("final_grade", i64::from(self.final_grade), 1, 99);
+        for (id, name) in &self.names {
+            validator.class_name(&format!("names.{}", id), name);
+        }
+        validator.finish()
+    }
+}
+
+/// What happens to one class.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ClassRollover {
+    pub class_id: i32,
+    pub name: String,
+    /// Current students, not counting earlier graduates or deleted students, who stay behind
+    pub students: usize,
+    /// The students graduate instead of moving on
+    pub graduating: bool,
+    /// The class the students move on to, `None` for graduating ones
+    pub new_name: Option<String>,
+    /// `None` if the class has yet to be created, or for graduating ones
+    pub new_class_id: Option<i32>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RolloverResponse {
+    pub message: String,
+    pub dry_run: bool,
+    pub school_year: String,
+    pub next_school_year: String,
+    /// Ordered by name
+    pub classes: Vec<ClassRollover>,
+    pub promoted: usize,
+    pub graduated: usize,
+}
+
+pub struct Rollover {
+    pub user: CurrentUser,
+    pub dry_run: bool,
+    pub fields: RolloverRequest,
+}
+
+impl Message for Rollover {
+    type Result = Result<RolloverResponse, ApiError>;
+}
+
+impl Handler<Rollover> for Database {
+    type Result = Result<RolloverResponse, ApiError>;
+
+    /// Classes of the next school year that already exist are reused, new ones keep
+    /// the homeroom teacher and every teacher of the class keeps access to it.
+    fn handle(&mut self, msg: Rollover, _: &mut Self::Context) -> Self::Result {
+        use crate::schema::{classes, past_classes, students, teacher_classes};
+        require_role(&msg.user, &[Role::Admin])?;
+        msg.fields.validate()?;
+        let conn = self.conn()?;
+        let fields = msg.fields;
+
+        let school_year = fields.school_year;
+        let next_year = next_school_year(&school_year)
+            .ok_or_else(|| ApiError::BadRequest(format!("`{}` isn't a school year.", school_year)))?;
+        if is_closed(&conn, &school_year)? {
+            return Err(ApiError::Conflict(format!("School year {} has already been rolled over.", school_year)));
+        }
+
+        let old_classes = classes::table
+            .filter(classes::school_year.eq(&school_year))
+            .order(classes::name)
+            .load::<Class>(&conn)?;
+        if old_classes.is_empty() {
+            return Err(ApiError::NotFound(format!("There are no classes in school year {}.", school_year)));
+        }
+        if let Some(id) = fields.names.keys().find(|&&id| !old_classes.iter().any(|class| class.id == id)) {
+            return Err(ApiError::NotFound(format!("Class with id of {} isn't in school year {}.", id, school_year)));
+        }
+        let existing: BTreeMap<String, i32> = classes::table
+            .filter(classes::school_year.eq(&next_year))
+            .select((classes::name, classes::id))
+            .load::<(String, i32)>(&conn)?
+            .into_iter()
+            .collect();
+        let old_ids: Vec<i32> = old_classes.iter().map(|class| class.id).collect();
+        let mut counts: BTreeMap<i32, usize> = BTreeMap::new();
+        for class_id in students::table
+            .filter(students::class_id.eq_any(&old_ids))
+            .filter(students::graduated_in.is_null())
+            .filter(live())
+            .select(students::class_id)
+            .load::<i32>(&conn)?
+        {
+            *counts.entry(class_id).or_insert(0) += 1;
+        }
+
+        let mut validator = Validator::new();
+        let mut moving_to: BTreeMap<String, i32> = BTreeMap::new();
+        let mut plan: Vec<(Class, ClassRollover)> = Vec::with_capacity(old_classes.len());
+        for class in old_classes {
+            let new_name = match fields.names.get(&class.id) {
+                Some(name) => Some(name.clone()),
+                None => match split_name(&class.name) {
+                    Some((grade, _)) if grade >= fields.final_grade => None,
+                    Some((grade, letters)) => Some(format!("{}{}", grade + 1, letters)),
+                    None => {
+                        validator.check(
+                            &format!("names.{}", class.id),
+                            false,
+                            &format!("is required, `{}` doesn't start with a grade", class.name)
+                        );
+                        None
+                    }
+                }
+            };
+            if let Some(ref new_name) = new_name {
+                if let Some(other) = moving_to.insert(new_name.clone(), class.id) {
+                    return Err(ApiError::Conflict(format!(
+                        "Classes with ids of {} and {} would both become `{}`.", other, class.id, new_name
+                    )));
+                }
+            }
+            let rollover = ClassRollover {
+                class_id: class.id,
+                name: class.name.clone(),
+                students: counts.get(&class.id).cloned().unwrap_or(0),
+                graduating: new_name.is_none(),
+                new_class_id: new_name.as_ref().and_then(|name| existing.get(name).cloned()),
+                new_name,
+            };
+            plan.push((class, rollover));
+        }
+        validator.finish()?;
+
+        if !msg.dry_run {
+            conn.transaction::<_, ApiError, _>(|| {
+                for (class, rollover) in plan.iter_mut() {
+                    let current = students::table
+                        .filter(students::class_id.eq(class.id))
+                        .filter(students::graduated_in.is_null())
+                        .filter(live());
+                    match rollover.new_name {
+                        Some(ref new_name) => {
+                            let new_id = match rollover.new_class_id {
+                                Some(id) => id,
+                                None => diesel::insert_into(classes::table)
+                                    .values((
+                                        classes::name.eq(new_name),
+                                        classes::school_year.eq(&next_year),
+                                        classes::homeroom_teacher_id.eq(class.homeroom_teacher_id),
+                                    ))
+                                    .returning(classes::id)
+                                    .get_result::<i32>(&conn)?,
+                            };
+                            rollover.new_class_id = Some(new_id);
+
+                            let access: Vec<_> = teacher_classes::table
+                                .filter(teacher_classes::class_id.eq(class.id))
+                                .select(teacher_classes::user_id)
+                                .load::<i32>(&conn)?
+                                .into_iter()
+                                .map(|teacher| (teacher_classes::user_id.eq(teacher), teacher_classes::class_id.eq(new_id)))
+                                .collect();
+                            if !access.is_empty() {
+                                diesel::insert_into(teacher_classes::table)
+                                    .values(&access)
+                                    .on_conflict_do_nothing()
+                                    .execute(&conn)?;
+                            }
+                            // So the report cards of the ending year still show this class
+                            let moving: Vec<_> = current
+                                .select(students::id)
+                                .load::<i32>(&conn)?
+                                .into_iter()
+                                .map(|student| (past_classes::student_id.eq(student), past_classes::class_id.eq(class.id)))
+                                .collect();
+                            if !moving.is_empty() {
+                                diesel::insert_into(past_classes::table)
+                                    .values(&moving)
+                                    .on_conflict_do_nothing()
+                                    .execute(&conn)?;
+                            }
+                            diesel::update(current).set(students::class_id.eq(new_id)).execute(&conn)?;
+                        }
+                        None => {
+                            diesel::update(current).set(students::graduated_in.eq(school_year.as_str())).execute(&conn)?;
+                        }
+                    }
+                }
+                diesel::insert_into(closed_school_years::table)
+                    .values((
+                        closed_school_years::school_year.eq(&school_year),
+                        closed_school_years::closed_by.eq(msg.user.id),
+                    ))
+                    .execute(&conn)
+                    .map_err(|err| match err {
+                        DatabaseError(DatabaseErrorKind::UniqueViolation, _) => ApiError::Conflict(
+                            format!("School year {} has already been rolled over.", school_year)
+                        ),
+                        err => ApiError::from(err)
+                    })?;
+                Ok(())
+            })?;
+            info!("School year {} rolled over into {}.", school_year, next_year);
+        }
+
+        let classes: Vec<ClassRollover> = plan.into_iter().map(|(_, rollover)| rollover).collect();
+        let promoted: usize = classes.iter().filter(|class| !class.graduating).map(|class| class.students).sum();
+        let graduated: usize = classes.iter().filter(|class| class.graduating).map(|class| class.students).sum();
+        Ok(RolloverResponse {
+            message: if msg.dry_run {
+                "Nothing was changed, this is what would happen.".to_string()
+            } else {
+                format!("School year {} was rolled over into {}.", school_year, next_year)
+            },
+            dry_run: msg.dry_run,
+            school_year,
+            next_school_year: next_year,
+            classes,
+            promoted,
+            graduated,
+        })
+    }
+}
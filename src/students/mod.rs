@@ -7,10 +7,27 @@ extern crate actix_web;
 mod models;
 
 pub use models::{
+    Student,
+    PhoneNumber,
+    RawPhoneNumber,
+    live,
+    require_live,
+    RegisterKey,
+    after_in_register,
     create,
+    import,
+    MAX_IMPORT_SIZE,
+    batch_create,
+    batch_update,
+    batch_delete,
     read,
+    export_csv,
+    export_xlsx,
+    read_one,
     update,
-    delete
+    delete,
+    restore,
+    Purger
 };
 
 
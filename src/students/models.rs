@@ -3,35 +3,102 @@
 
 use crate::schema::students;
 use crate::database::Database;
+use crate::error::ApiError;
 use actix_web::actix::{Message, Handler};
+use chrono::NaiveDateTime;
 use diesel;
+use diesel::dsl::IsNull;
+use diesel::pg::{Pg, PgConnection};
+use diesel::sql_types::Bool;
 
 #[allow(unused_imports)] // Throws errors without this import, but throws warning with it :/
 use diesel::prelude::*;
 
 mod imports;
 
+mod phone;
+pub use phone::{PhoneNumber, RawPhoneNumber};
+
 #[derive(Queryable, Serialize, Deserialize, Debug)]
 pub struct Student {
     pub id: i32,
     pub first_name: String,
     pub last_name: String,
-    pub class: String,
-    pub phone_number: i32
+    pub phone_number: PhoneNumber,
+    /// The last class of graduates
+    pub class_id: i32,
+    /// The school year a graduate finished, `None` for current students
+    pub graduated_in: Option<String>,
+    /// Deleted students are purged once the retention period is over
+    pub deleted_at: Option<NaiveDateTime>
+}
+
+/// Students that haven't been deleted. Only listing, restoring and purging deleted students
+/// look past it, everywhere else a deleted student is as good as gone.
+pub fn live() -> IsNull<students::deleted_at> {
+    students::deleted_at.is_null()
+}
+
+/// Fails with `ApiError::NotFound` if there's no such student or they were deleted.
+pub fn require_live(conn: &PgConnection, student: i32) -> Result<(), ApiError> {
+    let found = diesel::select(diesel::dsl::exists(students::table.find(student).filter(live())))
+        .get_result::<bool>(conn)?;
+    if found {
+        Ok(())
+    } else {
+        Err(ApiError::NotFound(format!("Student with id of {} not found.", student)))
+    }
+}
+
+/// Where the student is in the class register: last name, first name, then id to break ties.
+pub type RegisterKey = (String, String, i32);
+
+/// The students after `key` in the class register, for reading it a page at a time
+/// ordered by `(last_name, first_name, id)`.
+pub fn after_in_register(key: RegisterKey) -> Box<BoxableExpression<students::table, Pg, SqlType = Bool>> {
+    use crate::schema::students::dsl::*;
+    let (last, first, after) = key;
+    Box::new(last_name.gt(last.clone())
+        .or(last_name.eq(last.clone()).and(first_name.gt(first.clone())))
+        .or(last_name.eq(last).and(first_name.eq(first)).and(id.gt(after))))
 }
 
 /* Create */
 mod create;
 pub use create::*;
 
+/* Import */
+mod import;
+pub use import::*;
+
+/* Batch */
+mod batch;
+pub use batch::*;
+
 /* Read */
 mod read;
 pub use read::*;
 
+/* Export */
+mod export;
+pub use export::*;
+
+/* Read one */
+mod read_one;
+pub use read_one::*;
+
 /* Update */
 mod update;
 pub use update::*;
 
 /* Delete */
 mod delete;
-pub use delete::*;
\ No newline at end of file
+pub use delete::*;
+
+/* Restore */
+mod restore;
+pub use restore::*;
+
+/* Purge */
+mod purge;
+pub use purge::*;
\ No newline at end of file
@@ -0,0 +1,222 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use super::*;
+use super::imports::*;
+
+use diesel::result::Error as DieselError;
+use crate::error::ErrorBody;
+
+/// Adds every student from the array of create request bodies, e.g. `?all_or_nothing=true`.
+///
+/// Every student is added like with `create`, all of them in one transaction. By default
+/// a failed one doesn't stop the others, with `all_or_nothing` nothing is saved if any fails.
+pub fn batch_create((request, user, query, items): (HttpRequest<State>, CurrentUser, Query<BatchQuery>, Json<Vec<CreateRequest>>))
+    -> Box<Future<Item = Json<BatchResponse>, Error = actix_web::Error>>
+{
+    debug!("Request to create {} students: {:?}", items.len(), &*query);
+    send_batch(&request, user, query.into_inner(), BatchItems::Create(items.into_inner()))
+}
+
+/// Edits every student from the array of update request bodies, each with its `id`.
+///
+/// See `batch_create`.
+pub fn batch_update((request, user, query, items): (HttpRequest<State>, CurrentUser, Query<BatchQuery>, Json<Vec<UpdateItem>>))
+    -> Box<Future<Item = Json<BatchResponse>, Error = actix_web::Error>>
+{
+    debug!("Request to update {} students: {:?}", items.len(), &*query);
+    send_batch(&request, user, query.into_inner(), BatchItems::Update(items.into_inner()))
+}
+
+/// Deletes every student from the array of ids, admins only.
+///
+/// See `batch_create`.
+pub fn batch_delete((request, user, query, ids): (HttpRequest<State>, CurrentUser, Query<BatchQuery>, Json<Vec<i32>>))
+    -> Box<Future<Item = Json<BatchResponse>, Error = actix_web::Error>>
+{
+    debug!("Request to delete {} students: {:?}", ids.len(), &*query);
+    send_batch(&request, user, query.into_inner(), BatchItems::Delete(ids.into_inner()))
+}
+
+fn send_batch(request: &HttpRequest<State>, user: CurrentUser, query: BatchQuery, items: BatchItems)
+    -> Box<Future<Item = Json<BatchResponse>, Error = actix_web::Error>>
+{
+    request.state().db
+        .send(BatchRequest{user, all_or_nothing: query.all_or_nothing, items})
+        .from_err()
+        .and_then(|res| res.map(Json).map_err(actix_web::Error::from))
+        .responder()
+}
+
+/// Same as the most students read at once.
+pub const MAX_BATCH_SIZE: usize = 500;
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct BatchQuery {
+    #[serde(default)]
+    pub all_or_nothing: bool
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct UpdateItem {
+    pub id: i32,
+    #[serde(flatten)]
+    pub fields: UpdateRequest
+}
+
+pub enum BatchItems {
+    Create(Vec<CreateRequest>),
+    Update(Vec<UpdateItem>),
+    Delete(Vec<i32>),
+}
+
+impl BatchItems {
+    fn len(&self) -> usize {
+        match *self {
+            BatchItems::Create(ref items) => items.len(),
+            BatchItems::Update(ref items) => items.len(),
+            BatchItems::Delete(ref ids) => ids.len(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchStatus {
+    Created,
+    Updated,
+    Deleted,
+    Failed,
+    /// It succeeded, but something else failed with `all_or_nothing`
+    RolledBack,
+}
+
+#[derive(Serialize)]
+pub struct BatchResult {
+    /// Position in the request's array
+    pub index: usize,
+    pub status: BatchStatus,
+    /// The student's id, unless adding them failed or was rolled back
+    pub id: Option<i32>,
+    /// The added or edited student
+    pub student: Option<Student>,
+    /// Same as the body of the error response the single student endpoint would give
+    pub error: Option<ErrorBody>,
+}
+
+#[derive(Serialize)]
+pub struct BatchResponse {
+    pub message: String,
+    /// `false` when `all_or_nothing` rolled everything back
+    pub committed: bool,
+    pub succeeded: usize,
+    pub failed: usize,
+    /// In the same order as the request's array
+    pub results: Vec<BatchResult>,
+}
+
+pub struct BatchRequest {
+    pub user: CurrentUser,
+    pub all_or_nothing: bool,
+    pub items: BatchItems,
+}
+
+impl Message for BatchRequest {
+    type Result = Result<BatchResponse, ApiError>;
+}
+
+impl Handler<BatchRequest> for Database {
+    type Result = Result<BatchResponse, ApiError>;
+
+    fn handle(&mut self, msg: BatchRequest, _: &mut Self::Context) -> Self::Result {
+        match msg.items {
+            BatchItems::Delete(_) => require_role(&msg.user, &[Role::Admin])?,
+            _ => require_role(&msg.user, &[Role::Admin, Role::Teacher])?,
+        }
+        if msg.items.len() > MAX_BATCH_SIZE {
+            return Err(ApiError::BadRequest(format!(
+                "At most {} students can be changed at once, got {}.", MAX_BATCH_SIZE, msg.items.len()
+            )));
+        }
+        let conn = self.conn()?;
+        let user = &msg.user;
+        match msg.items {
+            BatchItems::Create(items) => run(
+                &conn,
+                items.into_iter().map(|fields| (None, fields)).collect(),
+                msg.all_or_nothing,
+                BatchStatus::Created,
+                |fields| create_student(&conn, user, fields).map(Some),
+            ),
+            BatchItems::Update(items) => run(
+                &conn,
+                items.into_iter().map(|item| (Some(item.id), item)).collect(),
+                msg.all_or_nothing,
+                BatchStatus::Updated,
+                |item| update_student(&conn, user, item.id, item.fields).map(Some),
+            ),
+            BatchItems::Delete(ids) => run(
+                &conn,
+                ids.into_iter().map(|id| (Some(id), id)).collect(),
+                msg.all_or_nothing,
+                BatchStatus::Deleted,
+                |id| delete_student(&conn, id).map(|_| None),
+            ),
+        }
+    }
+}
+
+/// Applies `apply` to every item in its own savepoint, inside one transaction.
+///
+/// The items come with the id of the student they're about, if it's known up front.
+fn run<T, F>(conn: &PgConnection, items: Vec<(Option<i32>, T)>, all_or_nothing: bool, done: BatchStatus, mut apply: F)
+    -> Result<BatchResponse, ApiError>
+    where F: FnMut(T) -> Result<Option<Student>, ApiError>
+{
+    let mut outcomes = Vec::with_capacity(items.len());
+    let transaction = conn.transaction::<_, ApiError, _>(|| {
+        for (id, item) in items {
+            // A failed statement would abort the whole transaction without the savepoint
+            outcomes.push((id, conn.transaction(|| apply(item))));
+        }
+        if all_or_nothing && outcomes.iter().any(|(_, outcome)| outcome.is_err()) {
+            return Err(DieselError::RollbackTransaction.into());
+        }
+        Ok(())
+    });
+    let committed = match transaction {
+        Ok(()) => true,
+        Err(ApiError::Database(DieselError::RollbackTransaction)) => false,
+        Err(err) => return Err(err),
+    };
+
+    let results: Vec<BatchResult> = outcomes
+        .into_iter()
+        .enumerate()
+        .map(|(index, (id, outcome))| match outcome {
+            Ok(_) if !committed => BatchResult { index, status: BatchStatus::RolledBack, id, student: None, error: None },
+            Ok(student) => BatchResult {
+                index,
+                status: done,
+                id: id.or_else(|| student.as_ref().map(|student| student.id)),
+                student,
+                error: None,
+            },
+            Err(err) => BatchResult { index, status: BatchStatus::Failed, id, student: None, error: Some(err.body()) },
+        })
+        .collect();
+    let failed = results.iter().filter(|result| result.status == BatchStatus::Failed).count();
+    let succeeded = if committed { results.len() - failed } else { 0 };
+
+    Ok(BatchResponse {
+        message: if committed {
+            format!("{} succeeded, {} failed.", succeeded, failed)
+        } else {
+            format!("Nothing was saved, {} failed.", failed)
+        },
+        committed,
+        succeeded,
+        failed,
+        results,
+    })
+}
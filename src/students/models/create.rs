@@ -6,33 +6,73 @@ use super::imports::*;
 
 /// This is the create handler.
 /// 
+/// Admins may add students to any class, teachers only to their own.
+/// 
 /// https://github.com/actix/actix-website/blob/master/content/docs/extractors.md#json
-pub fn create((request, new_student): (HttpRequest<State>, Json<CreateRequest>)) 
+pub fn create((request, user, new_student): (HttpRequest<State>, CurrentUser, Json<CreateRequest>)) 
     -> Box<Future<Item = Json<CreateResponse>, Error = actix_web::Error>> 
 {
     debug!("Request to create student: {:?}", &new_student);
     /* Add to database */
     request.state().db
-        .send(new_student.into_inner())
+        .send(CreateStudent {
+            user,
+            fields: new_student.into_inner()
+        })
         .from_err()
         .and_then(|res| {
-            info!("Successfully added student");
-            Ok(Json(CreateResponse {
-                message: "Success!".to_string(),
-                student: res.map_err(error::ErrorInternalServerError).ok()
-            }))
+            res.map(|student| {
+                info!("Successfully added student");
+                Json(CreateResponse {
+                    message: "Success!".to_string(),
+                    student: Some(student)
+                })
+            }).map_err(actix_web::Error::from)
         })
         .responder()
 }
 
+#[derive(Deserialize, Serialize, Debug)]
+pub struct CreateRequest {
+    pub first_name: String,
+    pub last_name: String,
+    pub class_id: i32,
+    pub phone_number: RawPhoneNumber
+}
+
 /// id should be set automatically
-#[derive(Insertable, Deserialize, Serialize, Debug)]
+#[derive(Insertable, Debug)]
 #[table_name="students"]
-pub struct CreateRequest {
-    first_name: String,
-    last_name: String,
-    class: String,
-    phone_number: i32
+pub struct NewStudent {
+    pub first_name: String,
+    pub last_name: String,
+    pub class_id: i32,
+    pub phone_number: PhoneNumber
+}
+
+impl CreateRequest {
+    /// Names without surrounding whitespace and the parsed phone number, once it's valid.
+    pub fn to_new(&self) -> Result<NewStudent, ApiError> {
+        Ok(NewStudent {
+            first_name: self.first_name.trim().to_string(),
+            last_name: self.last_name.trim().to_string(),
+            class_id: self.class_id,
+            phone_number: self.phone_number.parse().map_err(ApiError::BadRequest)?,
+        })
+    }
+}
+
+impl Validate for CreateRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut validator = Validator::new();
+        validator
+            .name("first_name", &self.first_name)
+            .name("last_name", &self.last_name);
+        if let Err(message) = self.phone_number.parse() {
+            validator.check("phone_number", false, &message);
+        }
+        validator.finish()
+    }
 }
 
 #[derive(Serialize)]
@@ -41,18 +81,30 @@ pub struct CreateResponse {
     pub student: Option<Student>
 }
 
-impl Message for CreateRequest {
-    type Result = Result<Student, diesel::result::Error>;
+pub struct CreateStudent {
+    pub user: CurrentUser,
+    pub fields: CreateRequest,
 }
 
-impl Handler<CreateRequest> for Database {
-    type Result = Result<Student, diesel::result::Error>;
+impl Message for CreateStudent {
+    type Result = Result<Student, ApiError>;
+}
+
+impl Handler<CreateStudent> for Database {
+    type Result = Result<Student, ApiError>;
 
-    fn handle(&mut self, msg: CreateRequest, _: &mut Self::Context) -> Self::Result {
-        //use crate::schema::students::dsl::*;
-        let conn = self.0.get().unwrap();
-        println!("Adding student {:?}", &msg);
-        diesel::insert_into(students::table).values(&msg).get_result::<Student>(&conn)
+    fn handle(&mut self, msg: CreateStudent, _: &mut Self::Context) -> Self::Result {
+        let conn = self.conn()?;
+        create_student(&conn, &msg.user, msg.fields)
     }
 }
 
+/// Checks and adds one student, also used by the batch endpoint.
+pub fn create_student(conn: &PgConnection, user: &CurrentUser, fields: CreateRequest) -> Result<Student, ApiError> {
+    fields.validate()?;
+    let new_student = fields.to_new()?;
+    require_class(conn, user, new_student.class_id)?;
+    debug!("Adding student {:?}", &new_student);
+    Ok(diesel::insert_into(students::table).values(&new_student).get_result::<Student>(conn)?)
+}
+
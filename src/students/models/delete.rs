@@ -4,58 +4,57 @@
 use super::*;
 use super::imports::*;
 
-use sentry::{Hub, Level};
-use sentry_actix::ActixWebHubExt;
+use chrono::Utc;
 
 /// This is the delete handler
-pub fn delete((request, id): (HttpRequest<State>, Path<i32>)) 
+/// 
+/// Only admins may delete students, 404 if there's no such student.
+/// The student is only marked as deleted, so they can be restored until they're purged.
+pub fn delete((request, user, id): (HttpRequest<State>, CurrentUser, Path<i32>)) 
     -> Box<Future<Item = HttpResponse, Error = actix_web::Error>> 
 {
-    // Diesel's `delete` method returns number of deleted rows, so we can check if we deleted something
     debug!("Request to delete student with id of {}.", id.as_ref());
     
     request.state().db
-        .send(DeleteRequest{id: id.clone()})
+        .send(DeleteRequest{id: id.clone(), user})
         .from_err()
-        .and_then(move |num_of_del_rows| {
-            let num_of_del_rows = num_of_del_rows.expect("Database error when deleting student");
-            if num_of_del_rows > 0 {
+        .and_then(move |res| {
+            res.map(|_| {
                 info!("Student with id of {} successfully deleted.", id);
-                Ok(HttpResponse::Ok()
+                HttpResponse::Ok()
                     .json(DeleteResponse {
-                        message: format!("Deleted student with id: {:?}.", id).to_string()
+                        message: format!("Deleted student with id: {:?}.", id)
                     })
-                )
-            } else {
-                let message = format!("Student with id of `{}` not found or something because I found {} rows.", id, &num_of_del_rows);
-                info!("{}", &message);
-                let hub = Hub::from_request(&request);
-
-                hub.capture_message(message.as_str(), Level::Error);
-                
-                Ok(HttpResponse::BadRequest()
-                    .json(DeleteResponse{message})
-                )
-            }
+            }).map_err(actix_web::Error::from)
         }).responder()
 }
 
-#[derive(Serialize, Deserialize)]
 pub struct DeleteRequest {
     pub id: i32,
+    pub user: CurrentUser,
 }
 
 impl Message for DeleteRequest {
-    type Result = Result<usize, diesel::result::Error>;
+    type Result = Result<usize, ApiError>;
 }
 
 impl Handler<DeleteRequest> for Database {
-    type Result = Result<usize, diesel::result::Error>;
+    type Result = Result<usize, ApiError>;
 
     fn handle(&mut self, msg: DeleteRequest, _: &mut Self::Context) -> Self::Result {
-        use crate::schema::students::dsl::*;
-        let conn = self.0.get().unwrap();
-        diesel::delete(students.filter(id.eq(msg.id))).execute(&conn)
+        require_role(&msg.user, &[Role::Admin])?;
+        let conn = self.conn()?;
+        delete_student(&conn, msg.id)
+    }
+}
+
+/// Fails with `ApiError::NotFound` when nothing was deleted, also used by the batch endpoint.
+pub fn delete_student(conn: &PgConnection, student_id: i32) -> Result<usize, ApiError> {
+    use crate::schema::students::dsl::*;
+    let not_deleted = students.filter(id.eq(student_id)).filter(live());
+    match diesel::update(not_deleted).set(deleted_at.eq(Utc::now().naive_utc())).execute(conn)? {
+        0 => Err(ApiError::NotFound(format!("Student with id of {} not found.", student_id))),
+        deleted => Ok(deleted)
     }
 }
 
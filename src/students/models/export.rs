@@ -0,0 +1,143 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use super::*;
+use super::imports::*;
+
+use bytes::Bytes;
+use futures::{stream, Stream};
+use futures::future::{self, Loop};
+use crate::export::{self, Cell, Row};
+
+const COLUMNS: [&str; 5] = ["id", "first_name", "last_name", "phone_number", "class_id"];
+
+/// Every student the list endpoint would return for the same filters and sorting, as CSV.
+///
+/// `limit` and `offset` are ignored. The students are read and sent a page at a time,
+/// each page starting after the last student of the previous one, so the whole list
+/// never sits in memory and students added or removed meanwhile don't shift the pages.
+pub fn export_csv((request, user, query): (HttpRequest<State>, CurrentUser, Query<ListQuery>))
+    -> Box<Future<Item = HttpResponse, Error = actix_web::Error>>
+{
+    debug!("Request to export students to CSV: {:?}", &*query);
+    let db = request.state().db.clone();
+    let query = query.into_inner();
+    // The first page is read before responding, so errors still get their status code
+    db.send(ExportPage{query: query.clone(), user: user.clone(), after: None})
+        .from_err()
+        .and_then(move |res| -> Result<HttpResponse, actix_web::Error> {
+            let page = res?;
+            let mut rows = vec![export::header(&COLUMNS)];
+            rows.extend(page.iter().map(row));
+            let first = export::csv(&rows, true)?;
+
+            let rest = stream::unfold(last_of_full(page), move |after| {
+                after.map(|after| {
+                    db.send(ExportPage{query: query.clone(), user: user.clone(), after: Some(after)})
+                        .from_err()
+                        .and_then(|res| -> Result<(Bytes, Option<Student>), actix_web::Error> {
+                            let page = res?;
+                            let rows: Vec<Row> = page.iter().map(row).collect();
+                            Ok((export::csv(&rows, false)?, last_of_full(page)))
+                        })
+                })
+            });
+            Ok(export::attachment(export::CSV, "students.csv")
+                .streaming(stream::once::<_, actix_web::Error>(Ok(first)).chain(rest)))
+        })
+        .responder()
+}
+
+/// Same as `export_csv`, but as XLSX, which has to be put together whole before it's sent.
+pub fn export_xlsx((request, user, query): (HttpRequest<State>, CurrentUser, Query<ListQuery>))
+    -> Box<Future<Item = HttpResponse, Error = actix_web::Error>>
+{
+    debug!("Request to export students to XLSX: {:?}", &*query);
+    let db = request.state().db.clone();
+    let query = query.into_inner();
+    future::loop_fn((vec![export::header(&COLUMNS)], None), move |(mut rows, after)| {
+        db.send(ExportPage{query: query.clone(), user: user.clone(), after})
+            .from_err()
+            .and_then(|res| -> Result<Loop<Vec<Row>, (Vec<Row>, Option<Student>)>, actix_web::Error> {
+                let page = res?;
+                rows.extend(page.iter().map(row));
+                Ok(match last_of_full(page) {
+                    Some(last) => Loop::Continue((rows, Some(last))),
+                    None => Loop::Break(rows),
+                })
+            })
+    })
+    .and_then(|rows| -> Result<HttpResponse, actix_web::Error> {
+        Ok(export::attachment(export::XLSX, "students.xlsx").body(export::xlsx("Students", &rows)?))
+    })
+    .responder()
+}
+
+/// The last student of a full page, where the next one starts. `None` if it's the last page.
+fn last_of_full(page: Vec<Student>) -> Option<Student> {
+    if page.len() < MAX_LIMIT as usize {
+        None
+    } else {
+        page.into_iter().last()
+    }
+}
+
+fn row(student: &Student) -> Row {
+    vec![
+        Cell::from(student.id),
+        Cell::from(student.first_name.as_str()),
+        Cell::from(student.last_name.as_str()),
+        Cell::from(student.phone_number.as_str()),
+        Cell::from(student.class_id),
+    ]
+}
+
+/// The largest page of students sorted like the query, that come after `after`.
+pub struct ExportPage {
+    pub query: ListQuery,
+    pub user: CurrentUser,
+    pub after: Option<Student>,
+}
+
+impl Message for ExportPage {
+    type Result = Result<Vec<Student>, ApiError>;
+}
+
+impl Handler<ExportPage> for Database {
+    type Result = Result<Vec<Student>, ApiError>;
+
+    /// Ties are broken by id in `sorted`, so (sort column, id) is unique and pages can't overlap.
+    fn handle(&mut self, msg: ExportPage, _: &mut Self::Context) -> Self::Result {
+        use crate::schema::students::dsl::*;
+        if msg.query.deleted {
+            require_role(&msg.user, &[Role::Admin])?;
+        }
+        let conn = self.conn()?;
+        let mut page = filtered(&msg.query, &msg.user);
+        if let Some(last) = msg.after {
+            page = match (msg.query.sort, msg.query.order) {
+                (SortColumn::Id, SortOrder::Asc) => page.filter(id.gt(last.id)),
+                (SortColumn::Id, SortOrder::Desc) => page.filter(id.lt(last.id)),
+                (SortColumn::FirstName, SortOrder::Asc) => page.filter(first_name.gt(last.first_name.clone())
+                    .or(first_name.eq(last.first_name).and(id.gt(last.id)))),
+                (SortColumn::FirstName, SortOrder::Desc) => page.filter(first_name.lt(last.first_name.clone())
+                    .or(first_name.eq(last.first_name).and(id.gt(last.id)))),
+                (SortColumn::LastName, SortOrder::Asc) => page.filter(last_name.gt(last.last_name.clone())
+                    .or(last_name.eq(last.last_name).and(id.gt(last.id)))),
+                (SortColumn::LastName, SortOrder::Desc) => page.filter(last_name.lt(last.last_name.clone())
+                    .or(last_name.eq(last.last_name).and(id.gt(last.id)))),
+                (SortColumn::PhoneNumber, SortOrder::Asc) => page.filter(phone_number.gt(last.phone_number.clone())
+                    .or(phone_number.eq(last.phone_number).and(id.gt(last.id)))),
+                (SortColumn::PhoneNumber, SortOrder::Desc) => page.filter(phone_number.lt(last.phone_number.clone())
+                    .or(phone_number.eq(last.phone_number).and(id.gt(last.id)))),
+                (SortColumn::ClassId, SortOrder::Asc) => page.filter(class_id.gt(last.class_id)
+                    .or(class_id.eq(last.class_id).and(id.gt(last.id)))),
+                (SortColumn::ClassId, SortOrder::Desc) => page.filter(class_id.lt(last.class_id)
+                    .or(class_id.eq(last.class_id).and(id.gt(last.id)))),
+            };
+        }
+        Ok(sorted(&msg.query, page)
+            .limit(MAX_LIMIT)
+            .load::<Student>(&conn)?)
+    }
+}
@@ -0,0 +1,189 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use super::*;
+use super::imports::*;
+
+use std::collections::{BTreeMap, BTreeSet};
+use crate::validation::FieldError;
+
+/// Adds every student from a CSV body at once, e.g. `?dry_run=true`.
+///
+/// The header has to name the `CreateRequest` fields: `first_name,last_name,class_id,phone_number`.
+/// Every row is checked first, and if any of them is invalid nothing is imported and
+/// each error is reported as `rows[<line>].<field>`, or `rows[<line>].row` for a malformed one. Otherwise all of them are inserted
+/// in one transaction, or with `dry_run` only counted.
+///
+/// Admins may import to any class, teachers only to their own.
+pub fn import((request, user, query, body): (HttpRequest<State>, CurrentUser, Query<ImportQuery>, String))
+    -> Box<Future<Item = Json<ImportResponse>, Error = actix_web::Error>>
+{
+    debug!("Request to import students: {:?}", &*query);
+    request.state().db
+        .send(ImportStudents {
+            user,
+            dry_run: query.dry_run,
+            csv: body
+        })
+        .from_err()
+        .and_then(|res| res.map(Json).map_err(actix_web::Error::from))
+        .responder()
+}
+
+/// CSV bodies up to 1 MiB, a few thousand students.
+pub const MAX_IMPORT_SIZE: usize = 1 << 20;
+
+/// Students inserted by one statement, Postgres takes at most 65535 parameters and each one has 4.
+const INSERT_CHUNK_SIZE: usize = 1000;
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct ImportQuery {
+    #[serde(default)]
+    pub dry_run: bool
+}
+
+#[derive(Serialize)]
+pub struct ImportResponse {
+    pub message: String,
+    pub dry_run: bool,
+    /// Valid rows, all of which were inserted unless it's a dry run
+    pub rows: usize,
+    /// Empty for dry runs
+    pub students: Vec<Student>
+}
+
+pub struct ImportStudents {
+    pub user: CurrentUser,
+    pub dry_run: bool,
+    pub csv: String,
+}
+
+impl Message for ImportStudents {
+    type Result = Result<ImportResponse, ApiError>;
+}
+
+impl Handler<ImportStudents> for Database {
+    type Result = Result<ImportResponse, ApiError>;
+
+    fn handle(&mut self, msg: ImportStudents, _: &mut Self::Context) -> Self::Result {
+        use crate::schema::classes;
+        require_role(&msg.user, &[Role::Admin, Role::Teacher])?;
+        let conn = self.conn()?;
+
+        let mut reader = csv::ReaderBuilder::new()
+            .trim(csv::Trim::All)
+            .from_reader(msg.csv.as_bytes());
+        let headers = reader
+            .headers()
+            .map_err(|err| ApiError::BadRequest(format!("Couldn't read the CSV header: {}", err)))?
+            .clone();
+
+        let mut rows: Vec<(u64, NewStudent)> = Vec::new();
+        // (line, error), so they can be ordered by line
+        let mut errors: Vec<(u64, FieldError)> = Vec::new();
+        let mut total = 0;
+        for record in reader.records() {
+            total += 1;
+            let record = match record {
+                Ok(record) => record,
+                Err(err) => {
+                    // e.g. a wrong number of columns, the rest of the rows can still be read
+                    let line = err.position().map_or(0, |position| position.line());
+                    errors.push((line, FieldError {
+                        field: format!("rows[{}].row", line),
+                        message: format!("{}", err),
+                    }));
+                    continue;
+                }
+            };
+            let line = record.position().map_or(0, |position| position.line());
+            match record.deserialize::<CreateRequest>(Some(&headers)) {
+                Ok(row) => match row.validate() {
+                    Ok(()) => rows.push((line, row.to_new()?)),
+                    Err(invalid) => errors.extend(invalid.errors.into_iter().map(|error| (line, FieldError {
+                        field: format!("rows[{}].{}", line, error.field),
+                        message: error.message,
+                    }))),
+                },
+                Err(err) => {
+                    let field = match *err.kind() {
+                        csv::ErrorKind::Deserialize { ref err, .. } => err
+                            .field()
+                            .and_then(|index| headers.get(index as usize))
+                            .unwrap_or("row"),
+                        _ => "row",
+                    };
+                    errors.push((line, FieldError {
+                        field: format!("rows[{}].{}", line, field),
+                        message: match *err.kind() {
+                            csv::ErrorKind::Deserialize { ref err, .. } => format!("{}", err.kind()),
+                            _ => format!("{}", err),
+                        },
+                    }));
+                }
+            }
+        }
+        if total == 0 {
+            return Err(ApiError::BadRequest("The CSV has no rows.".to_string()));
+        }
+
+        // Every class once, so a teacher importing a whole class isn't checked for each student
+        let mut class_ids: BTreeMap<i32, Option<String>> = BTreeMap::new();
+        for &(_, ref row) in &rows {
+            class_ids.entry(row.class_id).or_insert(None);
+        }
+        for (&class, problem) in class_ids.iter_mut() {
+            let exists = diesel::select(diesel::dsl::exists(classes::table.find(class))).get_result::<bool>(&conn)?;
+            *problem = if !exists {
+                Some(format!("class with id of {} not found", class))
+            } else {
+                match require_class(&conn, &msg.user, class) {
+                    Ok(()) => None,
+                    Err(ApiError::Forbidden(message)) => Some(message),
+                    Err(err) => return Err(err),
+                }
+            };
+        }
+        for &(line, ref row) in &rows {
+            if let Some(Some(ref message)) = class_ids.get(&row.class_id) {
+                errors.push((line, FieldError {
+                    field: format!("rows[{}].class_id", line),
+                    message: message.clone(),
+                }));
+            }
+        }
+
+        if !errors.is_empty() {
+            errors.sort_by_key(|&(line, _)| line);
+            let invalid: BTreeSet<u64> = errors.iter().map(|&(line, _)| line).collect();
+            return Err(ApiError::Validation(ValidationErrors {
+                message: format!("{} of {} rows have errors, nothing was imported.", invalid.len(), total),
+                errors: errors.into_iter().map(|(_, error)| error).collect(),
+            }));
+        }
+
+        let valid: Vec<NewStudent> = rows.into_iter().map(|(_, row)| row).collect();
+        if msg.dry_run {
+            return Ok(ImportResponse {
+                message: format!("All {} rows are valid, nothing was imported.", valid.len()),
+                dry_run: true,
+                rows: valid.len(),
+                students: Vec::new(),
+            });
+        }
+        let students = conn.transaction::<_, diesel::result::Error, _>(|| {
+            let mut inserted = Vec::with_capacity(valid.len());
+            for chunk in valid.chunks(INSERT_CHUNK_SIZE) {
+                inserted.extend(diesel::insert_into(students::table).values(chunk).get_results::<Student>(&conn)?);
+            }
+            Ok(inserted)
+        })?;
+        info!("Imported {} students.", students.len());
+        Ok(ImportResponse {
+            message: format!("Imported {} students.", students.len()),
+            dry_run: false,
+            rows: valid.len(),
+            students,
+        })
+    }
+}
@@ -6,10 +6,20 @@ pub use actix_web::{
     HttpRequest,
     HttpResponse,
     Path,
+    Query,
     AsyncResponder,
     error
 };
 pub use log::{debug, error, info, warn};
 pub use futures::future::Future;
 
-pub use crate::State;
\ No newline at end of file
+pub use crate::State;
+pub use crate::error::ApiError;
+pub use crate::login::{
+    CurrentUser,
+    Role,
+    require_role,
+    require_class,
+    require_student
+};
+pub use crate::validation::{Validate, ValidationErrors, Validator, require_changes};
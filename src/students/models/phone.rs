@@ -0,0 +1,181 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use std::fmt;
+use std::io::Write;
+
+use diesel::deserialize::{self, FromSql};
+use diesel::pg::Pg;
+use diesel::serialize::{self, Output, ToSql};
+use diesel::sql_types::Text;
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
+
+/// Numbers without a country code are Polish.
+const DEFAULT_COUNTRY_CODE: &str = "48";
+
+/// A phone number in E.164 format, e.g. `+48123456789`.
+///
+/// Parsed from `"+48 123 456 789"`, `"0048123456789"`, `"123-456-789"` or `123456789`.
+#[derive(Clone, Debug, PartialEq, Eq, AsExpression, FromSqlRow)]
+#[sql_type = "Text"]
+pub struct PhoneNumber(String);
+
+impl PhoneNumber {
+    pub fn parse(input: &str) -> Result<PhoneNumber, String> {
+        let input = input.trim();
+        let international = input.starts_with('+') || input.starts_with("00");
+        let mut digits = String::new();
+        for (i, c) in input.chars().enumerate() {
+            match c {
+                '0'..='9' => digits.push(c),
+                '+' if i == 0 => {}
+                ' ' | '.' | '(' | ')' => {}
+                // but not a minus sign
+                '-' if i > 0 => {}
+                _ => return Err(format!("phone number can't contain `{}`", c)),
+            }
+        }
+
+        let digits = if input.starts_with("00") {
+            digits[2..].to_string()
+        } else if !international && digits.len() == 9 {
+            format!("{}{}", DEFAULT_COUNTRY_CODE, digits)
+        } else if !international {
+            return Err("phone number without a country code must have nine digits".to_string());
+        } else {
+            digits
+        };
+
+        if digits.len() < 7 || digits.len() > 15 || digits.starts_with('0') {
+            return Err("phone number must have a country code and 7 to 15 digits".to_string());
+        }
+        Ok(PhoneNumber(format!("+{}", digits)))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for PhoneNumber {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl ToSql<Text, Pg> for PhoneNumber {
+    fn to_sql<W: Write>(&self, out: &mut Output<W, Pg>) -> serialize::Result {
+        <String as ToSql<Text, Pg>>::to_sql(&self.0, out)
+    }
+}
+
+impl FromSql<Text, Pg> for PhoneNumber {
+    fn from_sql(bytes: Option<&[u8]>) -> deserialize::Result<Self> {
+        <String as FromSql<Text, Pg>>::from_sql(bytes).map(PhoneNumber)
+    }
+}
+
+impl Serialize for PhoneNumber {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for PhoneNumber {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        RawPhoneNumber::deserialize(deserializer)?.parse().map_err(de::Error::custom)
+    }
+}
+
+/// A phone number as it was sent in a request body, not parsed yet.
+///
+/// Request bodies take this instead of `PhoneNumber`, so `Validate` can report
+/// a bad number as a `phone_number` field error instead of rejecting the whole body.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RawPhoneNumber(String);
+
+impl RawPhoneNumber {
+    pub fn parse(&self) -> Result<PhoneNumber, String> {
+        PhoneNumber::parse(&self.0)
+    }
+}
+
+impl Serialize for RawPhoneNumber {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+struct RawPhoneNumberVisitor;
+
+impl<'de> de::Visitor<'de> for RawPhoneNumberVisitor {
+    type Value = RawPhoneNumber;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a phone number")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<RawPhoneNumber, E> {
+        Ok(RawPhoneNumber(value.to_string()))
+    }
+
+    /// Numbers used to be sent as integers.
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<RawPhoneNumber, E> {
+        Ok(RawPhoneNumber(value.to_string()))
+    }
+
+    /// Negative ones fail parsing on the `-`.
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<RawPhoneNumber, E> {
+        Ok(RawPhoneNumber(value.to_string()))
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for RawPhoneNumber {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(RawPhoneNumberVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parsed(input: &str) -> String {
+        PhoneNumber::parse(input).unwrap().as_str().to_string()
+    }
+
+    #[test]
+    fn nine_digits_are_polish() {
+        assert_eq!(parsed("123456789"), "+48123456789");
+        assert_eq!(parsed("123-456-789"), "+48123456789");
+        assert_eq!(parsed(" 123 456 789 "), "+48123456789");
+    }
+
+    #[test]
+    fn keeps_the_country_code() {
+        assert_eq!(parsed("+48 123 456 789"), "+48123456789");
+        assert_eq!(parsed("0048123456789"), "+48123456789");
+        assert_eq!(parsed("+49 (30) 1234567"), "+49301234567");
+    }
+
+    #[test]
+    fn rejects_what_isnt_e164() {
+        assert!(PhoneNumber::parse("12345678").is_err());
+        assert!(PhoneNumber::parse("1234567890").is_err());
+        assert!(PhoneNumber::parse("+48 123 abc 789").is_err());
+        assert!(PhoneNumber::parse("123+456789").is_err());
+        assert!(PhoneNumber::parse("-123456789").is_err());
+        assert!(PhoneNumber::parse("+123456").is_err());
+        assert!(PhoneNumber::parse("+1234567890123456").is_err());
+        assert!(PhoneNumber::parse("+0123456789").is_err());
+        assert!(PhoneNumber::parse("").is_err());
+    }
+
+    #[test]
+    fn numbers_sent_as_integers() {
+        let raw: RawPhoneNumber = serde_json::from_str("123456789").unwrap();
+        assert_eq!(raw.parse().unwrap().as_str(), "+48123456789");
+        let raw: RawPhoneNumber = serde_json::from_str("-123456789").unwrap();
+        assert!(raw.parse().is_err());
+    }
+}
@@ -0,0 +1,67 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use super::*;
+use super::imports::*;
+
+use std::time::Duration as Interval;
+use actix_web::actix::{Actor, Addr, Arbiter, AsyncContext, Context};
+use chrono::{Duration, Utc};
+
+/// Deleted students are looked for every hour.
+const PURGE_INTERVAL_SECS: u64 = 60 * 60;
+
+/// Permanently deletes the students that were deleted longer than `retention` ago,
+/// together with everything that cascades from them.
+///
+/// Runs once when it's started and then every `PURGE_INTERVAL_SECS`.
+pub struct Purger {
+    pub db: Addr<Database>,
+    pub retention: Duration,
+}
+
+impl Purger {
+    fn purge(&self) {
+        let deleted_before = Utc::now().naive_utc() - self.retention;
+        Arbiter::spawn(
+            self.db
+                .send(PurgeStudents{deleted_before})
+                .then(|res| -> Result<(), ()> {
+                    match res {
+                        Ok(Ok(0)) => {}
+                        Ok(Ok(purged)) => info!("Purged {} students deleted before {}.", purged, deleted_before),
+                        Ok(Err(err)) => error!("Couldn't purge deleted students: {}", err),
+                        Err(err) => error!("Couldn't purge deleted students: {}", err),
+                    }
+                    Ok(())
+                })
+        );
+    }
+}
+
+impl Actor for Purger {
+    type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Self::Context) {
+        self.purge();
+        ctx.run_interval(Interval::from_secs(PURGE_INTERVAL_SECS), |purger, _| purger.purge());
+    }
+}
+
+pub struct PurgeStudents {
+    pub deleted_before: NaiveDateTime,
+}
+
+impl Message for PurgeStudents {
+    type Result = Result<usize, ApiError>;
+}
+
+impl Handler<PurgeStudents> for Database {
+    type Result = Result<usize, ApiError>;
+
+    fn handle(&mut self, msg: PurgeStudents, _: &mut Self::Context) -> Self::Result {
+        use crate::schema::students::dsl::*;
+        let conn = self.conn()?;
+        Ok(diesel::delete(students.filter(deleted_at.lt(msg.deleted_before))).execute(&conn)?)
+    }
+}
@@ -4,29 +4,228 @@
 use super::*;
 use super::imports::*;
 
-pub fn read(request: &HttpRequest<State>) 
-    -> Box<Future<Item = Json<Vec<Student>>, Error = actix_web::Error>> 
+use diesel::pg::Pg;
+
+/// Pages of students, e.g. `?limit=20&offset=40&sort=last_name&order=desc&class_id=1&last_name=kow`.
+/// 
+/// Admins and teachers see every student, parents only their children
+/// and students only themselves. Graduates are left out, unless `graduated=true`
+/// lists only them, and so are deleted students, unless an admin asks for `deleted=true`.
+pub fn read((request, user, query): (HttpRequest<State>, CurrentUser, Query<ListQuery>)) 
+    -> Box<Future<Item = Json<StudentsPage>, Error = actix_web::Error>> 
 {
-    debug!("Request to read all students.");
+    debug!("Request to read students: {:?}", &*query);
+    let query = query.into_inner();
+    let path = request.path().to_owned();
     request.state().db
-        .send(ReadRequest{})
+        .send(ReadRequest{query: query.clone(), user})
         .from_err()
-        .and_then(|res| res.map(Json).map_err(error::ErrorInternalServerError))
+        .and_then(move |res| {
+            res.map(|mut page| {
+                page.next = page.link(&path, &query, page.offset + page.limit);
+                page.previous = page.link(&path, &query, page.offset - page.limit);
+                Json(page)
+            }).map_err(actix_web::Error::from)
+        })
         .responder()
 }
 
-pub struct ReadRequest{}
+const DEFAULT_LIMIT: i64 = 50;
+pub const MAX_LIMIT: i64 = 500;
+
+fn default_limit() -> i64 {
+    DEFAULT_LIMIT
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum SortColumn {
+    Id,
+    FirstName,
+    LastName,
+    PhoneNumber,
+    ClassId,
+}
+
+impl Default for SortColumn {
+    fn default() -> Self {
+        SortColumn::Id
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    Asc,
+    Desc,
+}
+
+impl Default for SortOrder {
+    fn default() -> Self {
+        SortOrder::Asc
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ListQuery {
+    /// At most 500
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+    #[serde(default)]
+    pub offset: i64,
+    #[serde(default)]
+    pub sort: SortColumn,
+    #[serde(default)]
+    pub order: SortOrder,
+    pub class_id: Option<i32>,
+    /// Case insensitive prefix
+    pub first_name: Option<String>,
+    /// Case insensitive prefix
+    pub last_name: Option<String>,
+    /// Normalized like the body of a create request, so `123456789` finds `+48123456789`
+    pub phone_number: Option<PhoneNumber>,
+    /// Only graduates instead of only current students
+    #[serde(default)]
+    pub graduated: bool,
+    /// Only deleted students instead of the others, admins only
+    #[serde(default)]
+    pub deleted: bool
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct StudentsPage {
+    /// Number of students matching the filters, on all pages
+    pub total: i64,
+    pub limit: i64,
+    pub offset: i64,
+    /// Path and query of the next page, `None` on the last one
+    pub next: Option<String>,
+    /// Path and query of the previous page, `None` on the first one
+    pub previous: Option<String>,
+    pub students: Vec<Student>
+}
+
+impl StudentsPage {
+    /// Link to the page starting at `offset`, if there are students there.
+    fn link(&self, path: &str, query: &ListQuery, offset: i64) -> Option<String> {
+        if offset == self.offset || offset >= self.total || offset + self.limit <= 0 {
+            return None;
+        }
+        let mut query = query.clone();
+        query.limit = self.limit;
+        query.offset = offset.max(0);
+        serde_urlencoded::to_string(&query)
+            .ok()
+            .map(|query| format!("{}?{}", path, query))
+    }
+}
+
+/// `LIKE` pattern matching everything starting with `prefix`.
+fn prefix_pattern(prefix: &str) -> String {
+    let escaped = prefix.trim()
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    format!("{}%", escaped)
+}
+
+/// Students the user may see, narrowed down by the query's filters.
+/// 
+/// Neither sorted nor paged, so it can be counted too.
+pub fn filtered(query: &ListQuery, user: &CurrentUser) -> students::BoxedQuery<'static, Pg> {
+    use crate::schema::students::dsl::*;
+    use crate::schema::guardians;
+    let mut filtered = students.into_boxed();
+    match user.role {
+        Role::Admin | Role::Teacher => {}
+        Role::Parent => {
+            let children = guardians::table
+                .filter(guardians::user_id.eq(user.id))
+                .select(guardians::student_id);
+            filtered = filtered.filter(id.eq_any(children));
+        }
+        Role::Student => {
+            filtered = filtered.filter(id.nullable().eq(user.student_id));
+        }
+    }
+
+    if query.deleted {
+        filtered = filtered.filter(deleted_at.is_not_null());
+    } else {
+        filtered = filtered.filter(live());
+    }
+    if query.graduated {
+        filtered = filtered.filter(graduated_in.is_not_null());
+    } else {
+        filtered = filtered.filter(graduated_in.is_null());
+    }
+    if let Some(class) = query.class_id {
+        filtered = filtered.filter(class_id.eq(class));
+    }
+    if let Some(ref prefix) = query.first_name {
+        filtered = filtered.filter(first_name.ilike(prefix_pattern(prefix)));
+    }
+    if let Some(ref prefix) = query.last_name {
+        filtered = filtered.filter(last_name.ilike(prefix_pattern(prefix)));
+    }
+    if let Some(ref number) = query.phone_number {
+        filtered = filtered.filter(phone_number.eq(number.clone()));
+    }
+    filtered
+}
+
+/// Sorts by the query's column, ties are broken by id.
+pub fn sorted(query: &ListQuery, unsorted: students::BoxedQuery<'static, Pg>) -> students::BoxedQuery<'static, Pg> {
+    use crate::schema::students::dsl::*;
+    match (query.sort, query.order) {
+        (SortColumn::Id, SortOrder::Asc) => unsorted.order(id.asc()),
+        (SortColumn::Id, SortOrder::Desc) => unsorted.order(id.desc()),
+        (SortColumn::FirstName, SortOrder::Asc) => unsorted.order((first_name.asc(), id.asc())),
+        (SortColumn::FirstName, SortOrder::Desc) => unsorted.order((first_name.desc(), id.asc())),
+        (SortColumn::LastName, SortOrder::Asc) => unsorted.order((last_name.asc(), id.asc())),
+        (SortColumn::LastName, SortOrder::Desc) => unsorted.order((last_name.desc(), id.asc())),
+        (SortColumn::PhoneNumber, SortOrder::Asc) => unsorted.order((phone_number.asc(), id.asc())),
+        (SortColumn::PhoneNumber, SortOrder::Desc) => unsorted.order((phone_number.desc(), id.asc())),
+        (SortColumn::ClassId, SortOrder::Asc) => unsorted.order((class_id.asc(), id.asc())),
+        (SortColumn::ClassId, SortOrder::Desc) => unsorted.order((class_id.desc(), id.asc())),
+    }
+}
+
+pub struct ReadRequest {
+    pub query: ListQuery,
+    pub user: CurrentUser,
+}
 
 impl Message for ReadRequest {
-    type Result = Result<Vec<Student>, diesel::result::Error>;
+    type Result = Result<StudentsPage, ApiError>;
 }
 
 impl Handler<ReadRequest> for Database {
-    type Result = Result<Vec<Student>, diesel::result::Error>;
+    type Result = Result<StudentsPage, ApiError>;
+
+    fn handle(&mut self, msg: ReadRequest, _: &mut Self::Context) -> Self::Result {
+        if msg.query.deleted {
+            require_role(&msg.user, &[Role::Admin])?;
+        }
+        let conn = self.conn()?;
+        let limit = msg.query.limit.max(1).min(MAX_LIMIT);
+        let offset = msg.query.offset.max(0);
 
-    fn handle(&mut self, _msg: ReadRequest, _: &mut Self::Context) -> Self::Result {
-        use crate::schema::students::dsl::*;
-        let conn = self.0.get().unwrap();
-        students.order(id).load::<Student>(&conn)
+        let total = filtered(&msg.query, &msg.user)
+            .count()
+            .get_result::<i64>(&conn)?;
+        let page = sorted(&msg.query, filtered(&msg.query, &msg.user))
+            .limit(limit)
+            .offset(offset)
+            .load::<Student>(&conn)?;
+
+        Ok(StudentsPage {
+            total,
+            limit,
+            offset,
+            next: None,
+            previous: None,
+            students: page,
+        })
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,126 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use super::*;
+use super::imports::*;
+
+use futures::future;
+use crate::classes::Class;
+use crate::grades::Grade;
+use crate::guardians::{self, GuardianContact};
+
+/// Reads the student with the id from the path.
+/// 
+/// `?include=class,grades,guardians` embeds related data.
+/// Same access rules as the list of students.
+pub fn read_one((request, user, id, query): (HttpRequest<State>, CurrentUser, Path<i32>, Query<IncludeQuery>)) 
+    -> Box<Future<Item = Json<StudentDetails>, Error = actix_web::Error>> 
+{
+    debug!("Request to read student with id of {}.", id.as_ref());
+    let include = match Include::parse(query.include.as_ref().map_or("", String::as_str)) {
+        Ok(include) => include,
+        Err(message) => {
+            warn!("{}", &message);
+            return Box::new(future::err::<Json<StudentDetails>, actix_web::Error>(
+                ApiError::BadRequest(message).into()
+            ));
+        }
+    };
+    request.state().db
+        .send(ReadOneRequest{id: id.into_inner(), include, user})
+        .from_err()
+        .and_then(|res| res.map(Json).map_err(actix_web::Error::from))
+        .responder()
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct IncludeQuery {
+    /// Comma separated
+    pub include: Option<String>
+}
+
+/// What to embed in the response.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Include {
+    pub class: bool,
+    pub grades: bool,
+    pub guardians: bool,
+}
+
+impl Include {
+    fn parse(include: &str) -> Result<Include, String> {
+        let mut parsed = Include::default();
+        for name in include.split(',').map(str::trim).filter(|name| !name.is_empty()) {
+            match name {
+                "class" => parsed.class = true,
+                "grades" => parsed.grades = true,
+                "guardians" => parsed.guardians = true,
+                unknown => return Err(format!(
+                    "Can't include `{}`, only `class`, `grades` and `guardians`.", unknown
+                )),
+            }
+        }
+        Ok(parsed)
+    }
+}
+
+/// The student with whatever was asked for in `include`.
+#[derive(Serialize, Debug)]
+pub struct StudentDetails {
+    #[serde(flatten)]
+    pub student: Student,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub class: Option<Class>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub grades: Option<Vec<Grade>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub guardians: Option<Vec<GuardianContact>>
+}
+
+pub struct ReadOneRequest {
+    pub id: i32,
+    pub include: Include,
+    pub user: CurrentUser,
+}
+
+impl Message for ReadOneRequest {
+    type Result = Result<StudentDetails, ApiError>;
+}
+
+impl Handler<ReadOneRequest> for Database {
+    type Result = Result<StudentDetails, ApiError>;
+
+    fn handle(&mut self, msg: ReadOneRequest, _: &mut Self::Context) -> Self::Result {
+        use crate::schema::{classes, grades};
+        let conn = self.conn()?;
+        require_student(&conn, &msg.user, msg.id)?;
+
+        let student = students::table
+            .find(msg.id)
+            .filter(live())
+            .first::<Student>(&conn)
+            .optional()?
+            .ok_or_else(|| ApiError::NotFound(format!("Student with id of {} not found.", msg.id)))?;
+
+        let class = if msg.include.class {
+            Some(classes::table.find(student.class_id).first::<Class>(&conn)?)
+        } else {
+            None
+        };
+        let grades = if msg.include.grades {
+            Some(grades::table
+                .filter(grades::student_id.eq(student.id))
+                .order((grades::subject_id, grades::date, grades::id))
+                .load::<Grade>(&conn)?)
+        } else {
+            None
+        };
+        let guardians = if msg.include.guardians {
+            Some(guardians::contacts(&conn, student.id)?)
+        } else {
+            None
+        };
+
+        Ok(StudentDetails { student, class, grades, guardians })
+    }
+}
@@ -0,0 +1,59 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use super::*;
+use super::imports::*;
+
+/// Brings back the deleted student with the id from the path, with their grades and attendance.
+///
+/// Admins only, 404 if there's no such deleted student.
+pub fn restore((request, user, id): (HttpRequest<State>, CurrentUser, Path<i32>))
+    -> Box<Future<Item = Json<RestoreResponse>, Error = actix_web::Error>>
+{
+    debug!("Request to restore student with id of {}.", id.as_ref());
+    let id = id.into_inner();
+    request.state().db
+        .send(RestoreRequest{id, user})
+        .from_err()
+        .and_then(move |res| {
+            res.map(|student| {
+                info!("Student with id of {} successfully restored.", id);
+                Json(RestoreResponse {
+                    message: format!("Restored student with id: {:?}.", id),
+                    student: Some(student)
+                })
+            }).map_err(actix_web::Error::from)
+        })
+        .responder()
+}
+
+#[derive(Serialize)]
+pub struct RestoreResponse {
+    pub message: String,
+    pub student: Option<Student>
+}
+
+pub struct RestoreRequest {
+    pub id: i32,
+    pub user: CurrentUser,
+}
+
+impl Message for RestoreRequest {
+    type Result = Result<Student, ApiError>;
+}
+
+impl Handler<RestoreRequest> for Database {
+    type Result = Result<Student, ApiError>;
+
+    fn handle(&mut self, msg: RestoreRequest, _: &mut Self::Context) -> Self::Result {
+        use crate::schema::students::dsl::*;
+        require_role(&msg.user, &[Role::Admin])?;
+        let conn = self.conn()?;
+        let deleted = students.filter(id.eq(msg.id)).filter(deleted_at.is_not_null());
+        diesel::update(deleted)
+            .set(deleted_at.eq(None::<NaiveDateTime>))
+            .get_result::<Student>(&conn)
+            .optional()?
+            .ok_or_else(|| ApiError::NotFound(format!("Deleted student with id of {} not found.", msg.id)))
+    }
+}
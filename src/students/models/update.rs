@@ -4,64 +4,127 @@
 use super::*;
 use super::imports::*;
 
-pub fn update((request, id, updated_student): (HttpRequest<State>, Path<i32>, Json<UpdateRequest>)) 
+/// This is the update handler
+/// 
+/// Admins may edit every student, teachers only the ones in their classes.
+/// 404 if there's no such student.
+pub fn update((request, user, id, updated_student): (HttpRequest<State>, CurrentUser, Path<i32>, Json<UpdateRequest>)) 
     -> Box<Future<Item = HttpResponse, Error = actix_web::Error>> 
 {
     request.state().db
         .send(UpdateStudent {
             id: id.clone(),
+            user,
             fields: updated_student.into_inner()
         })
         .from_err()
         .and_then(move |updated_student| {
-            if let Ok(student) = updated_student {
-                Ok(HttpResponse::Ok().json(
+            match updated_student {
+                Ok(student) => Ok(HttpResponse::Ok().json(
                     UpdateResponse{ 
                         message: format!("Updated student with id: {:?}.", id),
                         student: Some(student),
                     }
-                ))
-            } else {
-                Ok(HttpResponse::BadRequest().json(
-                    UpdateResponse { 
-                        message: format!("Something went wrong. User with id of {} may not exist.", id),
-                        student: None,
-                    }
-                ))
+                )),
+                Err(e) => Err(actix_web::Error::from(e))
             }
         }).responder()
 }
 
+#[derive(Serialize, Deserialize)]
+pub struct UpdateRequest {
+    pub first_name: Option<String>,
+    pub last_name: Option<String>,
+    pub class_id: Option<i32>,
+    pub phone_number: Option<RawPhoneNumber>
+}
+
 // use of undeclared type or module `student_fieldss`
 /// 
 /// https://www.reddit.com/r/rust/comments/9qeldl/diesel_orm_asking_for_modules_that_do_not_exist/
-#[derive(Serialize, Deserialize, AsChangeset)]
+#[derive(AsChangeset)]
 #[table_name="students"]
-pub struct UpdateRequest {
-    pub first_name: Option<String>,
-    pub last_name: Option<String>,
-    pub class: Option<String>,
-    pub phone_number: Option<i32>
+struct StudentChanges {
+    first_name: Option<String>,
+    last_name: Option<String>,
+    class_id: Option<i32>,
+    phone_number: Option<PhoneNumber>
+}
+
+impl UpdateRequest {
+    /// Names without surrounding whitespace and the parsed phone number, once they're valid.
+    fn changes(&self) -> Result<StudentChanges, ApiError> {
+        Ok(StudentChanges {
+            first_name: self.first_name.as_ref().map(|name| name.trim().to_string()),
+            last_name: self.last_name.as_ref().map(|name| name.trim().to_string()),
+            class_id: self.class_id,
+            phone_number: match self.phone_number {
+                Some(ref number) => Some(number.parse().map_err(ApiError::BadRequest)?),
+                None => None,
+            },
+        })
+    }
+}
+
+/// Only the fields that are being changed are checked.
+impl Validate for UpdateRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut validator = Validator::new();
+        if let Some(ref name) = self.first_name {
+            validator.name("first_name", name);
+        }
+        if let Some(ref name) = self.last_name {
+            validator.name("last_name", name);
+        }
+        if let Some(Err(message)) = self.phone_number.as_ref().map(RawPhoneNumber::parse) {
+            validator.check("phone_number", false, &message);
+        }
+        validator.finish()
+    }
 }
 
-#[derive(Serialize, Deserialize)]
 pub struct UpdateStudent {
     pub id: i32,
+    pub user: CurrentUser,
     pub fields: UpdateRequest,
 }
 
 impl Message for UpdateStudent {
-    type Result = Result<Student, diesel::result::Error>;
+    type Result = Result<Student, ApiError>;
 }
 
 impl Handler<UpdateStudent> for Database {
-    type Result = Result<Student, diesel::result::Error>;
+    type Result = Result<Student, ApiError>;
 
     fn handle(&mut self, msg: UpdateStudent, _: &mut Self::Context) -> Self::Result {
-        use crate::schema::students::dsl::*;
-        let conn = self.0.get().unwrap();
-        diesel::update(students.filter(id.eq(msg.id))).set(msg.fields).get_result::<Student>(&conn)
+        let conn = self.conn()?;
+        update_student(&conn, &msg.user, msg.id, msg.fields)
+    }
+}
+
+/// Checks and edits one student, also used by the batch endpoint.
+///
+/// Teachers need to teach both the student's current class and the one they're moving to.
+pub fn update_student(conn: &PgConnection, user: &CurrentUser, student_id: i32, fields: UpdateRequest) -> Result<Student, ApiError> {
+    use crate::schema::students::dsl::*;
+    fields.validate()?;
+    require_changes(
+        fields.first_name.is_some() || fields.last_name.is_some()
+            || fields.class_id.is_some() || fields.phone_number.is_some()
+    )?;
+    let changes = fields.changes()?;
+    let current_class = students
+        .find(student_id)
+        .filter(live())
+        .select(class_id)
+        .first::<i32>(conn)
+        .optional()?
+        .ok_or_else(|| ApiError::NotFound(format!("Student with id of {} not found.", student_id)))?;
+    require_class(conn, user, current_class)?;
+    if let Some(new_class) = changes.class_id {
+        require_class(conn, user, new_class)?;
     }
+    Ok(diesel::update(students.find(student_id)).set(&changes).get_result::<Student>(conn)?)
 }
 
 #[derive(Deserialize, Serialize)]
@@ -0,0 +1,26 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use crate::schema::subjects;
+use crate::database::Database;
+use actix_web::actix::{Message, Handler};
+use diesel;
+
+#[allow(unused_imports)] // Throws errors without this import, but throws warning with it :/
+use diesel::prelude::*;
+
+mod imports;
+
+#[derive(Queryable, Serialize, Deserialize, Debug)]
+pub struct Subject {
+    pub id: i32,
+    pub name: String
+}
+
+/* Create */
+mod create;
+pub use create::*;
+
+/* Read */
+mod read;
+pub use read::*;
@@ -0,0 +1,72 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use super::*;
+use super::imports::*;
+
+use diesel::result::{DatabaseErrorKind, Error::DatabaseError};
+
+/// This is the create handler.
+/// 
+/// Admins only.
+pub fn create((request, user, new_subject): (HttpRequest<State>, CurrentUser, Json<CreateRequest>)) 
+    -> Box<Future<Item = Json<CreateResponse>, Error = actix_web::Error>> 
+{
+    debug!("Request to create subject: {:?}", &new_subject);
+    request.state().db
+        .send(CreateSubject {
+            user,
+            fields: new_subject.into_inner()
+        })
+        .from_err()
+        .and_then(|res| {
+            res.map(|subject| {
+                info!("Successfully added subject");
+                Json(CreateResponse {
+                    message: "Success!".to_string(),
+                    subject: Some(subject)
+                })
+            }).map_err(actix_web::Error::from)
+        })
+        .responder()
+}
+
+/// id should be set automatically
+#[derive(Insertable, Deserialize, Serialize, Debug)]
+#[table_name="subjects"]
+pub struct CreateRequest {
+    name: String
+}
+
+#[derive(Serialize)]
+pub struct CreateResponse {
+    pub message: String,
+    pub subject: Option<Subject>
+}
+
+pub struct CreateSubject {
+    pub user: CurrentUser,
+    pub fields: CreateRequest,
+}
+
+impl Message for CreateSubject {
+    type Result = Result<Subject, ApiError>;
+}
+
+impl Handler<CreateSubject> for Database {
+    type Result = Result<Subject, ApiError>;
+
+    fn handle(&mut self, msg: CreateSubject, _: &mut Self::Context) -> Self::Result {
+        require_role(&msg.user, &[Role::Admin])?;
+        let conn = self.conn()?;
+        diesel::insert_into(subjects::table)
+            .values(&msg.fields)
+            .get_result::<Subject>(&conn)
+            .map_err(|err| match err {
+                DatabaseError(DatabaseErrorKind::UniqueViolation, _) => ApiError::Conflict(
+                    format!("Subject `{}` already exists.", msg.fields.name)
+                ),
+                err => ApiError::from(err)
+            })
+    }
+}
@@ -0,0 +1,33 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use super::*;
+use super::imports::*;
+
+/// Every logged in user may list subjects.
+pub fn read((request, _user): (HttpRequest<State>, CurrentUser)) 
+    -> Box<Future<Item = Json<Vec<Subject>>, Error = actix_web::Error>> 
+{
+    debug!("Request to read all subjects.");
+    request.state().db
+        .send(ReadRequest{})
+        .from_err()
+        .and_then(|res| res.map(Json).map_err(actix_web::Error::from))
+        .responder()
+}
+
+pub struct ReadRequest{}
+
+impl Message for ReadRequest {
+    type Result = Result<Vec<Subject>, ApiError>;
+}
+
+impl Handler<ReadRequest> for Database {
+    type Result = Result<Vec<Subject>, ApiError>;
+
+    fn handle(&mut self, _msg: ReadRequest, _: &mut Self::Context) -> Self::Result {
+        use crate::schema::subjects::dsl::*;
+        let conn = self.conn()?;
+        Ok(subjects.order(name).load::<Subject>(&conn)?)
+    }
+}
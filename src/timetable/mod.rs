@@ -0,0 +1,19 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+extern crate serde_derive;
+extern crate actix_web;
+
+mod models;
+
+pub use models::{
+    Lesson,
+    planned_lesson,
+    create,
+    update,
+    delete,
+    class_week,
+    teacher_week,
+    class_calendar,
+    teacher_calendar
+};
@@ -0,0 +1,169 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use crate::schema::timetable;
+use crate::database::Database;
+use crate::error::ApiError;
+use actix_web::actix::{Message, Handler};
+use chrono::{Datelike, NaiveDate, NaiveTime};
+use crate::classes::school_year_of;
+use diesel;
+use diesel::pg::PgConnection;
+use diesel::result::{DatabaseErrorKind, Error::DatabaseError};
+
+#[allow(unused_imports)] // Throws errors without this import, but throws warning with it :/
+use diesel::prelude::*;
+
+mod imports;
+
+/// A lesson in the weekly plan of a class.
+///
+/// `weekday` is ISO, 1 is Monday. The school year is copied from the class.
+#[derive(Queryable, Serialize, Deserialize, Debug)]
+pub struct TimetableEntry {
+    pub id: i32,
+    pub class_id: i32,
+    pub school_year: String,
+    pub weekday: i16,
+    pub lesson_number: i16,
+    pub subject_id: i32,
+    pub teacher_id: i32,
+    pub room: Option<String>
+}
+
+/// A lesson with the names and hours filled in, as shown in a week.
+#[derive(Queryable, Serialize, Deserialize, Debug)]
+pub struct Lesson {
+    pub id: i32,
+    pub class_id: i32,
+    pub class_name: String,
+    pub school_year: String,
+    pub weekday: i16,
+    pub lesson_number: i16,
+    pub starts_at: NaiveTime,
+    pub ends_at: NaiveTime,
+    pub subject_id: i32,
+    pub subject_name: String,
+    pub teacher_id: i32,
+    pub teacher_login: String,
+    pub room: Option<String>
+}
+
+/// Whose week to load.
+pub enum Week {
+    Class(i32),
+    Teacher { id: i32, school_year: String },
+}
+
+/// Lessons ordered by weekday and lesson number.
+pub fn lessons(conn: &PgConnection, week: &Week) -> Result<Vec<Lesson>, ApiError> {
+    use crate::schema::{classes, lesson_times, subjects, users};
+    let mut query = timetable::table
+        .inner_join(classes::table)
+        .inner_join(lesson_times::table)
+        .inner_join(subjects::table)
+        .inner_join(users::table)
+        .select((
+            timetable::id,
+            timetable::class_id,
+            classes::name,
+            timetable::school_year,
+            timetable::weekday,
+            timetable::lesson_number,
+            lesson_times::starts_at,
+            lesson_times::ends_at,
+            timetable::subject_id,
+            subjects::name,
+            timetable::teacher_id,
+            users::login,
+            timetable::room,
+        ))
+        .order((timetable::weekday, timetable::lesson_number, classes::name))
+        .into_boxed();
+    match *week {
+        Week::Class(class) => {
+            query = query.filter(timetable::class_id.eq(class));
+        }
+        Week::Teacher { id, ref school_year } => {
+            query = query
+                .filter(timetable::teacher_id.eq(id))
+                .filter(timetable::school_year.eq(school_year.clone()));
+        }
+    }
+    Ok(query.load::<Lesson>(conn)?)
+}
+
+/// Teachers may only be planned for what they're assigned to teach.
+fn require_taught(conn: &PgConnection, teacher: i32, subject: i32, class: i32, school_year: &str) -> Result<(), ApiError> {
+    use crate::schema::teaching_assignments;
+    let assigned = diesel::select(diesel::dsl::exists(
+        teaching_assignments::table
+            .filter(teaching_assignments::teacher_id.eq(teacher))
+            .filter(teaching_assignments::subject_id.eq(subject))
+            .filter(teaching_assignments::class_id.eq(class))
+            .filter(teaching_assignments::school_year.eq(school_year))
+    )).get_result::<bool>(conn)?;
+    if assigned {
+        Ok(())
+    } else {
+        Err(ApiError::BadRequest(format!(
+            "Teacher with id of {} isn't assigned to teach subject with id of {} in class with id of {} in {}.",
+            teacher, subject, class, school_year
+        )))
+    }
+}
+
+/// The class's lesson in the plan on the date's weekday and lesson number, in the school year
+/// of the date. `None` for lessons outside of the plan, e.g. extra ones.
+pub fn planned_lesson(conn: &PgConnection, class: i32, date: NaiveDate, lesson_number: i16) -> Result<Option<i32>, ApiError> {
+    Ok(timetable::table
+        .filter(timetable::class_id.eq(class))
+        .filter(timetable::school_year.eq(school_year_of(date)))
+        .filter(timetable::weekday.eq(date.weekday().number_from_monday() as i16))
+        .filter(timetable::lesson_number.eq(lesson_number))
+        .select(timetable::id)
+        .first::<i32>(conn)
+        .optional()?)
+}
+
+/// Turns the double-booking constraint violations into 409 Conflicts.
+fn slot_taken(err: diesel::result::Error) -> ApiError {
+    match err {
+        DatabaseError(DatabaseErrorKind::UniqueViolation, info) => {
+            let message = match info.constraint_name() {
+                Some("timetable_class_slot_key") => "The class already has a lesson then.",
+                Some("timetable_teacher_slot_key") => "The teacher already teaches another lesson then.",
+                Some("timetable_room_slot_key") => "The room is already taken then.",
+                _ => "The lesson collides with another one.",
+            };
+            ApiError::Conflict(message.to_string())
+        }
+        DatabaseError(DatabaseErrorKind::ForeignKeyViolation, info) => ApiError::BadRequest(
+            match info.constraint_name() {
+                Some("timetable_lesson_number_fkey") => "There's no such lesson number.".to_string(),
+                _ => "The subject or teacher doesn't exist.".to_string(),
+            }
+        ),
+        err => ApiError::from(err)
+    }
+}
+
+/* Create */
+mod create;
+pub use create::*;
+
+/* Update */
+mod update;
+pub use update::*;
+
+/* Delete */
+mod delete;
+pub use delete::*;
+
+/* Week */
+mod week;
+pub use week::*;
+
+/* iCalendar */
+mod calendar;
+pub use calendar::*;
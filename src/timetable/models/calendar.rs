@@ -0,0 +1,108 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use super::*;
+use super::imports::*;
+
+use chrono::{Datelike, Duration, NaiveDate, Utc};
+use crate::classes::school_year_bounds;
+
+/// The week of the class as an iCalendar feed, repeating until the end of the school year.
+///
+/// Calendar apps subscribe by URL, so a calendar token may be sent as `?token=`, see `/me/calendar-token`.
+pub fn class_calendar((request, _user, id): (HttpRequest<State>, CurrentUser, Path<i32>))
+    -> Box<Future<Item = HttpResponse, Error = actix_web::Error>>
+{
+    debug!("Request to read calendar of class with id of {}.", id.as_ref());
+    request.state().db
+        .send(WeekRequest::Class(id.into_inner()))
+        .from_err()
+        .and_then(|res| res.and_then(|week| calendar(&week)).map(ics).map_err(actix_web::Error::from))
+        .responder()
+}
+
+/// The week of the teacher as an iCalendar feed, see `class_calendar` and `teacher_week`.
+pub fn teacher_calendar((request, _user, id, query): (HttpRequest<State>, CurrentUser, Path<i32>, Query<WeekQuery>))
+    -> Box<Future<Item = HttpResponse, Error = actix_web::Error>>
+{
+    debug!("Request to read calendar of teacher with id of {}.", id.as_ref());
+    request.state().db
+        .send(WeekRequest::Teacher(id.into_inner(), query.into_inner()))
+        .from_err()
+        .and_then(|res| res.and_then(|week| calendar(&week)).map(ics).map_err(actix_web::Error::from))
+        .responder()
+}
+
+fn ics(body: String) -> HttpResponse {
+    HttpResponse::Ok()
+        .content_type("text/calendar; charset=utf-8")
+        .body(body)
+}
+
+/// One weekly repeating event per lesson, from the 1st of September to the 30th of June.
+///
+/// Times are floating, i.e. in whatever timezone the phone is in.
+fn calendar(week: &WeekResponse) -> Result<String, ApiError> {
+    let (first_day, last_day) = school_year_bounds(&week.school_year)
+        .ok_or_else(|| ApiError::BadRequest(format!("`{}` is not a school year.", week.school_year)))?;
+    let stamp = Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//dziennik//timetable//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        format!("X-WR-CALNAME:{}", escape(&format!("{} {}", week.name, week.school_year))),
+    ];
+    for lesson in &week.lessons {
+        let day = first_weekday(first_day, lesson.weekday);
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!("UID:timetable-{}@dziennik", lesson.id));
+        lines.push(format!("DTSTAMP:{}", stamp));
+        lines.push(format!("DTSTART:{}", day.and_time(lesson.starts_at).format("%Y%m%dT%H%M%S")));
+        lines.push(format!("DTEND:{}", day.and_time(lesson.ends_at).format("%Y%m%dT%H%M%S")));
+        lines.push(format!("RRULE:FREQ=WEEKLY;UNTIL={}T235959", last_day.format("%Y%m%d")));
+        lines.push(format!("SUMMARY:{}", escape(&lesson.subject_name)));
+        if let Some(ref room) = lesson.room {
+            lines.push(format!("LOCATION:{}", escape(room)));
+        }
+        lines.push(format!(
+            "DESCRIPTION:{}",
+            escape(&format!("Class {}, lesson {}, teacher {}", lesson.class_name, lesson.lesson_number, lesson.teacher_login))
+        ));
+        lines.push("END:VEVENT".to_string());
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    Ok(lines.iter().map(|line| fold(line)).collect::<Vec<_>>().concat())
+}
+
+/// The first day on or after `from` that falls on the ISO weekday.
+fn first_weekday(from: NaiveDate, weekday: i16) -> NaiveDate {
+    let days = (i64::from(weekday) - i64::from(from.weekday().number_from_monday()) + 7) % 7;
+    from + Duration::days(days)
+}
+
+/// Escapes text values, RFC 5545 3.3.11.
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// Ends the line with CRLF, breaking it so no line is longer than 75 octets, RFC 5545 3.1.
+fn fold(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len() + 2);
+    let mut length = 0;
+    for c in line.chars() {
+        if length + c.len_utf8() > 75 {
+            folded.push_str("\r\n ");
+            length = 1;
+        }
+        folded.push(c);
+        length += c.len_utf8();
+    }
+    folded.push_str("\r\n");
+    folded
+}
@@ -0,0 +1,116 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use super::*;
+use super::imports::*;
+
+/// This is the create handler.
+///
+/// Admins only.
+pub fn create((request, user, new_lesson): (HttpRequest<State>, CurrentUser, Json<CreateRequest>))
+    -> Box<Future<Item = Json<CreateResponse>, Error = actix_web::Error>>
+{
+    debug!("Request to add lesson to timetable: {:?}", &new_lesson);
+    request.state().db
+        .send(CreateLesson {
+            user,
+            fields: new_lesson.into_inner()
+        })
+        .from_err()
+        .and_then(|res| {
+            res.map(|lesson| {
+                info!("Successfully added lesson to timetable");
+                Json(CreateResponse {
+                    message: "Success!".to_string(),
+                    lesson: Some(lesson)
+                })
+            }).map_err(actix_web::Error::from)
+        })
+        .responder()
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct CreateRequest {
+    pub class_id: i32,
+    pub weekday: i16,
+    pub lesson_number: i16,
+    pub subject_id: i32,
+    pub teacher_id: i32,
+    pub room: Option<String>
+}
+
+impl Validate for CreateRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut validator = Validator::new();
+        validator.range("weekday", self.weekday.into(), 1, 7);
+        if let Some(ref room) = self.room {
+            validator.text("room", room, MAX_ROOM_LENGTH);
+        }
+        validator.finish()
+    }
+}
+
+pub const MAX_ROOM_LENGTH: usize = 20;
+
+/// id should be set automatically
+#[derive(Insertable)]
+#[table_name="timetable"]
+struct NewLesson {
+    class_id: i32,
+    school_year: String,
+    weekday: i16,
+    lesson_number: i16,
+    subject_id: i32,
+    teacher_id: i32,
+    room: Option<String>
+}
+
+#[derive(Serialize)]
+pub struct CreateResponse {
+    pub message: String,
+    pub lesson: Option<TimetableEntry>
+}
+
+pub struct CreateLesson {
+    pub user: CurrentUser,
+    pub fields: CreateRequest,
+}
+
+impl Message for CreateLesson {
+    type Result = Result<TimetableEntry, ApiError>;
+}
+
+impl Handler<CreateLesson> for Database {
+    type Result = Result<TimetableEntry, ApiError>;
+
+    /// The teacher has to be assigned to teach the subject in the class.
+    fn handle(&mut self, msg: CreateLesson, _: &mut Self::Context) -> Self::Result {
+        use crate::schema::classes;
+        require_role(&msg.user, &[Role::Admin])?;
+        msg.fields.validate()?;
+        let conn = self.conn()?;
+        let fields = msg.fields;
+
+        let class_year = classes::table
+            .find(fields.class_id)
+            .select(classes::school_year)
+            .first::<String>(&conn)
+            .optional()?
+            .ok_or_else(|| ApiError::NotFound(format!("Class with id of {} not found.", fields.class_id)))?;
+        require_taught(&conn, fields.teacher_id, fields.subject_id, fields.class_id, &class_year)?;
+
+        let new_lesson = NewLesson {
+            class_id: fields.class_id,
+            school_year: class_year,
+            weekday: fields.weekday,
+            lesson_number: fields.lesson_number,
+            subject_id: fields.subject_id,
+            teacher_id: fields.teacher_id,
+            room: fields.room.map(|room| room.trim().to_string()),
+        };
+        diesel::insert_into(timetable::table)
+            .values(&new_lesson)
+            .get_result::<TimetableEntry>(&conn)
+            .map_err(slot_taken)
+    }
+}
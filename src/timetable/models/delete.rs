@@ -0,0 +1,54 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use super::*;
+use super::imports::*;
+
+/// This is the delete handler
+/// 
+/// Admins only, attendance already taken during the lesson stays.
+pub fn delete((request, user, id): (HttpRequest<State>, CurrentUser, Path<i32>)) 
+    -> Box<Future<Item = HttpResponse, Error = actix_web::Error>> 
+{
+    debug!("Request to delete lesson with id of {}.", id.as_ref());
+    
+    request.state().db
+        .send(DeleteRequest{id: id.clone(), user})
+        .from_err()
+        .and_then(move |res| {
+            res.map(|_| {
+                info!("Lesson with id of {} successfully deleted.", id);
+                HttpResponse::Ok()
+                    .json(DeleteResponse {
+                        message: format!("Deleted lesson with id: {:?}.", id)
+                    })
+            }).map_err(actix_web::Error::from)
+        }).responder()
+}
+
+pub struct DeleteRequest {
+    pub id: i32,
+    pub user: CurrentUser,
+}
+
+impl Message for DeleteRequest {
+    type Result = Result<usize, ApiError>;
+}
+
+impl Handler<DeleteRequest> for Database {
+    type Result = Result<usize, ApiError>;
+
+    fn handle(&mut self, msg: DeleteRequest, _: &mut Self::Context) -> Self::Result {
+        require_role(&msg.user, &[Role::Admin])?;
+        let conn = self.conn()?;
+        match diesel::delete(timetable::table.find(msg.id)).execute(&conn)? {
+            0 => Err(ApiError::NotFound(format!("Lesson with id of {} not found.", msg.id))),
+            deleted => Ok(deleted)
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct DeleteResponse {
+    pub message: String,
+}
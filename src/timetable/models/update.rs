@@ -0,0 +1,119 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use super::*;
+use super::imports::*;
+
+/// This is the update handler
+///
+/// Admins only, 404 if there's no such lesson.
+pub fn update((request, user, id, updated_lesson): (HttpRequest<State>, CurrentUser, Path<i32>, Json<UpdateRequest>))
+    -> Box<Future<Item = HttpResponse, Error = actix_web::Error>>
+{
+    request.state().db
+        .send(UpdateLesson {
+            id: id.clone(),
+            user,
+            fields: updated_lesson.into_inner()
+        })
+        .from_err()
+        .and_then(move |res| {
+            res.map(|lesson| {
+                HttpResponse::Ok().json(UpdateResponse {
+                    message: format!("Updated lesson with id: {:?}.", id),
+                    lesson: Some(lesson)
+                })
+            }).map_err(actix_web::Error::from)
+        }).responder()
+}
+
+/// `"room": null` removes the room, leaving it out keeps it as is.
+/// A lesson can't be moved to another class, delete it and create a new one instead.
+#[derive(Serialize, Deserialize, AsChangeset)]
+#[table_name="timetable"]
+pub struct UpdateRequest {
+    pub weekday: Option<i16>,
+    pub lesson_number: Option<i16>,
+    pub subject_id: Option<i32>,
+    pub teacher_id: Option<i32>,
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    pub room: Option<Option<String>>
+}
+
+impl UpdateRequest {
+    /// Room without surrounding whitespace.
+    fn trimmed(self) -> Self {
+        UpdateRequest {
+            room: self.room.map(|room| room.map(|room| room.trim().to_string())),
+            ..self
+        }
+    }
+}
+
+/// Only the fields that are being changed are checked.
+impl Validate for UpdateRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut validator = Validator::new();
+        if let Some(weekday) = self.weekday {
+            validator.range("weekday", weekday.into(), 1, 7);
+        }
+        if let Some(Some(ref room)) = self.room {
+            validator.text("room", room, MAX_ROOM_LENGTH);
+        }
+        validator.finish()
+    }
+}
+
+pub struct UpdateLesson {
+    pub id: i32,
+    pub user: CurrentUser,
+    pub fields: UpdateRequest,
+}
+
+impl Message for UpdateLesson {
+    type Result = Result<TimetableEntry, ApiError>;
+}
+
+impl Handler<UpdateLesson> for Database {
+    type Result = Result<TimetableEntry, ApiError>;
+
+    /// The teacher still has to be assigned to teach the subject after the change.
+    fn handle(&mut self, msg: UpdateLesson, _: &mut Self::Context) -> Self::Result {
+        require_role(&msg.user, &[Role::Admin])?;
+        msg.fields.validate()?;
+        require_changes(
+            msg.fields.weekday.is_some() || msg.fields.lesson_number.is_some() || msg.fields.subject_id.is_some()
+                || msg.fields.teacher_id.is_some() || msg.fields.room.is_some()
+        )?;
+        let lesson_id = msg.id;
+        let fields = msg.fields.trimmed();
+        let conn = self.conn()?;
+
+        conn.transaction(|| {
+            let current = timetable::table
+                .find(lesson_id)
+                .first::<TimetableEntry>(&conn)
+                .optional()?
+                .ok_or_else(|| ApiError::NotFound(format!("Lesson with id of {} not found.", lesson_id)))?;
+            if fields.teacher_id.is_some() || fields.subject_id.is_some() {
+                require_taught(
+                    &conn,
+                    fields.teacher_id.unwrap_or(current.teacher_id),
+                    fields.subject_id.unwrap_or(current.subject_id),
+                    current.class_id,
+                    &current.school_year
+                )?;
+            }
+            diesel::update(timetable::table.find(lesson_id))
+                .set(&fields)
+                .get_result::<TimetableEntry>(&conn)
+                .map_err(slot_taken)
+        })
+    }
+}
+
+#[derive(Serialize)]
+pub struct UpdateResponse {
+    pub message: String,
+    pub lesson: Option<TimetableEntry>
+}
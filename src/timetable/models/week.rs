@@ -0,0 +1,101 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use super::*;
+use super::imports::*;
+
+use crate::classes::school_year_of;
+
+/// The week of the class with the id from the path, any logged in user can see it.
+pub fn class_week((request, _user, id): (HttpRequest<State>, CurrentUser, Path<i32>))
+    -> Box<Future<Item = Json<WeekResponse>, Error = actix_web::Error>>
+{
+    debug!("Request to read timetable of class with id of {}.", id.as_ref());
+    request.state().db
+        .send(WeekRequest::Class(id.into_inner()))
+        .from_err()
+        .and_then(|res| res.map(Json).map_err(actix_web::Error::from))
+        .responder()
+}
+
+/// The week of the teacher with the id from the path, e.g. `?school_year=2018/2019`.
+/// Defaults to the current school year, any logged in user can see it.
+pub fn teacher_week((request, _user, id, query): (HttpRequest<State>, CurrentUser, Path<i32>, Query<WeekQuery>))
+    -> Box<Future<Item = Json<WeekResponse>, Error = actix_web::Error>>
+{
+    debug!("Request to read timetable of teacher with id of {}.", id.as_ref());
+    request.state().db
+        .send(WeekRequest::Teacher(id.into_inner(), query.into_inner()))
+        .from_err()
+        .and_then(|res| res.map(Json).map_err(actix_web::Error::from))
+        .responder()
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct WeekQuery {
+    pub school_year: Option<String>
+}
+
+impl Validate for WeekQuery {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut validator = Validator::new();
+        if let Some(ref year) = self.school_year {
+            validator.school_year("school_year", year);
+        }
+        validator.finish()
+    }
+}
+
+/// Named after the class or the teacher's login.
+#[derive(Serialize, Debug)]
+pub struct WeekResponse {
+    pub name: String,
+    pub school_year: String,
+    pub lessons: Vec<Lesson>
+}
+
+pub enum WeekRequest {
+    Class(i32),
+    Teacher(i32, WeekQuery),
+}
+
+impl Message for WeekRequest {
+    type Result = Result<WeekResponse, ApiError>;
+}
+
+impl Handler<WeekRequest> for Database {
+    type Result = Result<WeekResponse, ApiError>;
+
+    fn handle(&mut self, msg: WeekRequest, _: &mut Self::Context) -> Self::Result {
+        use crate::schema::{classes, users};
+        let conn = self.conn()?;
+        match msg {
+            WeekRequest::Class(class) => {
+                let (name, school_year) = classes::table
+                    .find(class)
+                    .select((classes::name, classes::school_year))
+                    .first::<(String, String)>(&conn)
+                    .optional()?
+                    .ok_or_else(|| ApiError::NotFound(format!("Class with id of {} not found.", class)))?;
+                let lessons = lessons(&conn, &Week::Class(class))?;
+                Ok(WeekResponse { name, school_year, lessons })
+            }
+            WeekRequest::Teacher(teacher, query) => {
+                query.validate()?;
+                let (name, role) = users::table
+                    .find(teacher)
+                    .select((users::login, users::role))
+                    .first::<(String, Role)>(&conn)
+                    .optional()?
+                    .ok_or_else(|| ApiError::NotFound(format!("User with id of {} not found.", teacher)))?;
+                if role != Role::Teacher {
+                    return Err(ApiError::NotFound(format!("Teacher with id of {} not found.", teacher)));
+                }
+                let school_year = query.school_year
+                    .unwrap_or_else(|| school_year_of(chrono::Local::today().naive_local()));
+                let lessons = lessons(&conn, &Week::Teacher { id: teacher, school_year: school_year.clone() })?;
+                Ok(WeekResponse { name, school_year, lessons })
+            }
+        }
+    }
+}
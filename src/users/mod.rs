@@ -0,0 +1,16 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+extern crate serde_derive;
+extern crate actix_web;
+
+mod models;
+
+pub use models::{
+    User,
+    display_name,
+    create,
+    read,
+    update,
+    delete
+};
@@ -0,0 +1,113 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use crate::schema::users;
+use crate::database::Database;
+use crate::login::Role;
+use crate::error::ApiError;
+use crate::students::require_live;
+use crate::validation::Validator;
+use actix_web::actix::{Message, Handler};
+use diesel;
+use diesel::pg::PgConnection;
+use diesel::result::{DatabaseErrorKind, Error::DatabaseError};
+
+#[allow(unused_imports)] // Throws errors without this import, but throws warning with it :/
+use diesel::prelude::*;
+
+mod imports;
+
+/// The password hash is never selected, so it can't end up in a response.
+#[derive(Queryable, Serialize, Deserialize, Debug)]
+pub struct User {
+    pub id: i32,
+    pub login: String,
+    pub first_name: Option<String>,
+    pub last_name: Option<String>,
+    pub role: Role,
+    pub student_id: Option<i32>,
+    pub active: bool
+}
+
+/// Columns of `User`, use with `select` and `returning`.
+pub const COLUMNS: (
+    users::id, users::login, users::first_name, users::last_name, users::role, users::student_id, users::active
+) = (users::id, users::login, users::first_name, users::last_name, users::role, users::student_id, users::active);
+
+/// `First Last` once the admin filled in both names, the login until then.
+pub fn display_name(login: String, first_name: Option<String>, last_name: Option<String>) -> String {
+    match (first_name, last_name) {
+        (Some(first_name), Some(last_name)) => format!("{} {}", first_name, last_name),
+        _ => login,
+    }
+}
+
+/// Turns the unique login constraint violation into a 409 Conflict.
+fn login_taken(err: diesel::result::Error, login: &str) -> ApiError {
+    match err {
+        DatabaseError(DatabaseErrorKind::UniqueViolation, _) => {
+            ApiError::Conflict(format!("Login `{}` is already taken.", login))
+        }
+        err => ApiError::from(err)
+    }
+}
+
+pub const MAX_LOGIN_LENGTH: usize = 100;
+pub const MIN_PASSWORD_LENGTH: usize = 8;
+
+/// Names are optional, but not empty when they're given.
+fn check_names(validator: &mut Validator, first_name: Option<&str>, last_name: Option<&str>) {
+    if let Some(first_name) = first_name {
+        validator.name("first_name", first_name);
+    }
+    if let Some(last_name) = last_name {
+        validator.name("last_name", last_name);
+    }
+}
+
+/// Login isn't empty and the password is long enough, once trimmed like at login.
+fn check_credentials(validator: &mut Validator, login: Option<&str>, password: Option<&str>) {
+    if let Some(login) = login {
+        validator.text("login", login, MAX_LOGIN_LENGTH);
+    }
+    if let Some(password) = password {
+        validator.check(
+            "password",
+            password.trim().chars().count() >= MIN_PASSWORD_LENGTH,
+            &format!("must be at least {} characters long", MIN_PASSWORD_LENGTH)
+        );
+    }
+}
+
+/// `student` accounts belong to a student, no other account does.
+fn check_student(validator: &mut Validator, role: Role, student_id: Option<i32>) {
+    if role == Role::Student {
+        validator.check("student_id", student_id.is_some(), "is required for `student` accounts");
+    } else {
+        validator.check("student_id", student_id.is_none(), "only `student` accounts belong to a student");
+    }
+}
+
+/// 404 if the account would belong to a student that doesn't exist or was deleted.
+fn require_student_of(conn: &PgConnection, student_id: Option<i32>) -> Result<(), ApiError> {
+    match student_id {
+        Some(student) => require_live(conn, student),
+        None => Ok(()),
+    }
+}
+
+/* Create */
+mod create;
+pub use create::*;
+
+/* Read */
+mod read;
+pub use read::*;
+
+/* Update */
+mod update;
+pub use update::*;
+
+/* Delete */
+mod delete;
+pub use delete::*;
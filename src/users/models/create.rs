@@ -0,0 +1,103 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use super::*;
+use super::imports::*;
+
+/// This is the create handler.
+/// 
+/// Admins only.
+pub fn create((request, user, new_user): (HttpRequest<State>, CurrentUser, Json<CreateRequest>)) 
+    -> Box<Future<Item = Json<CreateResponse>, Error = actix_web::Error>> 
+{
+    debug!("Request to create user with login: {}", &new_user.login);
+    request.state().db
+        .send(CreateUser {
+            user,
+            fields: new_user.into_inner()
+        })
+        .from_err()
+        .and_then(|res| {
+            res.map(|created| {
+                info!("Successfully added user with id of {}", created.id);
+                Json(CreateResponse {
+                    message: "Success!".to_string(),
+                    user: Some(created)
+                })
+            }).map_err(actix_web::Error::from)
+        })
+        .responder()
+}
+
+/// No `Debug`, so the password can't end up in the logs.
+#[derive(Deserialize)]
+pub struct CreateRequest {
+    login: String,
+    password: String,
+    first_name: Option<String>,
+    last_name: Option<String>,
+    role: Role,
+    /// The student row of a `student` account, required for them and only for them
+    student_id: Option<i32>
+}
+
+impl Validate for CreateRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut validator = Validator::new();
+        check_credentials(&mut validator, Some(&self.login), Some(&self.password));
+        check_names(&mut validator, self.first_name.as_ref().map(String::as_str), self.last_name.as_ref().map(String::as_str));
+        check_student(&mut validator, self.role, self.student_id);
+        validator.finish()
+    }
+}
+
+/// id should be set automatically
+#[derive(Insertable)]
+#[table_name="users"]
+struct NewUser {
+    login: String,
+    password_hash: String,
+    first_name: Option<String>,
+    last_name: Option<String>,
+    role: Role,
+    student_id: Option<i32>
+}
+
+#[derive(Serialize)]
+pub struct CreateResponse {
+    pub message: String,
+    pub user: Option<User>
+}
+
+pub struct CreateUser {
+    pub user: CurrentUser,
+    pub fields: CreateRequest,
+}
+
+impl Message for CreateUser {
+    type Result = Result<User, ApiError>;
+}
+
+impl Handler<CreateUser> for Database {
+    type Result = Result<User, ApiError>;
+
+    fn handle(&mut self, msg: CreateUser, _: &mut Self::Context) -> Self::Result {
+        require_role(&msg.user, &[Role::Admin])?;
+        msg.fields.validate()?;
+        let conn = self.conn()?;
+        require_student_of(&conn, msg.fields.student_id)?;
+        let new_user = NewUser {
+            login: msg.fields.login.trim().to_owned(),
+            password_hash: hash_password(msg.fields.password.trim()),
+            first_name: msg.fields.first_name.as_ref().map(|name| name.trim().to_owned()),
+            last_name: msg.fields.last_name.as_ref().map(|name| name.trim().to_owned()),
+            role: msg.fields.role,
+            student_id: msg.fields.student_id,
+        };
+        diesel::insert_into(users::table)
+            .values(&new_user)
+            .returning(COLUMNS)
+            .get_result::<User>(&conn)
+            .map_err(|err| login_taken(err, &new_user.login))
+    }
+}
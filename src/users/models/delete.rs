@@ -0,0 +1,65 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use super::*;
+use super::imports::*;
+
+/// This is the delete handler
+/// 
+/// Accounts are only deactivated, so whatever they're linked to stays intact.
+/// Admins only, 404 if there's no such user.
+pub fn delete((request, user, id): (HttpRequest<State>, CurrentUser, Path<i32>)) 
+    -> Box<Future<Item = HttpResponse, Error = actix_web::Error>> 
+{
+    debug!("Request to deactivate user with id of {}.", id.as_ref());
+    
+    request.state().db
+        .send(DeactivateUser{id: id.clone(), user})
+        .from_err()
+        .and_then(move |res| {
+            res.map(|_| {
+                info!("User with id of {} successfully deactivated.", id);
+                HttpResponse::Ok()
+                    .json(DeleteResponse {
+                        message: format!("Deactivated user with id: {:?}.", id)
+                    })
+            }).map_err(actix_web::Error::from)
+        }).responder()
+}
+
+pub struct DeactivateUser {
+    pub id: i32,
+    pub user: CurrentUser,
+}
+
+impl Message for DeactivateUser {
+    type Result = Result<usize, ApiError>;
+}
+
+impl Handler<DeactivateUser> for Database {
+    type Result = Result<usize, ApiError>;
+
+    fn handle(&mut self, msg: DeactivateUser, _: &mut Self::Context) -> Self::Result {
+        use crate::schema::sessions;
+        require_role(&msg.user, &[Role::Admin])?;
+        if msg.id == msg.user.id {
+            return Err(ApiError::Forbidden("You can't deactivate your own account.".to_string()));
+        }
+        let conn = self.conn()?;
+        conn.transaction::<_, ApiError, _>(|| {
+            let num_of_deactivated = diesel::update(users::table.find(msg.id))
+                .set(users::active.eq(false))
+                .execute(&conn)?;
+            if num_of_deactivated == 0 {
+                return Err(ApiError::NotFound(format!("User with id of {} not found.", msg.id)));
+            }
+            diesel::delete(sessions::table.filter(sessions::user_id.eq(msg.id))).execute(&conn)?;
+            Ok(num_of_deactivated)
+        })
+    }
+}
+
+#[derive(Serialize)]
+pub struct DeleteResponse {
+    pub message: String,
+}
@@ -0,0 +1,18 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+pub use actix_web::{
+    Json,
+    HttpRequest,
+    HttpResponse,
+    Path,
+    AsyncResponder,
+    error
+};
+pub use log::{debug, error, info, warn};
+pub use futures::future::Future;
+
+pub use crate::State;
+pub use crate::error::ApiError;
+pub use crate::login::{CurrentUser, Role, require_role, hash_password};
+pub use crate::validation::{Validate, ValidationErrors, Validator, require_changes, deserialize_nullable};
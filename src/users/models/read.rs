@@ -0,0 +1,37 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use super::*;
+use super::imports::*;
+
+/// Lists every account, deactivated ones included.
+/// 
+/// Admins only.
+pub fn read((request, user): (HttpRequest<State>, CurrentUser)) 
+    -> Box<Future<Item = Json<Vec<User>>, Error = actix_web::Error>> 
+{
+    debug!("Request to read all users.");
+    request.state().db
+        .send(ReadRequest{user})
+        .from_err()
+        .and_then(|res| res.map(Json).map_err(actix_web::Error::from))
+        .responder()
+}
+
+pub struct ReadRequest {
+    pub user: CurrentUser,
+}
+
+impl Message for ReadRequest {
+    type Result = Result<Vec<User>, ApiError>;
+}
+
+impl Handler<ReadRequest> for Database {
+    type Result = Result<Vec<User>, ApiError>;
+
+    fn handle(&mut self, msg: ReadRequest, _: &mut Self::Context) -> Self::Result {
+        require_role(&msg.user, &[Role::Admin])?;
+        let conn = self.conn()?;
+        Ok(users::table.select(COLUMNS).order(users::id).load::<User>(&conn)?)
+    }
+}
@@ -0,0 +1,162 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use super::*;
+use super::imports::*;
+
+/// This is the update handler
+/// 
+/// Admins only. Setting `active` back to `true` reactivates the account.
+/// Admins can't deactivate themselves or take away their own admin role.
+pub fn update((request, user, id, updated_user): (HttpRequest<State>, CurrentUser, Path<i32>, Json<UpdateRequest>)) 
+    -> Box<Future<Item = HttpResponse, Error = actix_web::Error>> 
+{
+    request.state().db
+        .send(UpdateUser {
+            id: id.clone(),
+            user,
+            fields: updated_user.into_inner()
+        })
+        .from_err()
+        .and_then(move |updated_user| {
+            match updated_user {
+                Ok(updated) => Ok(HttpResponse::Ok().json(
+                    UpdateResponse{ 
+                        message: format!("Updated user with id: {:?}.", id),
+                        user: Some(updated),
+                    }
+                )),
+                Err(ApiError::NotFound(_)) => Err(actix_web::Error::from(
+                    ApiError::NotFound(format!("User with id of {} does not exist.", id))
+                )),
+                Err(e) => Err(actix_web::Error::from(e))
+            }
+        }).responder()
+}
+
+/// `"student_id": null` unlinks the account from its student and `null` names remove them,
+/// leaving them out keeps them.
+#[derive(Deserialize)]
+pub struct UpdateRequest {
+    pub login: Option<String>,
+    pub password: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    pub first_name: Option<Option<String>>,
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    pub last_name: Option<Option<String>>,
+    pub role: Option<Role>,
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    pub student_id: Option<Option<i32>>,
+    pub active: Option<bool>
+}
+
+/// Only the fields that are being changed are checked, whether the account
+/// still fits its student is checked against the stored one, see `check_student`.
+impl Validate for UpdateRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut validator = Validator::new();
+        check_credentials(&mut validator, self.login.as_ref().map(String::as_str), self.password.as_ref().map(String::as_str));
+        check_names(
+            &mut validator,
+            self.first_name.as_ref().and_then(Option::as_ref).map(String::as_str),
+            self.last_name.as_ref().and_then(Option::as_ref).map(String::as_str)
+        );
+        validator.finish()
+    }
+}
+
+#[derive(AsChangeset)]
+#[table_name="users"]
+struct UserChanges {
+    login: Option<String>,
+    password_hash: Option<String>,
+    first_name: Option<Option<String>>,
+    last_name: Option<Option<String>>,
+    role: Option<Role>,
+    student_id: Option<Option<i32>>,
+    active: Option<bool>
+}
+
+pub struct UpdateUser {
+    pub id: i32,
+    pub user: CurrentUser,
+    pub fields: UpdateRequest,
+}
+
+impl Message for UpdateUser {
+    type Result = Result<User, ApiError>;
+}
+
+impl Handler<UpdateUser> for Database {
+    type Result = Result<User, ApiError>;
+
+    /// A new password or deactivation also logs the user out everywhere.
+    fn handle(&mut self, msg: UpdateUser, _: &mut Self::Context) -> Self::Result {
+        use crate::schema::sessions;
+        require_role(&msg.user, &[Role::Admin])?;
+        msg.fields.validate()?;
+        let user_id = msg.id;
+        let fields = msg.fields;
+        require_changes(
+            fields.login.is_some() || fields.password.is_some() || fields.first_name.is_some()
+                || fields.last_name.is_some() || fields.role.is_some()
+                || fields.student_id.is_some() || fields.active.is_some()
+        )?;
+        if user_id == msg.user.id {
+            if fields.active == Some(false) {
+                return Err(ApiError::Forbidden("You can't deactivate your own account.".to_string()));
+            }
+            if fields.role.map_or(false, |role| role != Role::Admin) {
+                return Err(ApiError::Forbidden("You can't take away your own admin role.".to_string()));
+            }
+        }
+        let conn = self.conn()?;
+
+        let (current_role, current_student) = users::table
+            .find(user_id)
+            .select((users::role, users::student_id))
+            .first::<(Role, Option<i32>)>(&conn)
+            .optional()?
+            .ok_or_else(|| ApiError::NotFound(format!("User with id of {} does not exist.", user_id)))?;
+        let mut validator = Validator::new();
+        check_student(
+            &mut validator,
+            fields.role.unwrap_or(current_role),
+            fields.student_id.unwrap_or(current_student)
+        );
+        validator.finish()?;
+        if let Some(student) = fields.student_id {
+            require_student_of(&conn, student)?;
+        }
+
+        let end_sessions = fields.password.is_some() || fields.active == Some(false);
+        let new_login = fields.login.map(|login| login.trim().to_owned());
+        let changes = UserChanges {
+            login: new_login.clone(),
+            password_hash: fields.password.map(|password| hash_password(password.trim())),
+            first_name: fields.first_name.map(|name| name.map(|name| name.trim().to_owned())),
+            last_name: fields.last_name.map(|name| name.map(|name| name.trim().to_owned())),
+            role: fields.role,
+            student_id: fields.student_id,
+            active: fields.active,
+        };
+
+        conn.transaction(|| {
+            let updated = diesel::update(users::table.find(user_id))
+                .set(&changes)
+                .returning(COLUMNS)
+                .get_result::<User>(&conn)
+                .map_err(|err| login_taken(err, new_login.as_ref().map_or("", String::as_str)))?;
+            if end_sessions {
+                diesel::delete(sessions::table.filter(sessions::user_id.eq(user_id))).execute(&conn)?;
+            }
+            Ok(updated)
+        })
+    }
+}
+
+#[derive(Serialize)]
+pub struct UpdateResponse {
+    pub message: String,
+    pub user: Option<User>
+}
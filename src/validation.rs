@@ -0,0 +1,138 @@
+//! MIT License
+//! Copyright (c) 2019 Jakub Koralewski
+
+use serde::{Deserialize, Deserializer};
+
+use crate::error::ApiError;
+
+/// Checks a request body before it gets anywhere near the database.
+pub trait Validate {
+    fn validate(&self) -> Result<(), ValidationErrors>;
+}
+
+/// Tells a missing field (`None`) apart from an explicit `null` (`Some(None)`),
+/// use with `#[serde(default, deserialize_with = "deserialize_nullable")]`.
+pub fn deserialize_nullable<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+    where D: Deserializer<'de>, T: Deserialize<'de>
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+/// Update request bodies have to change at least one field, diesel can't save an empty changeset.
+pub fn require_changes(changes_something: bool) -> Result<(), ApiError> {
+    if changes_something {
+        Ok(())
+    } else {
+        Err(ApiError::BadRequest("Nothing to update, at least one field is required.".to_string()))
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+/// Every field that failed, becomes `ApiError::Validation`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ValidationErrors {
+    pub message: String,
+    pub errors: Vec<FieldError>,
+}
+
+/// Collects every failed rule, so they can all be reported at once.
+///
+/// ```ignore
+/// Validator::new()
+///     .name("first_name", &self.first_name)
+///     .name("last_name", &self.last_name)
+///     .finish()
+/// ```
+#[derive(Default)]
+pub struct Validator {
+    errors: Vec<FieldError>,
+}
+
+pub const MAX_NAME_LENGTH: usize = 100;
+pub const MAX_EMAIL_LENGTH: usize = 254;
+
+impl Validator {
+    pub fn new() -> Self {
+        Validator::default()
+    }
+
+    /// Fails the field with the message unless `ok`.
+    pub fn check(&mut self, field: &str, ok: bool, message: &str) -> &mut Self {
+        if !ok {
+            self.errors.push(FieldError {
+                field: field.to_string(),
+                message: message.to_string(),
+            });
+        }
+        self
+    }
+
+    /// Not empty once trimmed, and at most `max` characters.
+    pub fn text(&mut self, field: &str, value: &str, max: usize) -> &mut Self {
+        let value = value.trim();
+        if value.is_empty() {
+            self.check(field, false, "must not be empty")
+        } else {
+            self.check(field, value.chars().count() <= max, &format!("must be at most {} characters long", max))
+        }
+    }
+
+    /// A person's first or last name.
+    pub fn name(&mut self, field: &str, value: &str) -> &mut Self {
+        self.text(field, value, MAX_NAME_LENGTH)
+    }
+
+    /// One or two digits followed by up to three lowercase letters, e.g. `3d` or `1lo`.
+    pub fn class_name(&mut self, field: &str, value: &str) -> &mut Self {
+        let digits = value.chars().take_while(|c| c.is_ascii_digit()).count();
+        let letters = &value[digits..];
+        let ok = digits >= 1 && digits <= 2
+            && letters.len() <= 3
+            && letters.chars().all(|c| c.is_ascii_lowercase());
+        self.check(field, ok, "must be one or two digits followed by up to three lowercase letters, e.g. `3d`")
+    }
+
+    /// Two consecutive years, e.g. `2018/2019`.
+    pub fn school_year(&mut self, field: &str, value: &str) -> &mut Self {
+        let years: Vec<Option<u16>> = value
+            .split('/')
+            .map(|year| if year.len() == 4 { year.parse().ok() } else { None })
+            .collect();
+        let ok = match years.as_slice() {
+            [Some(first), Some(second)] => *second == *first + 1,
+            _ => false,
+        };
+        self.check(field, ok, "must be two consecutive years, e.g. `2018/2019`")
+    }
+
+    /// Between `min` and `max`, both inclusive.
+    pub fn range(&mut self, field: &str, value: i64, min: i64, max: i64) -> &mut Self {
+        self.check(field, value >= min && value <= max, &format!("must be between {} and {}", min, max))
+    }
+
+    /// Something, an `@` and a domain with a dot in it.
+    pub fn email(&mut self, field: &str, value: &str) -> &mut Self {
+        let ok = match value.trim().rsplitn(2, '@').collect::<Vec<_>>().as_slice() {
+            [domain, local] => !local.is_empty() && domain.contains('.')
+                && !domain.starts_with('.') && !domain.ends_with('.'),
+            _ => false,
+        };
+        self.check(field, ok && value.len() <= MAX_EMAIL_LENGTH, "must be an email address")
+    }
+
+    pub fn finish(&mut self) -> Result<(), ValidationErrors> {
+        if self.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationErrors {
+                message: "Validation failed.".to_string(),
+                errors: self.errors.split_off(0),
+            })
+        }
+    }
+}